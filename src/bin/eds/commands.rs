@@ -0,0 +1,306 @@
+use serde_json::{Value, json};
+
+use eds_parser::{
+    ChangeKind, DCFFile, EDSFile, EDSObject, check_device_profile, check_eds_format,
+    eds_value_to_string, load_file, validate_standard_objects,
+};
+
+use crate::Report;
+
+fn load(path: &str) -> Result<EDSFile, String> {
+    load_file(path).map_err(|e| format!("{}: {}", path, e))
+}
+
+fn read(path: &str) -> Result<String, String> {
+    std::fs::read_to_string(path).map_err(|e| format!("{}: {}", path, e))
+}
+
+fn write(path: &str, data: &str) -> Result<(), String> {
+    std::fs::write(path, data).map_err(|e| format!("{}: {}", path, e))
+}
+
+fn hex(value: u32) -> String {
+    format!("0x{:08X}", value)
+}
+
+fn default_str(obj: &EDSObject) -> Option<String> {
+    obj.get_default()
+        .and_then(|v| eds_value_to_string(v, obj.get_data_type()?))
+}
+
+pub fn info(path: &str) -> Result<Report, String> {
+    let file = load(path)?;
+    let (fi, di) = (&file.file_info, &file.device_info);
+    let baudrates: Vec<String> = di.get_baudrates().iter().map(|b| b.to_string()).collect();
+    let rows = [
+        ("File name", fi.file_name.clone()),
+        (
+            "File version",
+            format!("{}.{}", fi.file_version, fi.file_revision),
+        ),
+        ("EDS version", fi.eds_version.to_string()),
+        ("Description", fi.description.clone()),
+        (
+            "Created",
+            format!(
+                "{} by {}",
+                fi.creation.format("%Y-%m-%d %H:%M"),
+                fi.created_by
+            ),
+        ),
+        (
+            "Modified",
+            format!(
+                "{} by {}",
+                fi.modification.format("%Y-%m-%d %H:%M"),
+                fi.modified_by
+            ),
+        ),
+        (
+            "Vendor",
+            format!("{} ({})", di.vendor_name, hex(di.vendor_number)),
+        ),
+        (
+            "Product",
+            format!("{} ({})", di.product_name, hex(di.product_number)),
+        ),
+        ("Revision", hex(di.revision_number)),
+        ("Order code", di.order_code.clone()),
+        ("Baud rates", format!("{} kbit/s", baudrates.join(", "))),
+        ("PDOs", format!("{} RPDO, {} TPDO", di.nr_rpdo, di.nr_tpdo)),
+        (
+            "LSS",
+            if di.lss_supported { "yes" } else { "no" }.to_string(),
+        ),
+        ("Objects", file.get_objects().len().to_string()),
+    ];
+    let text = rows
+        .iter()
+        .map(|(name, value)| format!("{:<14}{}\n", format!("{}:", name), value))
+        .collect();
+    let json = json!({
+        "file_info": {
+            "file_name": fi.file_name,
+            "file_version": fi.file_version,
+            "file_revision": fi.file_revision,
+            "eds_version": fi.eds_version.to_string(),
+            "description": fi.description,
+            "creation": fi.creation.to_rfc3339(),
+            "created_by": fi.created_by,
+            "modification": fi.modification.to_rfc3339(),
+            "modified_by": fi.modified_by,
+        },
+        "device_info": {
+            "vendor_name": di.vendor_name,
+            "vendor_number": di.vendor_number,
+            "product_name": di.product_name,
+            "product_number": di.product_number,
+            "revision_number": di.revision_number,
+            "order_code": di.order_code,
+            "baudrates": di.get_baudrates(),
+            "nr_rpdo": di.nr_rpdo,
+            "nr_tpdo": di.nr_tpdo,
+            "lss_supported": di.lss_supported,
+        },
+        "objects": file.get_objects().len(),
+    });
+    Ok(Report {
+        text,
+        json,
+        success: true,
+    })
+}
+
+fn object_line(obj: &EDSObject, is_sub: bool) -> String {
+    let addr = obj.get_address();
+    let mut line = if is_sub {
+        format!(
+            "  0x{:04X}sub{:X} {}",
+            addr.index,
+            addr.subindex,
+            obj.get_name()
+        )
+    } else {
+        format!("0x{:04X} {}", addr.index, obj.get_name())
+    };
+    match (obj.get_data_type(), obj.get_access_mode()) {
+        (Some(data_type), Some(access_mode)) if obj.get_entries().is_none() => {
+            let mappable = if obj.is_pdo_mappable() {
+                ", mappable"
+            } else {
+                ""
+            };
+            line += &format!(" [{:?} {}{}]", data_type, access_mode, mappable);
+        }
+        _ => line += &format!(" [{}]", obj.get_obj_type()),
+    }
+    if let Some(default) = default_str(obj) {
+        line += &format!(" = {}", default);
+    }
+    line + "\n"
+}
+
+fn object_json(obj: &EDSObject) -> Value {
+    let addr = obj.get_address();
+    let mut value = json!({
+        "index": format!("0x{:04X}", addr.index),
+        "subindex": addr.subindex,
+        "name": obj.get_name(),
+        "object_type": obj.get_obj_type().to_string(),
+        "data_type": obj.get_data_type().map(|t| format!("{:?}", t)),
+        "access_type": obj.get_access_mode().map(|m| m.to_string()),
+        "default": default_str(obj),
+        "pdo_mappable": obj.is_pdo_mappable(),
+    });
+    if let Some(entries) = obj.get_entries() {
+        value["sub_objects"] = entries.iter().map(object_json).collect();
+    }
+    value
+}
+
+pub fn tree(path: &str) -> Result<Report, String> {
+    let file = load(path)?;
+    let mut text = String::new();
+    let mut objects = Vec::new();
+    for obj in file.get_objects() {
+        text += &object_line(obj, false);
+        for entry in obj.get_entries().into_iter().flatten() {
+            text += &object_line(entry, true);
+        }
+        let mut value = object_json(obj);
+        value["list"] = json!(
+            file.get_object_list(obj.get_address().index)
+                .map(|l| l.to_string())
+        );
+        objects.push(value);
+    }
+    Ok(Report {
+        text,
+        json: json!({ "objects": objects }),
+        success: true,
+    })
+}
+
+//Files that fail to parse are reported as invalid with the parse error
+pub fn validate(path: &str) -> Result<Report, String> {
+    let errors: Vec<String> = match load_file(path) {
+        Err(e) => vec![e.to_string()],
+        Ok(file) => validate_standard_objects(&file)
+            .iter()
+            .chain(&check_device_profile(&file).issues)
+            .map(|i| i.to_string())
+            .collect(),
+    };
+    let text = if errors.is_empty() {
+        format!("{} is valid\n", path)
+    } else {
+        let mut text = format!("{} has {} errors\n", path, errors.len());
+        for error in &errors {
+            text += &format!("  {}\n", error);
+        }
+        text
+    };
+    Ok(Report {
+        text,
+        json: json!({ "file": path, "valid": errors.is_empty(), "errors": errors }),
+        success: errors.is_empty(),
+    })
+}
+
+pub fn diff(old: &str, new: &str) -> Result<Report, String> {
+    let diff = eds_parser::diff(&load(old)?, &load(new)?);
+    let info: Vec<Value> = diff
+        .file_info
+        .iter()
+        .chain(&diff.device_info)
+        .map(|c| json!({ "section": c.section, "key": c.key, "old": c.old, "new": c.new }))
+        .collect();
+    let objects: Vec<Value> = diff
+        .objects
+        .iter()
+        .map(|c| {
+            let kind = match c.kind {
+                ChangeKind::Added => "added",
+                ChangeKind::Removed => "removed",
+                ChangeKind::Changed => "changed",
+            };
+            let fields: Vec<Value> = c
+                .fields
+                .iter()
+                .map(|f| json!({ "field": f.field.to_string(), "old": f.old, "new": f.new }))
+                .collect();
+            json!({
+                "index": format!("0x{:04X}", c.address.index),
+                "subindex": c.is_sub_object.then_some(c.address.subindex),
+                "name": c.name,
+                "kind": kind,
+                "fields": fields,
+            })
+        })
+        .collect();
+    Ok(Report {
+        text: diff.to_string(),
+        json: json!({ "info": info, "objects": objects }),
+        success: true,
+    })
+}
+
+pub fn fmt(path: &str, check: bool, write_back: bool) -> Result<Report, String> {
+    let data = read(path)?;
+    let result = check_eds_format(&data).map_err(|e| format!("{}: {}", path, e))?;
+    if check {
+        let text = match result.first_difference {
+            None => format!("{} is formatted\n", path),
+            Some(line) => format!(
+                "{} is not formatted, first difference in line {}\n",
+                path, line
+            ),
+        };
+        return Ok(Report {
+            text,
+            json: json!({
+                "file": path,
+                "formatted": result.is_formatted(),
+                "first_difference": result.first_difference,
+            }),
+            success: result.is_formatted(),
+        });
+    }
+    if write_back {
+        let changed = !result.is_formatted();
+        if changed {
+            write(path, &result.formatted)?;
+        }
+        let text = match changed {
+            true => format!("Formatted {}\n", path),
+            false => format!("{} is already formatted\n", path),
+        };
+        return Ok(Report {
+            text,
+            json: json!({ "file": path, "changed": changed }),
+            success: true,
+        });
+    }
+    Ok(Report {
+        json: json!({ "file": path, "content": result.formatted }),
+        text: result.formatted,
+        success: true,
+    })
+}
+
+pub fn dcf(path: &str, node_id: u8, output: Option<&str>) -> Result<Report, String> {
+    let data = DCFFile::from_eds(load(path)?, node_id).to_dcf_string();
+    let Some(output) = output else {
+        return Ok(Report {
+            json: json!({ "node_id": node_id, "content": data }),
+            text: data,
+            success: true,
+        });
+    };
+    write(output, &data)?;
+    Ok(Report {
+        text: format!("Wrote DCF of node {} to {}\n", node_id, output),
+        json: json!({ "node_id": node_id, "output": output }),
+        success: true,
+    })
+}
@@ -0,0 +1,109 @@
+mod commands;
+
+#[cfg(test)]
+mod tests;
+
+use std::process::ExitCode;
+
+use clap::{Parser, Subcommand, ValueEnum};
+
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+enum OutputFormat {
+    Text,
+    Json,
+}
+
+#[derive(Debug, Parser)]
+#[command(
+    name = "eds",
+    version,
+    about = "Inspect, check and convert EDS and DCF files"
+)]
+struct Cli {
+    #[arg(long, value_enum, default_value = "text", global = true)]
+    format: OutputFormat,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    #[command(about = "Print the file and device info of a file")]
+    Info { file: String },
+    #[command(about = "Print the object dictionary of a file")]
+    Tree { file: String },
+    #[command(about = "Check a file against CiA 306, CiA 301 and its device profile")]
+    Validate { file: String },
+    #[command(about = "Compare two versions of a file")]
+    Diff { old: String, new: String },
+    #[command(about = "Rewrite a file in the canonical layout")]
+    Fmt {
+        file: String,
+        #[arg(
+            long,
+            conflicts_with = "write",
+            help = "Only report whether the file is formatted"
+        )]
+        check: bool,
+        #[arg(long, help = "Write the formatted file back instead of printing it")]
+        write: bool,
+    },
+    #[command(about = "Generate the DCF of a node from an EDS")]
+    Dcf {
+        file: String,
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=127))]
+        node_id: u8,
+        #[arg(long, short, help = "Write the DCF to a file instead of printing it")]
+        output: Option<String>,
+    },
+}
+
+//Output of a command in both formats, failed checks exit with 1
+pub struct Report {
+    pub text: String,
+    pub json: serde_json::Value,
+    pub success: bool,
+}
+
+impl Report {
+    fn render(&self, format: OutputFormat) -> String {
+        match format {
+            OutputFormat::Text => self.text.clone(),
+            OutputFormat::Json => format!("{:#}\n", self.json),
+        }
+    }
+}
+
+fn run(cli: &Cli) -> Result<Report, String> {
+    match &cli.command {
+        Command::Info { file } => commands::info(file),
+        Command::Tree { file } => commands::tree(file),
+        Command::Validate { file } => commands::validate(file),
+        Command::Diff { old, new } => commands::diff(old, new),
+        Command::Fmt { file, check, write } => commands::fmt(file, *check, *write),
+        Command::Dcf {
+            file,
+            node_id,
+            output,
+        } => commands::dcf(file, *node_id, output.as_deref()),
+    }
+}
+
+//Files that can not be read or parsed exit with 2 like usage errors
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    match run(&cli) {
+        Ok(report) => {
+            print!("{}", report.render(cli.format));
+            if report.success {
+                ExitCode::SUCCESS
+            } else {
+                ExitCode::from(1)
+            }
+        }
+        Err(e) => {
+            eprintln!("error: {}", e);
+            ExitCode::from(2)
+        }
+    }
+}
@@ -0,0 +1,163 @@
+use clap::Parser;
+use serde_json::json;
+
+use crate::{Cli, OutputFormat, Report, run};
+
+const EDS: &str = "res/micro-motor.eds";
+
+fn run_args(args: &[&str]) -> Report {
+    let cli = Cli::parse_from([&["eds"], args].concat());
+    run(&cli).expect("Failed to run command!")
+}
+
+fn copy_eds(dir: &tempfile::TempDir) -> String {
+    let path = dir.path().join("micro-motor.eds");
+    std::fs::copy(EDS, &path).expect("Failed to copy eds file!");
+    path.to_string_lossy().to_string()
+}
+
+#[test]
+fn test_info() {
+    let report = run_args(&["info", EDS]);
+    assert!(report.success);
+    assert!(
+        report
+            .text
+            .contains("Vendor:       Roboterclub Aachen e.V. (0xDEADBEEF)\n")
+    );
+    assert!(report.text.contains("Baud rates:   1000 kbit/s\n"));
+    assert_eq!(
+        report.json["device_info"]["vendor_number"],
+        json!(0xDEADBEEFu32)
+    );
+    assert_eq!(report.json["file_info"]["eds_version"], json!("4.0"));
+    assert_eq!(
+        report.json["file_info"]["creation"],
+        json!("2022-10-16T12:01:00+00:00")
+    );
+}
+
+#[test]
+fn test_tree() {
+    let report = run_args(&["tree", EDS]);
+    assert!(report.text.contains("0x1018 Identity Object [Record]\n"));
+    assert!(
+        report
+            .text
+            .contains("  0x1018sub1 Vendor ID [UInt32 ro] = 0x000001A3\n")
+    );
+    assert!(
+        report
+            .text
+            .contains("0x2002 PWM command [Int16 rww, mappable] = 0\n")
+    );
+
+    let objects = report.json["objects"]
+        .as_array()
+        .expect("Failed to get objects!");
+    let identity = objects
+        .iter()
+        .find(|o| o["index"] == "0x1018")
+        .expect("Failed to find 0x1018!");
+    assert_eq!(identity["list"], json!("Mandatory"));
+    assert_eq!(identity["sub_objects"][1]["default"], json!("0x000001A3"));
+}
+
+#[test]
+fn test_validate() {
+    //Records are accepted for 0x1003 and 0x1016, their sub-index 0 is not
+    let report = run_args(&["validate", EDS]);
+    assert!(!report.success);
+    assert_eq!(
+        report.json,
+        json!({
+            "file": EDS,
+            "valid": false,
+            "errors": [
+                "Address {0x1003.0} has data type UInt32 but must be UInt8",
+                "Address {0x1003.0} has access mode ro but must be rw",
+                "Address {0x1016.0} has data type UInt32 but must be UInt8",
+            ],
+        })
+    );
+
+    let dir = tempfile::tempdir().expect("Failed to create directory!");
+    let path = dir.path().join("broken.eds");
+    std::fs::write(&path, "[FileInfo]\nFileName=broken.eds\n").expect("Failed to write file!");
+    let report = run_args(&["validate", &path.to_string_lossy()]);
+    assert!(!report.success);
+    assert!(report.text.contains("has 1 errors\n"));
+}
+
+#[test]
+fn test_diff() {
+    let dir = tempfile::tempdir().expect("Failed to create directory!");
+    let path = copy_eds(&dir);
+    let data = std::fs::read_to_string(&path).expect("Failed to read file!");
+    std::fs::write(
+        &path,
+        data.replace("ParameterName=PWM command", "ParameterName=PWM"),
+    )
+    .expect("Failed to write file!");
+
+    let report = run_args(&["diff", EDS, &path]);
+    assert!(report.success);
+    assert_eq!(
+        report.text,
+        "~ 0x2002 PWM\n    ParameterName: PWM command -> PWM\n"
+    );
+    assert_eq!(
+        report.json["objects"][0],
+        json!({
+            "index": "0x2002",
+            "subindex": null,
+            "name": "PWM",
+            "kind": "changed",
+            "fields": [{ "field": "ParameterName", "old": "PWM command", "new": "PWM" }],
+        })
+    );
+}
+
+#[test]
+fn test_fmt() {
+    let dir = tempfile::tempdir().expect("Failed to create directory!");
+    let path = copy_eds(&dir);
+    let report = run_args(&["fmt", "--check", &path]);
+    assert!(!report.success);
+    assert_eq!(report.json["first_difference"], json!(2));
+
+    let report = run_args(&["fmt", "--write", &path]);
+    assert_eq!(report.json["changed"], json!(true));
+    let report = run_args(&["fmt", "--check", &path]);
+    assert!(report.success);
+    assert!(report.text.ends_with("is formatted\n"));
+    assert_eq!(run_args(&["fmt", &path]).text, run_args(&["fmt", EDS]).text);
+}
+
+#[test]
+fn test_dcf() {
+    let report = run_args(&["dcf", EDS, "--node-id", "5"]);
+    assert!(report.text.contains("[DeviceComissioning]\nNodeID=0x5\n"));
+    let dcf = eds_parser::load_dcf_string(&report.text).expect("Failed to parse dcf!");
+    assert_eq!(dcf.get_node_id(), 5);
+
+    let dir = tempfile::tempdir().expect("Failed to create directory!");
+    let output = dir.path().join("node5.dcf").to_string_lossy().to_string();
+    let report = run_args(&["dcf", EDS, "--node-id", "5", "-o", &output]);
+    assert_eq!(report.json["output"], json!(output));
+    let dcf = eds_parser::load_dcf_file(&output).expect("Failed to load dcf!");
+    assert_eq!(dcf.get_node_id(), 5);
+
+    assert!(Cli::try_parse_from(["eds", "dcf", EDS, "--node-id", "0"]).is_err());
+}
+
+#[test]
+fn test_render() {
+    let report = run_args(&["--format", "json", "validate", EDS]);
+    let cli = Cli::parse_from(["eds", "validate", EDS, "--format", "json"]);
+    assert_eq!(cli.format, OutputFormat::Json);
+    let rendered = report.render(cli.format);
+    let parsed: serde_json::Value = serde_json::from_str(&rendered).expect("Failed to parse json!");
+    assert_eq!(parsed, report.json);
+    assert_eq!(report.render(OutputFormat::Text), report.text);
+}
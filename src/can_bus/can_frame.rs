@@ -0,0 +1,63 @@
+use crate::CanBusError;
+use crate::can_log::CanLogFrame;
+
+pub const CAN_MAX_PAYLOAD: usize = 8;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct CanFrame {
+    pub cob_id: u32,
+    pub extended: bool,
+    pub rtr: bool,
+    pub data: Vec<u8>,
+}
+
+impl CanFrame {
+    pub fn new(cob_id: u32, data: &[u8]) -> CanFrame {
+        CanFrame {
+            cob_id,
+            extended: false,
+            rtr: false,
+            data: data.to_vec(),
+        }
+    }
+
+    pub fn new_extended(cob_id: u32, data: &[u8]) -> CanFrame {
+        CanFrame {
+            cob_id,
+            extended: true,
+            rtr: false,
+            data: data.to_vec(),
+        }
+    }
+
+    pub fn new_rtr(cob_id: u32) -> CanFrame {
+        CanFrame {
+            cob_id,
+            extended: false,
+            rtr: true,
+            data: Vec::new(),
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), CanBusError> {
+        let max_id = if self.extended { 0x1FFFFFFF } else { 0x7FF };
+        if self.cob_id > max_id || self.data.len() > CAN_MAX_PAYLOAD {
+            return Err(CanBusError::InvalidFrame {
+                cob_id: self.cob_id,
+                length: self.data.len(),
+            });
+        }
+        Ok(())
+    }
+}
+
+impl From<&CanLogFrame> for CanFrame {
+    fn from(value: &CanLogFrame) -> Self {
+        CanFrame {
+            cob_id: value.cob_id,
+            extended: value.extended,
+            rtr: value.rtr,
+            data: value.data.clone(),
+        }
+    }
+}
@@ -0,0 +1,203 @@
+use std::sync::{Arc, Condvar, Mutex};
+use std::time::{Duration, Instant};
+
+use super::{CanBus, CanFrame};
+use crate::CanBusError;
+
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct LoopbackFaults {
+    //Probabilities between 0 and 1 applied to every sent frame
+    pub drop_probability: f64,
+    pub corrupt_probability: f64,
+    pub delay: Duration,
+    //Additional random delay between 0 and jitter, may reorder frames
+    pub jitter: Duration,
+    pub seed: u64,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub struct LoopbackStats {
+    pub sent: u64,
+    pub dropped: u64,
+    pub corrupted: u64,
+}
+
+#[derive(Debug)]
+struct LoopbackState {
+    queues: Vec<Option<Vec<(Instant, CanFrame)>>>,
+    faults: LoopbackFaults,
+    rng: u64,
+    drop_next: u32,
+    stats: LoopbackStats,
+}
+
+impl LoopbackState {
+    //xorshift64, only used to make fault injection reproducible
+    fn next_random(&mut self) -> u64 {
+        let mut x = self.rng;
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        self.rng = x;
+        x
+    }
+
+    fn next_f64(&mut self) -> f64 {
+        (self.next_random() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    fn apply_faults(&mut self, frame: &CanFrame) -> Option<(Duration, CanFrame)> {
+        if self.drop_next > 0 {
+            self.drop_next -= 1;
+            self.stats.dropped += 1;
+            return None;
+        }
+        if self.faults.drop_probability > 0.0 && self.next_f64() < self.faults.drop_probability {
+            self.stats.dropped += 1;
+            return None;
+        }
+        let mut frame = frame.clone();
+        if !frame.data.is_empty()
+            && self.faults.corrupt_probability > 0.0
+            && self.next_f64() < self.faults.corrupt_probability
+        {
+            let bit = self.next_random() as usize % (frame.data.len() * 8);
+            frame.data[bit / 8] ^= 1 << (bit % 8);
+            self.stats.corrupted += 1;
+        }
+        let mut delay = self.faults.delay;
+        if !self.faults.jitter.is_zero() {
+            delay += self.faults.jitter.mul_f64(self.next_f64());
+        }
+        Some((delay, frame))
+    }
+}
+
+//In-process CAN bus, every frame sent by a node is received by all other
+//connected nodes
+#[derive(Debug, Clone)]
+pub struct LoopbackBus {
+    shared: Arc<(Mutex<LoopbackState>, Condvar)>,
+}
+
+#[derive(Debug)]
+pub struct LoopbackNode {
+    shared: Arc<(Mutex<LoopbackState>, Condvar)>,
+    id: usize,
+}
+
+impl Default for LoopbackBus {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LoopbackBus {
+    pub fn new() -> LoopbackBus {
+        LoopbackBus {
+            shared: Arc::new((
+                Mutex::new(LoopbackState {
+                    queues: Vec::new(),
+                    faults: LoopbackFaults::default(),
+                    rng: 1,
+                    drop_next: 0,
+                    stats: LoopbackStats::default(),
+                }),
+                Condvar::new(),
+            )),
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, LoopbackState> {
+        self.shared.0.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    pub fn connect(&self) -> LoopbackNode {
+        let mut state = self.lock();
+        state.queues.push(Some(Vec::new()));
+        LoopbackNode {
+            shared: self.shared.clone(),
+            id: state.queues.len() - 1,
+        }
+    }
+
+    pub fn set_faults(&self, faults: LoopbackFaults) {
+        let mut state = self.lock();
+        state.rng = faults.seed.max(1);
+        state.faults = faults;
+    }
+
+    //Drops the next count frames regardless of the configured probabilities
+    pub fn drop_next(&self, count: u32) {
+        self.lock().drop_next = count;
+    }
+
+    pub fn get_stats(&self) -> LoopbackStats {
+        self.lock().stats
+    }
+}
+
+impl LoopbackNode {
+    fn lock(&self) -> std::sync::MutexGuard<'_, LoopbackState> {
+        self.shared.0.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl Drop for LoopbackNode {
+    fn drop(&mut self) {
+        self.lock().queues[self.id] = None;
+    }
+}
+
+impl CanBus for LoopbackNode {
+    fn send(&mut self, frame: &CanFrame) -> Result<(), CanBusError> {
+        frame.validate()?;
+        let mut state = self.lock();
+        state.stats.sent += 1;
+        if let Some((delay, frame)) = state.apply_faults(frame) {
+            let deliver_at = Instant::now() + delay;
+            for (id, queue) in state.queues.iter_mut().enumerate() {
+                if id == self.id {
+                    continue;
+                }
+                if let Some(queue) = queue {
+                    let pos = queue.partition_point(|(t, _)| *t <= deliver_at);
+                    queue.insert(pos, (deliver_at, frame.clone()));
+                }
+            }
+        }
+        drop(state);
+        self.shared.1.notify_all();
+        Ok(())
+    }
+
+    fn recv(&mut self, timeout: Duration) -> Result<Option<CanFrame>, CanBusError> {
+        let deadline = Instant::now() + timeout;
+        let mut state = self.lock();
+        loop {
+            let now = Instant::now();
+            let queue = state.queues[self.id]
+                .as_mut()
+                .ok_or(CanBusError::Disconnected)?;
+            let next = queue.first().map(|(t, _)| *t);
+            if let Some(t) = next
+                && t <= now
+            {
+                return Ok(Some(queue.remove(0).1));
+            }
+            if now >= deadline {
+                return Ok(None);
+            }
+            let wait = match next {
+                Some(t) => (t - now).min(deadline - now),
+                None => deadline - now,
+            };
+            state = self
+                .shared
+                .1
+                .wait_timeout(state, wait)
+                .unwrap_or_else(|e| e.into_inner())
+                .0;
+        }
+    }
+}
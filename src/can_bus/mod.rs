@@ -0,0 +1,18 @@
+mod can_frame;
+mod loopback_bus;
+
+#[cfg(test)]
+mod tests;
+
+pub use can_frame::CanFrame;
+pub use loopback_bus::*;
+
+use std::time::Duration;
+
+use crate::CanBusError;
+
+pub trait CanBus {
+    fn send(&mut self, frame: &CanFrame) -> Result<(), CanBusError>;
+    //Returns None if no frame arrived within the timeout
+    fn recv(&mut self, timeout: Duration) -> Result<Option<CanFrame>, CanBusError>;
+}
@@ -0,0 +1,118 @@
+use std::thread;
+use std::time::Duration;
+
+use crate::CanBusError;
+use crate::can_bus::{CanBus, CanFrame, LoopbackBus, LoopbackFaults};
+
+const TIMEOUT: Duration = Duration::from_millis(500);
+
+#[test]
+fn test_loopback() {
+    let bus = LoopbackBus::new();
+    let mut a = bus.connect();
+    let mut b = bus.connect();
+    let mut c = bus.connect();
+    let frame = CanFrame::new(0x181, &[1, 2, 3]);
+    a.send(&frame).expect("Failed to send frame!");
+    assert_eq!(b.recv(TIMEOUT), Ok(Some(frame.clone())));
+    assert_eq!(c.recv(TIMEOUT), Ok(Some(frame)));
+    assert_eq!(a.recv(Duration::ZERO), Ok(None));
+    assert_eq!(b.recv(Duration::from_millis(10)), Ok(None));
+
+    assert_eq!(
+        a.send(&CanFrame::new(0x800, &[])),
+        Err(CanBusError::InvalidFrame {
+            cob_id: 0x800,
+            length: 0
+        })
+    );
+    assert!(a.send(&CanFrame::new_extended(0x800, &[])).is_ok());
+    assert_eq!(
+        a.send(&CanFrame::new(0x100, &[0; 9])),
+        Err(CanBusError::InvalidFrame {
+            cob_id: 0x100,
+            length: 9
+        })
+    );
+}
+
+#[test]
+fn test_threads() {
+    let bus = LoopbackBus::new();
+    let mut master = bus.connect();
+    let mut slave = bus.connect();
+    let handle = thread::spawn(move || {
+        let request = slave
+            .recv(TIMEOUT)
+            .expect("Failed to receive frame!")
+            .expect("Missing frame!");
+        slave
+            .send(&CanFrame::new(0x580 + request.data[0] as u32, &[]))
+            .expect("Failed to send frame!");
+    });
+    master
+        .send(&CanFrame::new(0x600, &[5]))
+        .expect("Failed to send frame!");
+    let response = master.recv(TIMEOUT).expect("Failed to receive frame!");
+    assert_eq!(response.map(|f| f.cob_id), Some(0x585));
+    handle.join().expect("Slave thread failed!");
+}
+
+#[test]
+fn test_faults() {
+    let bus = LoopbackBus::new();
+    let mut a = bus.connect();
+    let mut b = bus.connect();
+    let frame = CanFrame::new(0x201, &[0x55; 8]);
+
+    bus.drop_next(2);
+    for _ in 0..3 {
+        a.send(&frame).expect("Failed to send frame!");
+    }
+    assert_eq!(b.recv(TIMEOUT), Ok(Some(frame.clone())));
+    assert_eq!(b.recv(Duration::ZERO), Ok(None));
+
+    bus.set_faults(LoopbackFaults {
+        corrupt_probability: 1.0,
+        seed: 42,
+        ..Default::default()
+    });
+    a.send(&frame).expect("Failed to send frame!");
+    let corrupted = b
+        .recv(TIMEOUT)
+        .expect("Failed to receive frame!")
+        .expect("Missing frame!");
+    let flipped: u32 = corrupted
+        .data
+        .iter()
+        .zip(frame.data.iter())
+        .map(|(x, y)| (x ^ y).count_ones())
+        .sum();
+    assert_eq!(flipped, 1);
+
+    bus.set_faults(LoopbackFaults {
+        drop_probability: 0.5,
+        seed: 7,
+        ..Default::default()
+    });
+    for _ in 0..200 {
+        a.send(&frame).expect("Failed to send frame!");
+    }
+    let stats = bus.get_stats();
+    assert_eq!(stats.sent, 204);
+    assert_eq!(stats.corrupted, 1);
+    assert!(stats.dropped > 2 + 50 && stats.dropped < 2 + 150);
+
+    bus.set_faults(LoopbackFaults {
+        delay: Duration::from_millis(50),
+        ..Default::default()
+    });
+    while b
+        .recv(Duration::ZERO)
+        .expect("Failed to receive frame!")
+        .is_some()
+    {}
+    a.send(&frame).expect("Failed to send frame!");
+    assert_eq!(b.recv(Duration::from_millis(5)), Ok(None));
+    assert_eq!(b.recv(TIMEOUT), Ok(Some(frame)));
+}
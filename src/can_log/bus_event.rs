@@ -0,0 +1,183 @@
+use std::fmt::Display;
+
+use super::{CanLogFrame, DecodedValue};
+use crate::eds_file::Address;
+use crate::network::CobIdKind;
+
+#[derive(Debug, Clone)]
+pub enum BusEvent {
+    Nmt {
+        command: u8,
+        node_id: u8,
+    },
+    Sync {
+        counter: Option<u8>,
+    },
+    Time {
+        millis: u32,
+        days: u16,
+    },
+    Emcy {
+        node_id: u8,
+        error_code: u16,
+        error_register: u8,
+        manufacturer_data: Vec<u8>,
+    },
+    Heartbeat {
+        node_id: u8,
+        state: u8,
+    },
+    SdoRequest {
+        node_id: u8,
+        command: u8,
+        address: Option<Address>,
+        name: Option<String>,
+        data: Vec<u8>,
+    },
+    SdoResponse {
+        node_id: u8,
+        command: u8,
+        address: Option<Address>,
+        name: Option<String>,
+        data: Vec<u8>,
+    },
+    Pdo {
+        node_id: u8,
+        kind: CobIdKind,
+        values: Vec<DecodedValue>,
+    },
+    Unknown,
+}
+
+#[derive(Debug, Clone)]
+pub struct CanLogEvent {
+    pub frame: CanLogFrame,
+    pub event: BusEvent,
+}
+
+pub fn nmt_state_name(state: u8) -> &'static str {
+    match state & 0x7F {
+        0 => "boot-up",
+        4 => "stopped",
+        5 => "operational",
+        127 => "pre-operational",
+        _ => "unknown",
+    }
+}
+
+pub fn nmt_command_name(command: u8) -> &'static str {
+    match command {
+        1 => "start",
+        2 => "stop",
+        128 => "enter pre-operational",
+        129 => "reset node",
+        130 => "reset communication",
+        _ => "unknown",
+    }
+}
+
+impl BusEvent {
+    pub fn get_type_name(&self) -> &'static str {
+        match &self {
+            BusEvent::Nmt { .. } => "NMT",
+            BusEvent::Sync { .. } => "SYNC",
+            BusEvent::Time { .. } => "TIME",
+            BusEvent::Emcy { .. } => "EMCY",
+            BusEvent::Heartbeat { .. } => "Heartbeat",
+            BusEvent::SdoRequest { .. } => "SDO request",
+            BusEvent::SdoResponse { .. } => "SDO response",
+            BusEvent::Pdo { .. } => "PDO",
+            BusEvent::Unknown => "Unknown",
+        }
+    }
+
+    pub fn get_node_id(&self) -> Option<u8> {
+        match &self {
+            BusEvent::Nmt { node_id, .. }
+            | BusEvent::Emcy { node_id, .. }
+            | BusEvent::Heartbeat { node_id, .. }
+            | BusEvent::SdoRequest { node_id, .. }
+            | BusEvent::SdoResponse { node_id, .. }
+            | BusEvent::Pdo { node_id, .. } => Some(*node_id),
+            _ => None,
+        }
+    }
+}
+
+fn sdo_string(
+    f: &mut std::fmt::Formatter<'_>,
+    command: &u8,
+    address: &Option<Address>,
+    name: &Option<String>,
+    data: &[u8],
+) -> std::fmt::Result {
+    write!(f, "command 0x{:02X}", command)?;
+    if let Some(addr) = address {
+        write!(f, " {:04X}sub{:X}", addr.index, addr.subindex)?;
+    }
+    if let Some(name) = name {
+        write!(f, " ({})", name)?;
+    }
+    let data: String = data.iter().map(|b| format!("{:02X}", b)).collect();
+    write!(f, " data {}", data)
+}
+
+impl Display for BusEvent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self {
+            BusEvent::Nmt { command, node_id } => {
+                write!(f, "NMT {} node {}", nmt_command_name(*command), node_id)
+            }
+            BusEvent::Sync { counter } => match counter {
+                Some(c) => write!(f, "SYNC {}", c),
+                None => write!(f, "SYNC"),
+            },
+            BusEvent::Time { millis, days } => write!(f, "TIME {} days {} ms", days, millis),
+            BusEvent::Emcy {
+                node_id,
+                error_code,
+                error_register,
+                manufacturer_data: _,
+            } => write!(
+                f,
+                "EMCY node {} error 0x{:04X} register 0x{:02X}",
+                node_id, error_code, error_register
+            ),
+            BusEvent::Heartbeat { node_id, state } => {
+                write!(f, "Heartbeat node {} {}", node_id, nmt_state_name(*state))
+            }
+            BusEvent::SdoRequest {
+                node_id,
+                command,
+                address,
+                name,
+                data,
+            } => {
+                write!(f, "SDO request node {} ", node_id)?;
+                sdo_string(f, command, address, name, data)
+            }
+            BusEvent::SdoResponse {
+                node_id,
+                command,
+                address,
+                name,
+                data,
+            } => {
+                write!(f, "SDO response node {} ", node_id)?;
+                sdo_string(f, command, address, name, data)
+            }
+            BusEvent::Pdo {
+                node_id,
+                kind,
+                values,
+            } => {
+                write!(f, "{} node {}", kind, node_id)?;
+                for v in values {
+                    write!(f, " {}={}", v.name, v.get_value_string())?;
+                }
+                Ok(())
+            }
+            BusEvent::Unknown => write!(f, "Unknown"),
+        }
+    }
+}
@@ -0,0 +1,169 @@
+use crate::ParseError;
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct CanLogFrame {
+    pub timestamp: Option<f64>,
+    pub interface: String,
+    pub cob_id: u32,
+    pub extended: bool,
+    pub rtr: bool,
+    pub data: Vec<u8>,
+}
+
+//Header and event lines of Vector ASC files that carry no frame
+const ASC_KEYWORDS: [&str; 6] = ["date", "base", "internal", "no", "Begin", "End"];
+
+fn parse_hex_byte(val: &str) -> Option<u8> {
+    if val.len() != 2 {
+        return None;
+    }
+    u8::from_str_radix(val, 16).ok()
+}
+
+fn parse_hex_bytes(val: &str) -> Option<Vec<u8>> {
+    if val.len() % 2 != 0 {
+        return None;
+    }
+    (0..val.len())
+        .step_by(2)
+        .map(|i| parse_hex_byte(val.get(i..i + 2)?))
+        .collect()
+}
+
+impl CanLogFrame {
+    fn parse_id(val: &str) -> Option<(u32, bool)> {
+        let (val, forced_extended) = match val.strip_suffix(['x', 'X']) {
+            Some(v) => (v, true),
+            None => (val, false),
+        };
+        let id = u32::from_str_radix(val, 16).ok()?;
+        let extended = forced_extended || val.len() > 3;
+        if id > 0x1FFFFFFF || (!extended && id > 0x7FF) {
+            return None;
+        }
+        Some((id, extended))
+    }
+
+    //candump -l: (1436509052.249713) can0 123#DEADBEEF
+    fn parse_compact(timestamp: Option<f64>, interface: &str, frame: &str) -> Option<CanLogFrame> {
+        let (id, payload) = frame.split_once('#')?;
+        let (cob_id, extended) = Self::parse_id(id)?;
+        let (rtr, data) = if payload.starts_with(['R', 'r']) {
+            (true, Vec::new())
+        } else {
+            (false, parse_hex_bytes(payload)?)
+        };
+        Some(CanLogFrame {
+            timestamp,
+            interface: interface.to_string(),
+            cob_id,
+            extended,
+            rtr,
+            data,
+        })
+    }
+
+    //candump: can0  123   [4]  DE AD BE EF
+    fn parse_spaced(timestamp: Option<f64>, tokens: &[&str]) -> Option<CanLogFrame> {
+        let (cob_id, extended) = Self::parse_id(tokens[1])?;
+        let dlc: usize = tokens[2]
+            .strip_prefix('[')?
+            .strip_suffix(']')?
+            .parse()
+            .ok()?;
+        let rtr = tokens.get(3) == Some(&"remote");
+        let data = if rtr {
+            Vec::new()
+        } else {
+            let bytes = tokens.get(3..3 + dlc)?;
+            bytes
+                .iter()
+                .map(|b| parse_hex_byte(b))
+                .collect::<Option<_>>()?
+        };
+        Some(CanLogFrame {
+            timestamp,
+            interface: tokens[0].to_string(),
+            cob_id,
+            extended,
+            rtr,
+            data,
+        })
+    }
+
+    //ASC: 0.015991 1  123             Rx   d 8 00 00 00 00 00 00 00 00
+    fn parse_asc(timestamp: f64, tokens: &[&str]) -> Option<CanLogFrame> {
+        let (cob_id, extended) = Self::parse_id(tokens[2])?;
+        let rtr = tokens[4] == "r";
+        let data = if rtr {
+            Vec::new()
+        } else {
+            let dlc: usize = tokens.get(5)?.parse().ok()?;
+            let bytes = tokens.get(6..6 + dlc)?;
+            bytes
+                .iter()
+                .map(|b| parse_hex_byte(b))
+                .collect::<Option<_>>()?
+        };
+        Some(CanLogFrame {
+            timestamp: Some(timestamp),
+            interface: tokens[1].to_string(),
+            cob_id,
+            extended,
+            rtr,
+            data,
+        })
+    }
+
+    //Returns None for lines that do not contain a frame
+    pub fn parse_line(line: &str) -> Result<Option<CanLogFrame>, ParseError> {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with("//") || trimmed.starts_with('#') {
+            return Ok(None);
+        }
+        let err = || ParseError::InvalidFormatting {
+            line: line.to_string(),
+        };
+        let tokens: Vec<&str> = trimmed.split_whitespace().collect();
+        if ASC_KEYWORDS.contains(&tokens[0]) {
+            return Ok(None);
+        }
+
+        let (timestamp, rest) = match tokens[0].strip_prefix('(') {
+            Some(t) => {
+                let t = t.strip_suffix(')').ok_or_else(err)?;
+                let t = t.parse::<f64>().ok().filter(|t| t.is_finite());
+                (Some(t.ok_or_else(err)?), &tokens[1..])
+            }
+            None => (None, &tokens[..]),
+        };
+        if rest.len() == 2 && rest[1].contains('#') {
+            return Self::parse_compact(timestamp, rest[0], rest[1])
+                .map(Some)
+                .ok_or_else(err);
+        }
+        if rest.len() >= 3 && rest[2].starts_with('[') {
+            return Self::parse_spaced(timestamp, rest)
+                .map(Some)
+                .ok_or_else(err);
+        }
+        if timestamp.is_none()
+            && let Ok(t) = tokens[0].parse::<f64>()
+            && t.is_finite()
+        {
+            if tokens.len() >= 5
+                && ["Rx", "Tx"].contains(&tokens[3])
+                && ["d", "r"].contains(&tokens[4])
+            {
+                return Self::parse_asc(t, &tokens).map(Some).ok_or_else(err);
+            }
+            //Error frames and other logged events
+            return Ok(None);
+        }
+        Err(err())
+    }
+
+    pub fn get_data_string(&self) -> String {
+        self.data.iter().map(|b| format!("{:02X}", b)).collect()
+    }
+}
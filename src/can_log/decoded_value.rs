@@ -0,0 +1,28 @@
+use std::fmt::Debug;
+
+use crate::eds_file::{Address, DataType, EDSValue, eds_value_to_string};
+
+#[derive(Debug)]
+pub struct DecodedValue {
+    pub address: Address,
+    pub name: String,
+    pub data_type: DataType,
+    pub value: Box<dyn EDSValue>,
+}
+
+impl Clone for DecodedValue {
+    fn clone(&self) -> Self {
+        DecodedValue {
+            address: self.address,
+            name: self.name.clone(),
+            data_type: self.data_type.clone(),
+            value: self.value.clone_box(),
+        }
+    }
+}
+
+impl DecodedValue {
+    pub fn get_value_string(&self) -> String {
+        eds_value_to_string(self.value.as_ref(), &self.data_type).unwrap_or_default()
+    }
+}
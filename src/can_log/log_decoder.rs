@@ -0,0 +1,204 @@
+use std::collections::HashMap;
+
+use super::{BusEvent, CanLogEvent, CanLogFrame, DecodedValue};
+use crate::CommParamError;
+use crate::eds_file::Address;
+use crate::network::{CobIdKind, CobIdUsage, Network, NetworkNode};
+use crate::pdo::{PdoDirection, PdoMapping};
+
+pub struct LogDecoder {
+    network: Network,
+    usages: Vec<CobIdUsage>,
+    mappings: HashMap<(u8, CobIdKind), PdoMapping>,
+}
+
+//SDO commands that carry a multiplexer in bytes 1 to 3, block transfers
+//only carry it in their initiate commands
+fn sdo_has_address(command: u8, request: bool) -> bool {
+    let specifier = command >> 5;
+    match (request, specifier) {
+        (true, 1 | 2 | 4) | (false, 2..=4) => true,
+        (_, 5) => command & 0x03 == 0,
+        (_, 6) => command & 0x01 == 0,
+        _ => false,
+    }
+}
+
+impl LogDecoder {
+    pub fn new(network: &Network) -> Result<LogDecoder, CommParamError> {
+        let usages = network.get_cob_id_usages()?;
+        let mut mappings = HashMap::new();
+        for usage in &usages {
+            let (direction, number) = match usage.kind {
+                CobIdKind::Rpdo { number } => (PdoDirection::Receive, number),
+                CobIdKind::Tpdo { number } => (PdoDirection::Transmit, number),
+                _ => continue,
+            };
+            let Some(node) = network.get_node(usage.node_id) else {
+                continue;
+            };
+            //PDOs without a decodable mapping are reported without values
+            if let Ok(mapping) = PdoMapping::decode(&node.file, direction, number) {
+                mappings.insert((usage.node_id, usage.kind), mapping);
+            }
+        }
+        Ok(LogDecoder {
+            network: network.clone(),
+            usages,
+            mappings,
+        })
+    }
+
+    //Producers take precedence, an RPDO is only used if no node sends the frame
+    fn find_usage(&self, frame: &CanLogFrame) -> Option<&CobIdUsage> {
+        let mut matching = self
+            .usages
+            .iter()
+            .filter(|u| u.cob_id == frame.cob_id && u.extended == frame.extended);
+        let first = matching.clone().find(|u| !u.consumer);
+        first.or_else(|| matching.next())
+    }
+
+    fn get_name(&self, node_id: u8, addr: &Address) -> Option<String> {
+        let node = self.network.get_node(node_id)?;
+        node.file.get_object(addr).map(|o| o.get_name().clone())
+    }
+
+    fn decode_sdo(&self, node_id: u8, frame: &CanLogFrame, request: bool) -> BusEvent {
+        let command = frame.data.first().copied().unwrap_or(0);
+        let address = if frame.data.len() >= 4 && sdo_has_address(command, request) {
+            let index = u16::from_le_bytes([frame.data[1], frame.data[2]]);
+            Some(Address::new(index, frame.data[3]))
+        } else {
+            None
+        };
+        let name = address.and_then(|a| self.get_name(node_id, &a));
+        let data = frame.data.get(1..).unwrap_or_default().to_vec();
+        match request {
+            true => BusEvent::SdoRequest {
+                node_id,
+                command,
+                address,
+                name,
+                data,
+            },
+            false => BusEvent::SdoResponse {
+                node_id,
+                command,
+                address,
+                name,
+                data,
+            },
+        }
+    }
+
+    fn decode_pdo(&self, node: &NetworkNode, kind: CobIdKind, frame: &CanLogFrame) -> BusEvent {
+        let mut values = Vec::new();
+        if let Some(mapping) = self.mappings.get(&(node.node_id, kind))
+            && let Ok(unpacked) = mapping.unpack(&node.file.device_info, &frame.data)
+        {
+            for (entry, (_, value)) in mapping.entries.iter().zip(unpacked) {
+                values.push(DecodedValue {
+                    address: entry.address,
+                    name: self
+                        .get_name(node.node_id, &entry.address)
+                        .unwrap_or_default(),
+                    data_type: entry.data_type.clone(),
+                    value,
+                });
+            }
+        }
+        BusEvent::Pdo {
+            node_id: node.node_id,
+            kind,
+            values,
+        }
+    }
+
+    fn decode_emcy(node_id: u8, frame: &CanLogFrame) -> BusEvent {
+        let data = &frame.data;
+        BusEvent::Emcy {
+            node_id,
+            error_code: u16::from_le_bytes([
+                data.first().copied().unwrap_or(0),
+                data.get(1).copied().unwrap_or(0),
+            ]),
+            error_register: data.get(2).copied().unwrap_or(0),
+            manufacturer_data: data.get(3..).unwrap_or_default().to_vec(),
+        }
+    }
+
+    fn decode_time(frame: &CanLogFrame) -> BusEvent {
+        let mut data = frame.data.clone();
+        data.resize(6, 0);
+        BusEvent::Time {
+            millis: u32::from_le_bytes([data[0], data[1], data[2], data[3]]) & 0x0FFFFFFF,
+            days: u16::from_le_bytes([data[4], data[5]]),
+        }
+    }
+
+    //Frames of nodes missing from the network are decoded using the
+    //predefined connection set
+    fn decode_predefined(&self, frame: &CanLogFrame) -> BusEvent {
+        if frame.extended {
+            return BusEvent::Unknown;
+        }
+        let node_id = (frame.cob_id & 0x7F) as u8;
+        match frame.cob_id & 0x780 {
+            0x080 if node_id == 0 => BusEvent::Sync {
+                counter: frame.data.first().copied(),
+            },
+            0x080 => Self::decode_emcy(node_id, frame),
+            0x100 if node_id == 0 => Self::decode_time(frame),
+            0x580 if node_id != 0 => self.decode_sdo(node_id, frame, false),
+            0x600 if node_id != 0 => self.decode_sdo(node_id, frame, true),
+            0x700 if node_id != 0 => BusEvent::Heartbeat {
+                node_id,
+                state: frame.data.first().copied().unwrap_or(0),
+            },
+            _ => BusEvent::Unknown,
+        }
+    }
+
+    pub fn decode_frame(&self, frame: &CanLogFrame) -> BusEvent {
+        if frame.cob_id == 0 && !frame.extended {
+            return BusEvent::Nmt {
+                command: frame.data.first().copied().unwrap_or(0),
+                node_id: frame.data.get(1).copied().unwrap_or(0),
+            };
+        }
+        let Some(usage) = self.find_usage(frame) else {
+            return self.decode_predefined(frame);
+        };
+        let node_id = usage.node_id;
+        match usage.kind {
+            CobIdKind::Sync => BusEvent::Sync {
+                counter: frame.data.first().copied(),
+            },
+            CobIdKind::Time => Self::decode_time(frame),
+            CobIdKind::Emcy => Self::decode_emcy(node_id, frame),
+            CobIdKind::Heartbeat => BusEvent::Heartbeat {
+                node_id,
+                state: frame.data.first().copied().unwrap_or(0),
+            },
+            CobIdKind::SdoRx { .. } => self.decode_sdo(node_id, frame, true),
+            CobIdKind::SdoTx { .. } => self.decode_sdo(node_id, frame, false),
+            CobIdKind::Rpdo { .. } | CobIdKind::Tpdo { .. } => {
+                match self.network.get_node(node_id) {
+                    Some(node) => self.decode_pdo(node, usage.kind, frame),
+                    None => BusEvent::Unknown,
+                }
+            }
+        }
+    }
+
+    pub fn decode(&self, frames: &[CanLogFrame]) -> Vec<CanLogEvent> {
+        frames
+            .iter()
+            .map(|frame| CanLogEvent {
+                frame: frame.clone(),
+                event: self.decode_frame(frame),
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,139 @@
+mod bus_event;
+mod can_log_frame;
+mod decoded_value;
+mod log_decoder;
+
+#[cfg(test)]
+mod tests;
+
+pub use bus_event::*;
+pub use can_log_frame::CanLogFrame;
+pub use decoded_value::DecodedValue;
+pub use log_decoder::LogDecoder;
+
+use crate::ParseError;
+use crate::raw_file::RawFile;
+
+pub fn parse_can_log(lines: &[String]) -> Result<Vec<CanLogFrame>, ParseError> {
+    let mut out = Vec::new();
+    for line in lines {
+        if let Some(frame) = CanLogFrame::parse_line(line)? {
+            out.push(frame);
+        }
+    }
+    Ok(out)
+}
+
+pub fn load_can_log(filename: &str) -> Result<Vec<CanLogFrame>, ParseError> {
+    let rfile = RawFile::new(filename)?;
+    parse_can_log(rfile.get_lines())
+}
+
+fn csv_field(val: &str) -> String {
+    if val.contains([',', '"', '\n']) {
+        format!("\"{}\"", val.replace('"', "\"\""))
+    } else {
+        val.to_string()
+    }
+}
+
+fn json_string(val: &str) -> String {
+    let mut out = String::from("\"");
+    for c in val.chars() {
+        match c {
+            '"' => out += "\\\"",
+            '\\' => out += "\\\\",
+            '\n' => out += "\\n",
+            '\r' => out += "\\r",
+            '\t' => out += "\\t",
+            c if (c as u32) < 0x20 => out += &format!("\\u{:04x}", c as u32),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+//Frames that are built by hand may hold timestamps that JSON can not
+//represent, they are written like missing ones
+fn timestamp(frame: &CanLogFrame) -> Option<String> {
+    frame
+        .timestamp
+        .filter(|t| t.is_finite())
+        .map(|t| format!("{:.6}", t))
+}
+
+//PDOs produce one row per mapped value, every other event a single row
+pub fn events_to_csv(events: &[CanLogEvent]) -> String {
+    let mut out = String::from("timestamp,interface,cob_id,data,event,node_id,name,value\n");
+    for e in events {
+        let prefix = format!(
+            "{},{},0x{:X},{},{},{}",
+            timestamp(&e.frame).unwrap_or_default(),
+            csv_field(&e.frame.interface),
+            e.frame.cob_id,
+            e.frame.get_data_string(),
+            e.event.get_type_name(),
+            e.event
+                .get_node_id()
+                .map(|n| n.to_string())
+                .unwrap_or_default()
+        );
+        match &e.event {
+            BusEvent::Pdo { values, .. } if !values.is_empty() => {
+                for v in values {
+                    out += &format!(
+                        "{},{},{}\n",
+                        prefix,
+                        csv_field(&v.name),
+                        csv_field(&v.get_value_string())
+                    );
+                }
+            }
+            event => out += &format!("{},,{}\n", prefix, csv_field(&event.to_string())),
+        }
+    }
+    out
+}
+
+pub fn events_to_json(events: &[CanLogEvent]) -> String {
+    let mut entries = Vec::new();
+    for e in events {
+        let mut fields = vec![
+            (
+                "timestamp",
+                timestamp(&e.frame).unwrap_or("null".to_string()),
+            ),
+            ("interface", json_string(&e.frame.interface)),
+            ("cob_id", e.frame.cob_id.to_string()),
+            ("extended", e.frame.extended.to_string()),
+            ("data", json_string(&e.frame.get_data_string())),
+            ("event", json_string(e.event.get_type_name())),
+        ];
+        if let Some(node_id) = e.event.get_node_id() {
+            fields.push(("node_id", node_id.to_string()));
+        }
+        fields.push(("description", json_string(&e.event.to_string())));
+        if let BusEvent::Pdo { values, .. } = &e.event {
+            let values: Vec<String> = values
+                .iter()
+                .map(|v| {
+                    format!(
+                        "{{\"index\":{},\"subindex\":{},\"name\":{},\"value\":{}}}",
+                        v.address.index,
+                        v.address.subindex,
+                        json_string(&v.name),
+                        json_string(&v.get_value_string())
+                    )
+                })
+                .collect();
+            fields.push(("values", format!("[{}]", values.join(","))));
+        }
+        let fields: Vec<String> = fields
+            .iter()
+            .map(|(k, v)| format!("{}:{}", json_string(k), v))
+            .collect();
+        entries.push(format!("{{{}}}", fields.join(",")));
+    }
+    format!("[{}]", entries.join(",\n"))
+}
@@ -0,0 +1,214 @@
+use crate::ParseError;
+use crate::can_log::{
+    BusEvent, CanLogFrame, LogDecoder, events_to_csv, events_to_json, parse_can_log,
+};
+use crate::eds_file::Address;
+use crate::load_file;
+use crate::network::{CobIdKind, Network};
+use crate::tests::utils::*;
+
+const LOG: [&str; 10] = [
+    "(1.000000) can0 000#0105",
+    "(1.001000) can0 080#",
+    "(1.002000) can0 182#3412",
+    "(1.003000) can0 705#05",
+    "(1.004000) can0 605#4017100000000000",
+    "(1.005000) can0 085#0010010000000000",
+    "",
+    "date Mon Oct 19 10:00:00 am 2026",
+    "1.006000 1  182             Rx   d 2 34 12",
+    "  can0  1FFFFFFF   [1]  AA",
+];
+
+fn make_network() -> Network {
+    let dcf = make_dcf_file(&PDO_DCF.to_vec()).expect("Failed to parse dcf file!");
+    let efile = load_file("res/micro-motor.eds").expect("Failed to load sample file!");
+    let mut network = Network::new();
+    network.add_dcf(&dcf);
+    network.add_eds(&efile, 5);
+    network
+}
+
+#[test]
+fn test_parse_lines() {
+    let frame = CanLogFrame::parse_line("(1436509052.249713) can0 123#DEADBEEF")
+        .expect("Failed to parse line!")
+        .expect("Missing frame!");
+    assert_eq!(frame.timestamp, Some(1436509052.249713));
+    assert_eq!(frame.interface, "can0");
+    assert_eq!(frame.cob_id, 0x123);
+    assert!(!frame.extended);
+    assert_eq!(frame.data, vec![0xDE, 0xAD, 0xBE, 0xEF]);
+
+    let frame = CanLogFrame::parse_line("(0.5) vcan1 00000123#R")
+        .expect("Failed to parse line!")
+        .expect("Missing frame!");
+    assert!(frame.extended);
+    assert!(frame.rtr);
+
+    let frame = CanLogFrame::parse_line("  can0  701   [1]  7F")
+        .expect("Failed to parse line!")
+        .expect("Missing frame!");
+    assert_eq!(frame.timestamp, None);
+    assert_eq!(frame.data, vec![0x7F]);
+
+    let frame = CanLogFrame::parse_line("2.5 1  18FF0001x       Tx   d 1 01")
+        .expect("Failed to parse line!")
+        .expect("Missing frame!");
+    assert_eq!(frame.cob_id, 0x18FF0001);
+    assert!(frame.extended);
+    assert_eq!(frame.interface, "1");
+
+    assert!(matches!(
+        CanLogFrame::parse_line("2.5 1 ErrorFrame"),
+        Ok(None)
+    ));
+    assert!(matches!(
+        CanLogFrame::parse_line("(1.0) can0 123#ABC"),
+        Err(ParseError::InvalidFormatting { line: _ })
+    ));
+    assert!(matches!(
+        CanLogFrame::parse_line("garbage"),
+        Err(ParseError::InvalidFormatting { line: _ })
+    ));
+}
+
+#[test]
+fn test_decode_log() {
+    let lines: Vec<String> = LOG.iter().map(|l| l.to_string()).collect();
+    let frames = parse_can_log(&lines).expect("Failed to parse log!");
+    assert_eq!(frames.len(), 8);
+    let decoder = LogDecoder::new(&make_network()).expect("Failed to create decoder!");
+    let events = decoder.decode(&frames);
+
+    assert!(matches!(
+        events[0].event,
+        BusEvent::Nmt {
+            command: 1,
+            node_id: 5
+        }
+    ));
+    assert!(matches!(events[1].event, BusEvent::Sync { counter: None }));
+    match &events[2].event {
+        BusEvent::Pdo {
+            node_id,
+            kind,
+            values,
+        } => {
+            assert_eq!(*node_id, 2);
+            assert_eq!(*kind, CobIdKind::Tpdo { number: 1 });
+            assert_eq!(values.len(), 1);
+            assert_eq!(values[0].name, "Speed");
+            assert_eq!(values[0].get_value_string(), "0x1234");
+        }
+        e => panic!("Unexpected event {:?}", e),
+    }
+    assert!(matches!(
+        events[3].event,
+        BusEvent::Heartbeat {
+            node_id: 5,
+            state: 5
+        }
+    ));
+    match &events[4].event {
+        BusEvent::SdoRequest {
+            node_id,
+            command,
+            address,
+            name,
+            data: _,
+        } => {
+            assert_eq!(*node_id, 5);
+            assert_eq!(*command, 0x40);
+            assert_eq!(*address, Some(Address::new(0x1017, 0)));
+            assert!(name.is_some());
+        }
+        e => panic!("Unexpected event {:?}", e),
+    }
+    assert!(matches!(
+        events[5].event,
+        BusEvent::Emcy {
+            node_id: 5,
+            error_code: 0x1000,
+            error_register: 1,
+            manufacturer_data: _
+        }
+    ));
+    assert!(matches!(events[6].event, BusEvent::Pdo { node_id: 2, .. }));
+    assert!(matches!(events[7].event, BusEvent::Unknown));
+
+    let csv = events_to_csv(&events);
+    let rows: Vec<&str> = csv.lines().collect();
+    assert_eq!(rows.len(), 9);
+    assert_eq!(rows[3], "1.002000,can0,0x182,3412,PDO,2,Speed,0x1234");
+    assert_eq!(
+        rows[4],
+        "1.003000,can0,0x705,05,Heartbeat,5,,Heartbeat node 5 operational"
+    );
+
+    let json = events_to_json(&events[2..3]);
+    assert_eq!(
+        json,
+        "[{\"timestamp\":1.002000,\"interface\":\"can0\",\"cob_id\":386,\"extended\":false,\
+         \"data\":\"3412\",\"event\":\"PDO\",\"node_id\":2,\"description\":\"TPDO1 node 2 Speed=0x1234\",\
+         \"values\":[{\"index\":8192,\"subindex\":0,\"name\":\"Speed\",\"value\":\"0x1234\"}]}]"
+    );
+}
+
+#[test]
+fn test_sdo_block_transfers() {
+    let lines: Vec<String> = [
+        "(2.000000) can0 605#C617100002000000",
+        "(2.001000) can0 585#A417100000000000",
+        "(2.002000) can0 605#C900000000000000",
+        "(2.003000) can0 605#A4171000FF000000",
+        "(2.004000) can0 585#C617100002000000",
+        "(2.005000) can0 605#A300000000000000",
+    ]
+    .iter()
+    .map(|l| l.to_string())
+    .collect();
+    let frames = parse_can_log(&lines).expect("Failed to parse log!");
+    let decoder = LogDecoder::new(&make_network()).expect("Failed to create decoder!");
+    let addresses: Vec<Option<Address>> = decoder
+        .decode(&frames)
+        .iter()
+        .map(|e| match &e.event {
+            BusEvent::SdoRequest { address, .. } | BusEvent::SdoResponse { address, .. } => {
+                *address
+            }
+            e => panic!("Unexpected event {:?}", e),
+        })
+        .collect();
+    let heartbeat = Some(Address::new(0x1017, 0));
+    assert_eq!(
+        addresses,
+        vec![heartbeat, heartbeat, None, heartbeat, heartbeat, None]
+    );
+}
+
+#[test]
+fn test_json_timestamps() {
+    assert!(matches!(
+        CanLogFrame::parse_line("(nan) can0 123#00"),
+        Err(ParseError::InvalidFormatting { line: _ })
+    ));
+    assert!(matches!(
+        CanLogFrame::parse_line("inf 1  123             Rx   d 1 00"),
+        Err(ParseError::InvalidFormatting { line: _ })
+    ));
+
+    let frame = CanLogFrame::parse_line("(1.0) can0 080#")
+        .expect("Failed to parse line!")
+        .expect("Missing frame!");
+    let decoder = LogDecoder::new(&make_network()).expect("Failed to create decoder!");
+    let mut events = decoder.decode(&[frame.clone(), frame.clone(), frame]);
+    events[0].frame.timestamp = Some(f64::NAN);
+    events[1].frame.timestamp = Some(f64::INFINITY);
+    let json: serde_json::Value =
+        serde_json::from_str(&events_to_json(&events)).expect("Failed to parse json!");
+    assert_eq!(json[0]["timestamp"], serde_json::Value::Null);
+    assert_eq!(json[1]["timestamp"], serde_json::Value::Null);
+    assert_eq!(json[2]["timestamp"], 1.0);
+    assert!(events_to_csv(&events).contains("\n,can0,0x80,"));
+}
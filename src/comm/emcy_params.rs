@@ -0,0 +1,61 @@
+use super::{COB_ID_INVALID_BIT, check_index, decode_cob_id, encode_cob_id, read_default};
+use crate::CommParamError;
+use crate::dcf::ObjectWrite;
+use crate::eds_file::{Address, DataType, EDSFile};
+
+pub const EMCY_COB_ID_INDEX: u16 = 0x1014;
+pub const EMCY_INHIBIT_INDEX: u16 = 0x1015;
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct EmcyParams {
+    pub cob_id: u32,
+    pub extended: bool,
+    pub valid: bool,
+    pub inhibit_100us: u16,
+}
+
+impl EmcyParams {
+    pub fn decode(raw: u32, inhibit_100us: u16) -> EmcyParams {
+        let (cob_id, extended) = decode_cob_id(raw);
+        EmcyParams {
+            cob_id,
+            extended,
+            valid: raw & COB_ID_INVALID_BIT == 0,
+            inhibit_100us,
+        }
+    }
+
+    pub fn encode(&self) -> u32 {
+        let mut raw = encode_cob_id(self.cob_id, self.extended);
+        if !self.valid {
+            raw |= COB_ID_INVALID_BIT;
+        }
+        raw
+    }
+
+    pub fn read(file: &EDSFile, node_id: u8) -> Result<EmcyParams, CommParamError> {
+        check_index(file, EMCY_COB_ID_INDEX)?;
+        let raw = read_default(file, &Address::new(EMCY_COB_ID_INDEX, 0), node_id)?;
+        let inhibit = read_default(file, &Address::new(EMCY_INHIBIT_INDEX, 0), node_id)?;
+        Ok(Self::decode(
+            raw.unwrap_or(0x80 + node_id as u64) as u32,
+            inhibit.unwrap_or(0) as u16,
+        ))
+    }
+
+    pub fn to_writes(&self, file: &EDSFile) -> Vec<ObjectWrite> {
+        let mut out = vec![ObjectWrite::new(
+            Address::new(EMCY_COB_ID_INDEX, 0),
+            DataType::UInt32,
+            Box::new(self.encode()),
+        )];
+        if file.get_index_object(EMCY_INHIBIT_INDEX).is_some() {
+            out.push(ObjectWrite::new(
+                Address::new(EMCY_INHIBIT_INDEX, 0),
+                DataType::UInt16,
+                Box::new(self.inhibit_100us),
+            ));
+        }
+        out
+    }
+}
@@ -0,0 +1,58 @@
+mod emcy_params;
+mod sdo_server_params;
+mod sync_params;
+mod time_params;
+
+#[cfg(test)]
+mod tests;
+
+pub use emcy_params::EmcyParams;
+pub use sdo_server_params::{MAX_SDO_SERVER_NUMBER, SDO_SERVER_INDEX, SdoServerParams};
+pub use sync_params::SyncParams;
+pub use time_params::TimeParams;
+
+use crate::CommParamError;
+use crate::eds_file::{Address, EDSFile, eds_value_to_u64};
+
+pub const COB_ID_INVALID_BIT: u32 = 1 << 31;
+pub const COB_ID_BIT_30: u32 = 1 << 30;
+pub const COB_ID_EXTENDED_BIT: u32 = 1 << 29;
+
+pub fn decode_cob_id(raw: u32) -> (u32, bool) {
+    if raw & COB_ID_EXTENDED_BIT != 0 {
+        (raw & 0x1FFFFFFF, true)
+    } else {
+        (raw & 0x7FF, false)
+    }
+}
+
+pub fn encode_cob_id(cob_id: u32, extended: bool) -> u32 {
+    if extended {
+        (cob_id & 0x1FFFFFFF) | COB_ID_EXTENDED_BIT
+    } else {
+        cob_id & 0x7FF
+    }
+}
+
+pub(crate) fn check_index(file: &EDSFile, index: u16) -> Result<(), CommParamError> {
+    file.get_index_object(index)
+        .map(|_| ())
+        .ok_or(CommParamError::MissingObject {
+            addr: Address::new(index, 0),
+        })
+}
+
+//Objects without a default value are reported as None so callers can fall
+//back to the predefined connection set
+pub(crate) fn read_default(
+    file: &EDSFile,
+    addr: &Address,
+    node_id: u8,
+) -> Result<Option<u64>, CommParamError> {
+    match file.get_object(addr).and_then(|obj| obj.get_default()) {
+        None => Ok(None),
+        Some(v) => eds_value_to_u64(v, Some(node_id))
+            .map(Some)
+            .ok_or(CommParamError::InvalidValue { addr: *addr }),
+    }
+}
@@ -0,0 +1,119 @@
+use super::{COB_ID_INVALID_BIT, check_index, decode_cob_id, encode_cob_id, read_default};
+use crate::CommParamError;
+use crate::dcf::ObjectWrite;
+use crate::eds_file::{Address, DataType, EDSFile};
+
+pub const SDO_SERVER_INDEX: u16 = 0x1200;
+pub const MAX_SDO_SERVER_NUMBER: u16 = 128;
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct SdoServerParams {
+    pub number: u16,
+    pub rx_cob_id: u32,
+    pub rx_valid: bool,
+    pub rx_extended: bool,
+    pub tx_cob_id: u32,
+    pub tx_valid: bool,
+    pub tx_extended: bool,
+    pub client_node_id: Option<u8>,
+}
+
+impl SdoServerParams {
+    //Servers are numbered from 1 to 128
+    pub fn index(number: u16) -> Option<u16> {
+        let offset = number
+            .checked_sub(1)
+            .filter(|n| *n < MAX_SDO_SERVER_NUMBER)?;
+        SDO_SERVER_INDEX.checked_add(offset)
+    }
+
+    fn checked_index(number: u16) -> Result<u16, CommParamError> {
+        Self::index(number).ok_or(CommParamError::InvalidSdoServerNumber { number })
+    }
+
+    pub fn decode(number: u16, rx_raw: u32, tx_raw: u32, client_node_id: Option<u8>) -> Self {
+        let (rx_cob_id, rx_extended) = decode_cob_id(rx_raw);
+        let (tx_cob_id, tx_extended) = decode_cob_id(tx_raw);
+        SdoServerParams {
+            number,
+            rx_cob_id,
+            rx_valid: rx_raw & COB_ID_INVALID_BIT == 0,
+            rx_extended,
+            tx_cob_id,
+            tx_valid: tx_raw & COB_ID_INVALID_BIT == 0,
+            tx_extended,
+            client_node_id,
+        }
+    }
+
+    pub fn encode(&self) -> (u32, u32) {
+        let mut rx = encode_cob_id(self.rx_cob_id, self.rx_extended);
+        let mut tx = encode_cob_id(self.tx_cob_id, self.tx_extended);
+        if !self.rx_valid {
+            rx |= COB_ID_INVALID_BIT;
+        }
+        if !self.tx_valid {
+            tx |= COB_ID_INVALID_BIT;
+        }
+        (rx, tx)
+    }
+
+    //The default server SDO always uses the predefined connection set
+    pub fn read(file: &EDSFile, number: u16, node_id: u8) -> Result<Self, CommParamError> {
+        let index = Self::checked_index(number)?;
+        check_index(file, index)?;
+        let rx = read_default(file, &Address::new(index, 1), node_id)?;
+        let tx = read_default(file, &Address::new(index, 2), node_id)?;
+        let client = read_default(file, &Address::new(index, 3), node_id)?;
+        let (rx, tx) = match (number, rx, tx) {
+            (1, _, _) => (0x600 + node_id as u64, 0x580 + node_id as u64),
+            (_, Some(rx), Some(tx)) => (rx, tx),
+            (_, None, _) => {
+                return Err(CommParamError::InvalidValue {
+                    addr: Address::new(index, 1),
+                });
+            }
+            (_, _, None) => {
+                return Err(CommParamError::InvalidValue {
+                    addr: Address::new(index, 2),
+                });
+            }
+        };
+        Ok(Self::decode(
+            number,
+            rx as u32,
+            tx as u32,
+            client.map(|c| c as u8),
+        ))
+    }
+
+    pub fn read_all(file: &EDSFile, node_id: u8) -> Result<Vec<Self>, CommParamError> {
+        let mut out = Vec::new();
+        for number in 1..=MAX_SDO_SERVER_NUMBER {
+            if file
+                .get_index_object(Self::checked_index(number)?)
+                .is_some()
+            {
+                out.push(Self::read(file, number, node_id)?);
+            }
+        }
+        Ok(out)
+    }
+
+    pub fn to_writes(&self) -> Result<Vec<ObjectWrite>, CommParamError> {
+        let index = Self::checked_index(self.number)?;
+        let (rx, tx) = self.encode();
+        let mut out = vec![
+            ObjectWrite::new(Address::new(index, 1), DataType::UInt32, Box::new(rx)),
+            ObjectWrite::new(Address::new(index, 2), DataType::UInt32, Box::new(tx)),
+        ];
+        if let Some(client) = self.client_node_id {
+            out.push(ObjectWrite::new(
+                Address::new(index, 3),
+                DataType::UInt8,
+                Box::new(client),
+            ));
+        }
+        Ok(out)
+    }
+}
@@ -0,0 +1,73 @@
+use super::{COB_ID_BIT_30, check_index, decode_cob_id, encode_cob_id, read_default};
+use crate::CommParamError;
+use crate::dcf::ObjectWrite;
+use crate::eds_file::{Address, DataType, EDSFile};
+
+pub const SYNC_COB_ID_INDEX: u16 = 0x1005;
+pub const SYNC_PERIOD_INDEX: u16 = 0x1006;
+pub const SYNC_OVERFLOW_INDEX: u16 = 0x1019;
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct SyncParams {
+    pub cob_id: u32,
+    pub extended: bool,
+    pub generator: bool,
+    pub cycle_period_us: u32,
+    pub counter_overflow: u8,
+}
+
+impl SyncParams {
+    pub fn decode(raw: u32, cycle_period_us: u32, counter_overflow: u8) -> SyncParams {
+        let (cob_id, extended) = decode_cob_id(raw);
+        SyncParams {
+            cob_id,
+            extended,
+            generator: raw & COB_ID_BIT_30 != 0,
+            cycle_period_us,
+            counter_overflow,
+        }
+    }
+
+    pub fn encode(&self) -> u32 {
+        let mut raw = encode_cob_id(self.cob_id, self.extended);
+        if self.generator {
+            raw |= COB_ID_BIT_30;
+        }
+        raw
+    }
+
+    pub fn read(file: &EDSFile, node_id: u8) -> Result<SyncParams, CommParamError> {
+        check_index(file, SYNC_COB_ID_INDEX)?;
+        let raw = read_default(file, &Address::new(SYNC_COB_ID_INDEX, 0), node_id)?;
+        let period = read_default(file, &Address::new(SYNC_PERIOD_INDEX, 0), node_id)?;
+        let overflow = read_default(file, &Address::new(SYNC_OVERFLOW_INDEX, 0), node_id)?;
+        Ok(Self::decode(
+            raw.unwrap_or(0x80) as u32,
+            period.unwrap_or(0) as u32,
+            overflow.unwrap_or(0) as u8,
+        ))
+    }
+
+    pub fn to_writes(&self, file: &EDSFile) -> Vec<ObjectWrite> {
+        let mut out = vec![ObjectWrite::new(
+            Address::new(SYNC_COB_ID_INDEX, 0),
+            DataType::UInt32,
+            Box::new(self.encode()),
+        )];
+        if file.get_index_object(SYNC_PERIOD_INDEX).is_some() {
+            out.push(ObjectWrite::new(
+                Address::new(SYNC_PERIOD_INDEX, 0),
+                DataType::UInt32,
+                Box::new(self.cycle_period_us),
+            ));
+        }
+        if file.get_index_object(SYNC_OVERFLOW_INDEX).is_some() {
+            out.push(ObjectWrite::new(
+                Address::new(SYNC_OVERFLOW_INDEX, 0),
+                DataType::UInt8,
+                Box::new(self.counter_overflow),
+            ));
+        }
+        out
+    }
+}
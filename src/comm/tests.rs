@@ -0,0 +1,129 @@
+use crate::CommParamError;
+use crate::comm::{EmcyParams, SdoServerParams, SyncParams, TimeParams};
+use crate::eds_file::Address;
+use crate::load_file;
+use crate::pdo::{PdoCommParams, PdoDirection};
+
+#[test]
+fn test_cob_id_words() {
+    let sync = SyncParams::decode(0x40000080, 1000, 0);
+    assert_eq!(sync.cob_id, 0x80);
+    assert!(sync.generator);
+    assert!(!sync.extended);
+    assert_eq!(sync.encode(), 0x40000080);
+
+    let time = TimeParams::decode(0xA0000100);
+    assert_eq!(time.cob_id, 0x100);
+    assert!(time.consumer);
+    assert!(!time.producer);
+    assert!(time.extended);
+    assert_eq!(time.encode(), 0xA0000100);
+
+    let emcy = EmcyParams::decode(0x80000085, 10);
+    assert!(!emcy.valid);
+    assert_eq!(emcy.encode(), 0x80000085);
+
+    let sdo = SdoServerParams::decode(2, 0x641, 0x800005C1, Some(3));
+    assert!(sdo.rx_valid);
+    assert!(!sdo.tx_valid);
+    assert_eq!(sdo.tx_cob_id, 0x5C1);
+    assert_eq!(sdo.encode(), (0x641, 0x800005C1));
+}
+
+#[test]
+fn test_read_sample() {
+    let efile = load_file("res/micro-motor.eds").expect("Failed to load sample file!");
+    let sync = SyncParams::read(&efile, 5).expect("Failed to read sync parameters!");
+    assert_eq!(sync.cob_id, 0);
+    assert!(!sync.generator);
+
+    let emcy = EmcyParams::read(&efile, 5).expect("Failed to read emcy parameters!");
+    assert_eq!(emcy.cob_id, 0x85);
+    assert!(emcy.valid);
+    assert_eq!(emcy.inhibit_100us, 50);
+    assert_eq!(emcy.to_writes(&efile).len(), 2);
+
+    let sdo = SdoServerParams::read_all(&efile, 5).expect("Failed to read sdo parameters!");
+    assert_eq!(sdo.len(), 1);
+    assert_eq!(sdo[0].rx_cob_id, 0x605);
+    assert_eq!(sdo[0].tx_cob_id, 0x585);
+
+    assert_eq!(
+        TimeParams::read(&efile, 5),
+        Err(CommParamError::MissingObject {
+            addr: Address::new(0x1012, 0)
+        })
+    );
+}
+
+#[test]
+fn test_read_sample_pdo() {
+    let efile = load_file("res/micro-motor.eds").expect("Failed to load sample file!");
+    let pdos = PdoCommParams::read_all(&efile, 5).expect("Failed to read pdo parameters!");
+    assert_eq!(pdos.len(), 8);
+
+    let tpdo1 = PdoCommParams::read(&efile, PdoDirection::Transmit, 1, 5)
+        .expect("Failed to read pdo parameters!");
+    assert_eq!(tpdo1.get_communication_index(), Some(0x1800));
+    assert_eq!(tpdo1.cob_id, 0x185);
+    assert!(tpdo1.valid);
+    assert!(tpdo1.rtr_allowed);
+    assert_eq!(tpdo1.transmission_type, 255);
+    assert!(tpdo1.is_event_driven());
+    assert_eq!(tpdo1.to_writes(&efile).map(|w| w.len()), Ok(4));
+
+    let tpdo3 = PdoCommParams::read(&efile, PdoDirection::Transmit, 3, 5)
+        .expect("Failed to read pdo parameters!");
+    assert_eq!(tpdo3.cob_id, 0x385);
+    assert!(!tpdo3.valid);
+    assert_eq!(tpdo3.encode_cob_id_word(), 0x80000385);
+
+    let rpdo1 = PdoCommParams::read(&efile, PdoDirection::Receive, 1, 5)
+        .expect("Failed to read pdo parameters!");
+    assert_eq!(rpdo1.cob_id, 0x205);
+    assert_eq!(rpdo1.to_writes(&efile).map(|w| w.len()), Ok(2));
+    assert_eq!(
+        PdoCommParams::predefined_cob_id(PdoDirection::Receive, 4, 5),
+        Some(0x505)
+    );
+    assert_eq!(
+        PdoCommParams::predefined_cob_id(PdoDirection::Receive, 5, 5),
+        None
+    );
+}
+
+#[test]
+fn test_parameter_numbers() {
+    let efile = load_file("res/micro-motor.eds").expect("Failed to load sample file!");
+    assert_eq!(
+        PdoCommParams::communication_index(PdoDirection::Receive, 512),
+        Some(0x15FF)
+    );
+    for number in [0, 513, 0xFFFF] {
+        assert_eq!(
+            PdoCommParams::communication_index(PdoDirection::Transmit, number),
+            None
+        );
+        assert_eq!(
+            PdoCommParams::read(&efile, PdoDirection::Transmit, number, 5),
+            Err(CommParamError::InvalidPdoNumber {
+                direction: PdoDirection::Transmit,
+                number
+            })
+        );
+    }
+
+    assert_eq!(SdoServerParams::index(128), Some(0x127F));
+    for number in [0, 129, 0xFFFF] {
+        assert_eq!(SdoServerParams::index(number), None);
+        assert_eq!(
+            SdoServerParams::read(&efile, number, 5),
+            Err(CommParamError::InvalidSdoServerNumber { number })
+        );
+    }
+    let sdo = SdoServerParams::decode(0, 0x641, 0x5C1, None);
+    assert_eq!(
+        sdo.to_writes().err(),
+        Some(CommParamError::InvalidSdoServerNumber { number: 0 })
+    );
+}
@@ -0,0 +1,53 @@
+use super::{
+    COB_ID_BIT_30, COB_ID_INVALID_BIT, check_index, decode_cob_id, encode_cob_id, read_default,
+};
+use crate::CommParamError;
+use crate::dcf::ObjectWrite;
+use crate::eds_file::{Address, DataType, EDSFile};
+
+pub const TIME_COB_ID_INDEX: u16 = 0x1012;
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct TimeParams {
+    pub cob_id: u32,
+    pub extended: bool,
+    pub consumer: bool,
+    pub producer: bool,
+}
+
+impl TimeParams {
+    pub fn decode(raw: u32) -> TimeParams {
+        let (cob_id, extended) = decode_cob_id(raw);
+        TimeParams {
+            cob_id,
+            extended,
+            consumer: raw & COB_ID_INVALID_BIT != 0,
+            producer: raw & COB_ID_BIT_30 != 0,
+        }
+    }
+
+    pub fn encode(&self) -> u32 {
+        let mut raw = encode_cob_id(self.cob_id, self.extended);
+        if self.consumer {
+            raw |= COB_ID_INVALID_BIT;
+        }
+        if self.producer {
+            raw |= COB_ID_BIT_30;
+        }
+        raw
+    }
+
+    pub fn read(file: &EDSFile, node_id: u8) -> Result<TimeParams, CommParamError> {
+        check_index(file, TIME_COB_ID_INDEX)?;
+        let raw = read_default(file, &Address::new(TIME_COB_ID_INDEX, 0), node_id)?;
+        Ok(Self::decode(raw.unwrap_or(0x100) as u32))
+    }
+
+    pub fn to_writes(&self) -> Vec<ObjectWrite> {
+        vec![ObjectWrite::new(
+            Address::new(TIME_COB_ID_INDEX, 0),
+            DataType::UInt32,
+            Box::new(self.encode()),
+        )]
+    }
+}
@@ -0,0 +1,262 @@
+mod standard_object;
+mod standard_object_issue;
+mod standard_objects;
+
+#[cfg(test)]
+mod tests;
+
+pub use standard_object::*;
+pub use standard_object_issue::StandardObjectIssue;
+pub use standard_objects::STANDARD_OBJECTS;
+pub(crate) use standard_objects::{RO, ZERO, entries, highest_subindex, value};
+
+use crate::EditError;
+use crate::eds_file::{
+    AccessMode, Address, DataType, EDSFile, EDSObject, EDSValue, NodeIdOffset, ObjectType,
+    eds_value_from_bytes,
+};
+use crate::pdo::{PdoCommParams, PdoDirection};
+
+pub const COMMUNICATION_PROFILE_INDICES: std::ops::RangeInclusive<u16> = 0x1000..=0x1FFF;
+
+pub fn get_standard_object(index: u16) -> Option<&'static StandardObject> {
+    STANDARD_OBJECTS.iter().find(|o| o.contains(index))
+}
+
+//Standard objects whose name contains the query, ignoring case
+pub fn search_standard_objects(query: &str) -> Vec<&'static StandardObject> {
+    let query = query.to_lowercase();
+    STANDARD_OBJECTS
+        .iter()
+        .filter(|o| o.name.to_lowercase().contains(&query))
+        .collect()
+}
+
+//Sub-objects of an EDS object as address, data type and access mode, compact
+//arrays are represented by their first entry
+fn get_sub_objects(obj: &EDSObject) -> Vec<(Address, Option<&DataType>, Option<&AccessMode>)> {
+    match obj {
+        EDSObject::EDSNull { .. } => Vec::new(),
+        EDSObject::EDSArray { entries, .. } => entries
+            .iter()
+            .map(|e| (*e.get_address(), e.get_data_type(), e.get_access_mode()))
+            .collect(),
+        EDSObject::CompactEDSArray { address, .. } => vec![(
+            Address::new(address.index, 1),
+            obj.get_data_type(),
+            obj.get_access_mode(),
+        )],
+        _ => vec![(
+            *obj.get_address(),
+            obj.get_data_type(),
+            obj.get_access_mode(),
+        )],
+    }
+}
+
+pub(crate) fn validate_object(
+    obj: &EDSObject,
+    standard: &StandardObject,
+) -> Vec<StandardObjectIssue> {
+    let index = obj.get_address().index;
+    let mut out = Vec::new();
+    if !standard.allows_object_type(obj.get_obj_type()) {
+        out.push(StandardObjectIssue::WrongObjectType {
+            index,
+            expected: standard.object_type.clone(),
+            found: obj.get_obj_type().clone(),
+        });
+        return out;
+    }
+    let subs = get_sub_objects(obj);
+    for (addr, data_type, access_mode) in &subs {
+        let Some(sub) = standard.get_sub_object(addr.subindex) else {
+            out.push(StandardObjectIssue::UnknownSubObject { addr: *addr });
+            continue;
+        };
+        if let Some(data_type) = data_type
+            && **data_type != sub.data_type
+        {
+            out.push(StandardObjectIssue::WrongDataType {
+                addr: *addr,
+                expected: sub.data_type.clone(),
+                found: (*data_type).clone(),
+            });
+        }
+        if let Some(access_mode) = access_mode
+            && !sub.allows(access_mode)
+        {
+            out.push(StandardObjectIssue::WrongAccessMode {
+                addr: *addr,
+                allowed: sub.access_modes.to_vec(),
+                found: (*access_mode).clone(),
+            });
+        }
+    }
+    if !matches!(obj, EDSObject::CompactEDSArray { .. }) {
+        for addr in standard.get_mandatory_addresses(index) {
+            if !subs.iter().any(|(a, _, _)| *a == addr) {
+                out.push(StandardObjectIssue::MissingSubObject { addr });
+            }
+        }
+    }
+    out
+}
+
+//Checks all objects of the communication profile area against CiA 301,
+//manufacturer specific entries in that area are not reported
+pub fn validate_standard_objects(file: &EDSFile) -> Vec<StandardObjectIssue> {
+    let mut out = Vec::new();
+    for standard in STANDARD_OBJECTS.iter() {
+        if standard.presence == Presence::Mandatory
+            && file.get_index_object(standard.index).is_none()
+        {
+            out.push(StandardObjectIssue::MissingObject {
+                index: standard.index,
+            });
+        }
+    }
+    let mut objects: Vec<&EDSObject> = file
+        .get_objects()
+        .into_iter()
+        .filter(|o| COMMUNICATION_PROFILE_INDICES.contains(&o.get_address().index))
+        .collect();
+    objects.sort_by_key(|o| o.get_address().index);
+    for obj in objects {
+        if let Some(standard) = get_standard_object(obj.get_address().index) {
+            out.extend(validate_object(obj, standard));
+        }
+    }
+    out
+}
+
+fn make_default(default: StandardDefault, data_type: &DataType) -> Option<Box<dyn EDSValue>> {
+    match default {
+        StandardDefault::None => None,
+        StandardDefault::Value(v) => {
+            let size = data_type.bit_size()?.div_ceil(8) as usize;
+            eds_value_from_bytes(&v.to_le_bytes()[..size], data_type)
+        }
+        StandardDefault::NodeId(offset) => Some(Box::new(NodeIdOffset { offset })),
+    }
+}
+
+//The predefined connection set gives PDOs 1 to 4 node id relative COB-IDs,
+//all others start out invalid
+fn pdo_cob_id_default(index: u16) -> Option<StandardDefault> {
+    let (direction, base) = match index {
+        0x1400..=0x15FF => (PdoDirection::Receive, 0x1400),
+        0x1800..=0x19FF => (PdoDirection::Transmit, 0x1800),
+        _ => return None,
+    };
+    Some(
+        match PdoCommParams::predefined_cob_id(direction, index - base + 1, 0) {
+            Some(cob_id) => StandardDefault::NodeId(cob_id as u64),
+            None => StandardDefault::Value(0x80000000),
+        },
+    )
+}
+
+fn make_variable(
+    address: Address,
+    name: String,
+    data_type: &DataType,
+    access_modes: &[AccessMode],
+    default: Option<Box<dyn EDSValue>>,
+) -> EDSObject {
+    EDSObject::EDSVariable {
+        address,
+        name,
+        object_type: ObjectType::Variable,
+        data_type: data_type.clone(),
+        access_mode: access_modes[0].clone(),
+        default,
+        pdo_mappable: false,
+        low_limit: None,
+        high_limit: None,
+        refuse_write_on_download: false,
+        refuse_read_on_scan: false,
+    }
+}
+
+//Builds a correctly shaped standard object, sub-entry ranges are created with
+//their usual number of entries
+pub fn make_standard_object(index: u16) -> Result<EDSObject, EditError> {
+    let standard = get_standard_object(index).ok_or(EditError::UnknownStandardObject { index })?;
+    if standard.object_type == ObjectType::Variable {
+        let sub = &standard.entries[0];
+        return Ok(make_variable(
+            Address::new(index, 0),
+            standard.name.to_string(),
+            &sub.data_type,
+            sub.access_modes,
+            make_default(sub.default, &sub.data_type),
+        ));
+    }
+    let mut entries = Vec::new();
+    for sub in standard.entries.iter().skip(1) {
+        for n in 0..sub.insert_count {
+            let subindex = sub.subindex + n;
+            let name = if sub.last_subindex > sub.subindex {
+                format!("{} {}", sub.name, n + 1)
+            } else {
+                sub.name.to_string()
+            };
+            let default = match pdo_cob_id_default(index) {
+                Some(default) if subindex == 1 => default,
+                _ => sub.default,
+            };
+            entries.push(make_variable(
+                Address::new(index, subindex),
+                name,
+                &sub.data_type,
+                sub.access_modes,
+                make_default(default, &sub.data_type),
+            ));
+        }
+    }
+    let sub0 = &standard.entries[0];
+    let highest = entries
+        .last()
+        .map(|e| e.get_address().subindex)
+        .unwrap_or(0);
+    let default = match sub0.default {
+        StandardDefault::None => StandardDefault::Value(highest as u64),
+        default => default,
+    };
+    entries.insert(
+        0,
+        make_variable(
+            Address::new(index, 0),
+            sub0.name.to_string(),
+            &sub0.data_type,
+            sub0.access_modes,
+            make_default(default, &sub0.data_type),
+        ),
+    );
+    Ok(EDSObject::EDSArray {
+        address: Address::new(index, 0),
+        name: standard.name.to_string(),
+        object_type: standard.object_type.clone(),
+        sub_number: entries.len() as u8,
+        entries,
+        refuse_write_on_download: false,
+        refuse_read_on_scan: false,
+    })
+}
+
+//Adds a standard object to the mandatory or optional objects of the file
+pub fn insert_standard_object(file: &mut EDSFile, index: u16) -> Result<(), EditError> {
+    if file.get_index_object(index).is_some() {
+        return Err(EditError::ObjectExists { index });
+    }
+    let obj = make_standard_object(index)?;
+    let address = *obj.get_address();
+    let mandatory = get_standard_object(index).is_some_and(|s| s.presence == Presence::Mandatory);
+    if mandatory {
+        file.mandatory_object.insert(address, obj);
+    } else {
+        file.optional_object.insert(address, obj);
+    }
+    Ok(())
+}
@@ -0,0 +1,93 @@
+use std::fmt::Display;
+
+use crate::eds_file::{AccessMode, Address, DataType, ObjectType};
+
+const RECORD_ARRAYS: [u16; 2] = [0x1003, 0x1016];
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Presence {
+    Mandatory,
+    //Mandatory depending on other features, e.g. heartbeat without guarding
+    Conditional,
+    Optional,
+}
+
+impl Display for Presence {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self {
+            Presence::Mandatory => write!(f, "mandatory"),
+            Presence::Conditional => write!(f, "conditional"),
+            Presence::Optional => write!(f, "optional"),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum StandardDefault {
+    None,
+    Value(u64),
+    //Added to the node id, e.g. $NODEID+0x80
+    NodeId(u64),
+}
+
+//Sub-entry layout, a range of subindices shares one definition as in
+//"Standard error field" 1-254
+#[derive(Debug, PartialEq, Clone)]
+pub struct StandardSubObject {
+    pub subindex: u8,
+    pub last_subindex: u8,
+    pub name: &'static str,
+    pub data_type: DataType,
+    pub access_modes: &'static [AccessMode],
+    pub default: StandardDefault,
+    pub mandatory: bool,
+    //Entries created when inserting the object
+    pub insert_count: u8,
+}
+
+impl StandardSubObject {
+    pub fn contains(&self, subindex: u8) -> bool {
+        (self.subindex..=self.last_subindex).contains(&subindex)
+    }
+
+    pub fn allows(&self, access_mode: &AccessMode) -> bool {
+        self.access_modes.contains(access_mode)
+    }
+}
+
+//Object of the CiA 301 communication profile, repeated objects like the PDO
+//parameters cover count consecutive indices
+#[derive(Debug, PartialEq, Clone)]
+pub struct StandardObject {
+    pub index: u16,
+    pub count: u16,
+    pub name: &'static str,
+    pub object_type: ObjectType,
+    pub presence: Presence,
+    pub entries: &'static [StandardSubObject],
+}
+
+impl StandardObject {
+    pub fn contains(&self, index: u16) -> bool {
+        index >= self.index && index - self.index < self.count
+    }
+
+    //The error history and consumer heartbeat times are arrays, many vendor
+    //files declare them as records
+    pub fn allows_object_type(&self, object_type: &ObjectType) -> bool {
+        *object_type == self.object_type
+            || (*object_type == ObjectType::Record && RECORD_ARRAYS.contains(&self.index))
+    }
+
+    pub fn get_sub_object(&self, subindex: u8) -> Option<&StandardSubObject> {
+        self.entries.iter().find(|e| e.contains(subindex))
+    }
+
+    pub fn get_mandatory_addresses(&self, index: u16) -> Vec<Address> {
+        self.entries
+            .iter()
+            .filter(|e| e.mandatory)
+            .map(|e| Address::new(index, e.subindex))
+            .collect()
+    }
+}
@@ -0,0 +1,79 @@
+use std::fmt::Display;
+
+use crate::eds_file::{AccessMode, Address, DataType, ObjectType};
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum StandardObjectIssue {
+    MissingObject {
+        index: u16,
+    },
+    MissingSubObject {
+        addr: Address,
+    },
+    UnknownSubObject {
+        addr: Address,
+    },
+    WrongObjectType {
+        index: u16,
+        expected: ObjectType,
+        found: ObjectType,
+    },
+    WrongDataType {
+        addr: Address,
+        expected: DataType,
+        found: DataType,
+    },
+    WrongAccessMode {
+        addr: Address,
+        allowed: Vec<AccessMode>,
+        found: AccessMode,
+    },
+}
+
+impl Display for StandardObjectIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self {
+            Self::MissingObject { index } => {
+                write!(f, "Mandatory object 0x{:04X} is missing", index)
+            }
+            Self::MissingSubObject { addr } => {
+                write!(f, "Mandatory sub-object {} is missing", addr)
+            }
+            Self::UnknownSubObject { addr } => {
+                write!(f, "{} is not defined by its standard", addr)
+            }
+            Self::WrongObjectType {
+                index,
+                expected,
+                found,
+            } => write!(
+                f,
+                "Object 0x{:04X} has object type {} but must be {}",
+                index, found, expected
+            ),
+            Self::WrongDataType {
+                addr,
+                expected,
+                found,
+            } => write!(
+                f,
+                "{} has data type {:?} but must be {:?}",
+                addr, found, expected
+            ),
+            Self::WrongAccessMode {
+                addr,
+                allowed,
+                found,
+            } => {
+                let list: Vec<String> = allowed.iter().map(|m| m.to_string()).collect();
+                write!(
+                    f,
+                    "{} has access mode {} but must be {}",
+                    addr,
+                    found,
+                    list.join(" or ")
+                )
+            }
+        }
+    }
+}
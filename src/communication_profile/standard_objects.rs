@@ -0,0 +1,494 @@
+use super::{Presence, StandardDefault, StandardObject, StandardSubObject};
+use crate::eds_file::{AccessMode, DataType, ObjectType};
+
+pub(crate) const RO: &[AccessMode] = &[AccessMode::ReadOnly, AccessMode::Constant];
+pub(crate) const RW: &[AccessMode] = &[AccessMode::ReadWrite];
+//Devices with static configuration may implement these read only
+pub(crate) const RW_RO: &[AccessMode] = &[
+    AccessMode::ReadWrite,
+    AccessMode::ReadOnly,
+    AccessMode::Constant,
+];
+
+pub(crate) const fn entry(
+    subindex: u8,
+    name: &'static str,
+    data_type: DataType,
+    access_modes: &'static [AccessMode],
+    default: StandardDefault,
+    mandatory: bool,
+) -> StandardSubObject {
+    StandardSubObject {
+        subindex,
+        last_subindex: subindex,
+        name,
+        data_type,
+        access_modes,
+        default,
+        mandatory,
+        insert_count: 1,
+    }
+}
+
+pub(crate) const fn value(
+    data_type: DataType,
+    access_modes: &'static [AccessMode],
+    default: StandardDefault,
+) -> StandardSubObject {
+    entry(0, "", data_type, access_modes, default, true)
+}
+
+pub(crate) const fn highest_subindex() -> StandardSubObject {
+    entry(
+        0,
+        "Highest sub-index supported",
+        DataType::UInt8,
+        RO,
+        StandardDefault::None,
+        true,
+    )
+}
+
+pub(crate) const fn entries(
+    last_subindex: u8,
+    name: &'static str,
+    data_type: DataType,
+    access_modes: &'static [AccessMode],
+    insert_count: u8,
+) -> StandardSubObject {
+    StandardSubObject {
+        subindex: 1,
+        last_subindex,
+        name,
+        data_type,
+        access_modes,
+        default: StandardDefault::Value(0),
+        mandatory: false,
+        insert_count,
+    }
+}
+
+pub(crate) const NONE: StandardDefault = StandardDefault::None;
+pub(crate) const ZERO: StandardDefault = StandardDefault::Value(0);
+
+//Layout shared by the RPDO and TPDO communication parameters, the COB-ID
+//default depends on the PDO number and is filled in on insert
+const fn pdo_cob_id(name: &'static str) -> StandardSubObject {
+    entry(1, name, DataType::UInt32, RW_RO, NONE, true)
+}
+
+const fn pdo_transmission_type() -> StandardSubObject {
+    entry(
+        2,
+        "Transmission type",
+        DataType::UInt8,
+        RW_RO,
+        StandardDefault::Value(255),
+        true,
+    )
+}
+
+const fn pdo_optional(subindex: u8, name: &'static str, data_type: DataType) -> StandardSubObject {
+    entry(subindex, name, data_type, RW_RO, ZERO, false)
+}
+
+//Reserved entries of older versions of CiA 301, they are accepted in files
+//but not created when inserting the object
+const fn pdo_reserved(subindex: u8, name: &'static str, data_type: DataType) -> StandardSubObject {
+    StandardSubObject {
+        insert_count: 0,
+        ..entry(subindex, name, data_type, RW_RO, ZERO, false)
+    }
+}
+
+const PDO_MAPPING: [StandardSubObject; 2] = [
+    entry(
+        0,
+        "Number of mapped application objects in PDO",
+        DataType::UInt8,
+        RW_RO,
+        ZERO,
+        true,
+    ),
+    entries(64, "Application object", DataType::UInt32, RW_RO, 8),
+];
+
+pub static STANDARD_OBJECTS: [StandardObject; 32] = [
+    StandardObject {
+        index: 0x1000,
+        count: 1,
+        name: "Device type",
+        object_type: ObjectType::Variable,
+        presence: Presence::Mandatory,
+        entries: &[value(DataType::UInt32, RO, ZERO)],
+    },
+    StandardObject {
+        index: 0x1001,
+        count: 1,
+        name: "Error register",
+        object_type: ObjectType::Variable,
+        presence: Presence::Mandatory,
+        entries: &[value(DataType::UInt8, RO, ZERO)],
+    },
+    StandardObject {
+        index: 0x1002,
+        count: 1,
+        name: "Manufacturer status register",
+        object_type: ObjectType::Variable,
+        presence: Presence::Optional,
+        entries: &[value(DataType::UInt32, RO, ZERO)],
+    },
+    StandardObject {
+        index: 0x1003,
+        count: 1,
+        name: "Pre-defined error field",
+        object_type: ObjectType::Array,
+        presence: Presence::Optional,
+        entries: &[
+            entry(0, "Number of errors", DataType::UInt8, RW, ZERO, true),
+            entries(254, "Standard error field", DataType::UInt32, RO, 8),
+        ],
+    },
+    StandardObject {
+        index: 0x1005,
+        count: 1,
+        name: "COB-ID SYNC message",
+        object_type: ObjectType::Variable,
+        presence: Presence::Conditional,
+        entries: &[value(DataType::UInt32, RW_RO, StandardDefault::Value(0x80))],
+    },
+    StandardObject {
+        index: 0x1006,
+        count: 1,
+        name: "Communication cycle period",
+        object_type: ObjectType::Variable,
+        presence: Presence::Conditional,
+        entries: &[value(DataType::UInt32, RW, ZERO)],
+    },
+    StandardObject {
+        index: 0x1007,
+        count: 1,
+        name: "Synchronous window length",
+        object_type: ObjectType::Variable,
+        presence: Presence::Optional,
+        entries: &[value(DataType::UInt32, RW, ZERO)],
+    },
+    StandardObject {
+        index: 0x1008,
+        count: 1,
+        name: "Manufacturer device name",
+        object_type: ObjectType::Variable,
+        presence: Presence::Optional,
+        entries: &[value(DataType::VisibleString, RO, NONE)],
+    },
+    StandardObject {
+        index: 0x1009,
+        count: 1,
+        name: "Manufacturer hardware version",
+        object_type: ObjectType::Variable,
+        presence: Presence::Optional,
+        entries: &[value(DataType::VisibleString, RO, NONE)],
+    },
+    StandardObject {
+        index: 0x100A,
+        count: 1,
+        name: "Manufacturer software version",
+        object_type: ObjectType::Variable,
+        presence: Presence::Optional,
+        entries: &[value(DataType::VisibleString, RO, NONE)],
+    },
+    StandardObject {
+        index: 0x100C,
+        count: 1,
+        name: "Guard time",
+        object_type: ObjectType::Variable,
+        presence: Presence::Conditional,
+        entries: &[value(DataType::UInt16, RW, ZERO)],
+    },
+    StandardObject {
+        index: 0x100D,
+        count: 1,
+        name: "Life time factor",
+        object_type: ObjectType::Variable,
+        presence: Presence::Conditional,
+        entries: &[value(DataType::UInt8, RW, ZERO)],
+    },
+    StandardObject {
+        index: 0x1010,
+        count: 1,
+        name: "Store parameters",
+        object_type: ObjectType::Array,
+        presence: Presence::Optional,
+        entries: &[
+            highest_subindex(),
+            entries(127, "Save parameters", DataType::UInt32, RW, 1),
+        ],
+    },
+    StandardObject {
+        index: 0x1011,
+        count: 1,
+        name: "Restore default parameters",
+        object_type: ObjectType::Array,
+        presence: Presence::Optional,
+        entries: &[
+            highest_subindex(),
+            entries(127, "Restore parameters", DataType::UInt32, RW, 1),
+        ],
+    },
+    StandardObject {
+        index: 0x1012,
+        count: 1,
+        name: "COB-ID time stamp object",
+        object_type: ObjectType::Variable,
+        presence: Presence::Optional,
+        entries: &[value(
+            DataType::UInt32,
+            RW_RO,
+            StandardDefault::Value(0x100),
+        )],
+    },
+    StandardObject {
+        index: 0x1013,
+        count: 1,
+        name: "High resolution time stamp",
+        object_type: ObjectType::Variable,
+        presence: Presence::Optional,
+        entries: &[value(DataType::UInt32, RW, ZERO)],
+    },
+    StandardObject {
+        index: 0x1014,
+        count: 1,
+        name: "COB-ID EMCY",
+        object_type: ObjectType::Variable,
+        presence: Presence::Conditional,
+        entries: &[value(
+            DataType::UInt32,
+            RW_RO,
+            StandardDefault::NodeId(0x80),
+        )],
+    },
+    StandardObject {
+        index: 0x1015,
+        count: 1,
+        name: "Inhibit time EMCY",
+        object_type: ObjectType::Variable,
+        presence: Presence::Optional,
+        entries: &[value(DataType::UInt16, RW, ZERO)],
+    },
+    StandardObject {
+        index: 0x1016,
+        count: 1,
+        name: "Consumer heartbeat time",
+        object_type: ObjectType::Array,
+        presence: Presence::Optional,
+        entries: &[
+            highest_subindex(),
+            entries(127, "Consumer heartbeat time", DataType::UInt32, RW, 1),
+        ],
+    },
+    StandardObject {
+        index: 0x1017,
+        count: 1,
+        name: "Producer heartbeat time",
+        object_type: ObjectType::Variable,
+        presence: Presence::Conditional,
+        entries: &[value(DataType::UInt16, RW, ZERO)],
+    },
+    StandardObject {
+        index: 0x1018,
+        count: 1,
+        name: "Identity object",
+        object_type: ObjectType::Record,
+        presence: Presence::Mandatory,
+        entries: &[
+            highest_subindex(),
+            entry(1, "Vendor-ID", DataType::UInt32, RO, ZERO, true),
+            entry(2, "Product code", DataType::UInt32, RO, ZERO, false),
+            entry(3, "Revision number", DataType::UInt32, RO, ZERO, false),
+            entry(4, "Serial number", DataType::UInt32, RO, ZERO, false),
+        ],
+    },
+    StandardObject {
+        index: 0x1019,
+        count: 1,
+        name: "Synchronous counter overflow value",
+        object_type: ObjectType::Variable,
+        presence: Presence::Optional,
+        entries: &[value(DataType::UInt8, RW, ZERO)],
+    },
+    StandardObject {
+        index: 0x1020,
+        count: 1,
+        name: "Verify configuration",
+        object_type: ObjectType::Array,
+        presence: Presence::Optional,
+        entries: &[
+            highest_subindex(),
+            entry(1, "Configuration date", DataType::UInt32, RW, ZERO, true),
+            entry(2, "Configuration time", DataType::UInt32, RW, ZERO, true),
+        ],
+    },
+    StandardObject {
+        index: 0x1028,
+        count: 1,
+        name: "Emergency consumer object",
+        object_type: ObjectType::Array,
+        presence: Presence::Optional,
+        entries: &[
+            highest_subindex(),
+            entries(127, "Emergency consumer", DataType::UInt32, RW, 1),
+        ],
+    },
+    StandardObject {
+        index: 0x1029,
+        count: 1,
+        name: "Error behavior object",
+        object_type: ObjectType::Array,
+        presence: Presence::Optional,
+        entries: &[
+            highest_subindex(),
+            entries(254, "Error class", DataType::UInt8, RW, 1),
+        ],
+    },
+    StandardObject {
+        index: 0x1200,
+        count: 1,
+        name: "SDO server parameter",
+        object_type: ObjectType::Record,
+        presence: Presence::Optional,
+        entries: &[
+            highest_subindex(),
+            entry(
+                1,
+                "COB-ID client to server",
+                DataType::UInt32,
+                RO,
+                StandardDefault::NodeId(0x600),
+                true,
+            ),
+            entry(
+                2,
+                "COB-ID server to client",
+                DataType::UInt32,
+                RO,
+                StandardDefault::NodeId(0x580),
+                true,
+            ),
+        ],
+    },
+    StandardObject {
+        index: 0x1201,
+        count: 0x7F,
+        name: "SDO server parameter",
+        object_type: ObjectType::Record,
+        presence: Presence::Optional,
+        entries: &[
+            highest_subindex(),
+            entry(
+                1,
+                "COB-ID client to server",
+                DataType::UInt32,
+                RW_RO,
+                StandardDefault::Value(0x80000000),
+                true,
+            ),
+            entry(
+                2,
+                "COB-ID server to client",
+                DataType::UInt32,
+                RW_RO,
+                StandardDefault::Value(0x80000000),
+                true,
+            ),
+            entry(
+                3,
+                "Node-ID of the SDO client",
+                DataType::UInt8,
+                RW,
+                ZERO,
+                false,
+            ),
+        ],
+    },
+    StandardObject {
+        index: 0x1280,
+        count: 0x80,
+        name: "SDO client parameter",
+        object_type: ObjectType::Record,
+        presence: Presence::Optional,
+        entries: &[
+            highest_subindex(),
+            entry(
+                1,
+                "COB-ID client to server",
+                DataType::UInt32,
+                RW,
+                StandardDefault::Value(0x80000000),
+                true,
+            ),
+            entry(
+                2,
+                "COB-ID server to client",
+                DataType::UInt32,
+                RW,
+                StandardDefault::Value(0x80000000),
+                true,
+            ),
+            entry(
+                3,
+                "Node-ID of the SDO server",
+                DataType::UInt8,
+                RW,
+                ZERO,
+                true,
+            ),
+        ],
+    },
+    StandardObject {
+        index: 0x1400,
+        count: 0x200,
+        name: "RPDO communication parameter",
+        object_type: ObjectType::Record,
+        presence: Presence::Optional,
+        entries: &[
+            highest_subindex(),
+            pdo_cob_id("COB-ID used by RPDO"),
+            pdo_transmission_type(),
+            pdo_optional(3, "Inhibit time", DataType::UInt16),
+            pdo_reserved(4, "Compatibility entry", DataType::UInt8),
+            pdo_optional(5, "Event timer", DataType::UInt16),
+        ],
+    },
+    StandardObject {
+        index: 0x1600,
+        count: 0x200,
+        name: "RPDO mapping parameter",
+        object_type: ObjectType::Record,
+        presence: Presence::Optional,
+        entries: &PDO_MAPPING,
+    },
+    StandardObject {
+        index: 0x1800,
+        count: 0x200,
+        name: "TPDO communication parameter",
+        object_type: ObjectType::Record,
+        presence: Presence::Optional,
+        entries: &[
+            highest_subindex(),
+            pdo_cob_id("COB-ID used by TPDO"),
+            pdo_transmission_type(),
+            pdo_optional(3, "Inhibit time", DataType::UInt16),
+            pdo_reserved(4, "Compatibility entry", DataType::UInt8),
+            pdo_optional(5, "Event timer", DataType::UInt16),
+            pdo_optional(6, "SYNC start value", DataType::UInt8),
+        ],
+    },
+    StandardObject {
+        index: 0x1A00,
+        count: 0x200,
+        name: "TPDO mapping parameter",
+        object_type: ObjectType::Record,
+        presence: Presence::Optional,
+        entries: &PDO_MAPPING,
+    },
+];
@@ -0,0 +1,258 @@
+use crate::EditError;
+use crate::communication_profile::{
+    StandardObjectIssue, get_standard_object, insert_standard_object, search_standard_objects,
+    validate_standard_objects,
+};
+use crate::eds_file::{AccessMode, Address, DataType, NodeIdOffset, ObjectType};
+use crate::load_file;
+use crate::tests::utils::*;
+
+#[test]
+fn test_validate_standard_objects() {
+    let lines = vec![
+        "[MandatoryObjects]",
+        "SupportedObjects=2",
+        "1=0x1000",
+        "2=0x1018",
+        "[1000]",
+        "ParameterName=Device type",
+        "ObjectType=0x7",
+        "DataType=0x0007",
+        "AccessType=ro",
+        "[1018]",
+        "ParameterName=Identity object",
+        "ObjectType=0x9",
+        "SubNumber=2",
+        "[1018sub0]",
+        "ParameterName=Highest sub-index supported",
+        "ObjectType=0x7",
+        "DataType=0x0005",
+        "AccessType=rw",
+        "DefaultValue=2",
+        "[1018sub2]",
+        "ParameterName=Product code",
+        "ObjectType=0x7",
+        "DataType=0x0007",
+        "AccessType=ro",
+        "[OptionalObjects]",
+        "SupportedObjects=2",
+        "1=0x1017",
+        "2=0x1A00",
+        "[1017]",
+        "ParameterName=Producer heartbeat time",
+        "ObjectType=0x7",
+        "DataType=0x0007",
+        "AccessType=rw",
+        "[1A00]",
+        "ParameterName=TPDO1 mapping parameter",
+        "ObjectType=0x9",
+        "SubNumber=2",
+        "[1A00sub0]",
+        "ParameterName=Number of mapped objects",
+        "ObjectType=0x7",
+        "DataType=0x0005",
+        "AccessType=rw",
+        "[1A00sub41]",
+        "ParameterName=Mapped object 65",
+        "ObjectType=0x7",
+        "DataType=0x0007",
+        "AccessType=rw",
+        "[ManufacturerObjects]",
+        "SupportedObjects=0",
+    ];
+    let file = make_eds_file(&lines).expect("Failed to parse eds file!");
+    assert_eq!(
+        validate_standard_objects(&file),
+        vec![
+            StandardObjectIssue::MissingObject { index: 0x1001 },
+            StandardObjectIssue::WrongDataType {
+                addr: Address::new(0x1017, 0),
+                expected: DataType::UInt16,
+                found: DataType::UInt32
+            },
+            StandardObjectIssue::WrongAccessMode {
+                addr: Address::new(0x1018, 0),
+                allowed: vec![AccessMode::ReadOnly, AccessMode::Constant],
+                found: AccessMode::ReadWrite
+            },
+            StandardObjectIssue::MissingSubObject {
+                addr: Address::new(0x1018, 1)
+            },
+            StandardObjectIssue::UnknownSubObject {
+                addr: Address::new(0x1A00, 0x41)
+            },
+        ]
+    );
+    assert_eq!(
+        StandardObjectIssue::WrongAccessMode {
+            addr: Address::new(0x1018, 0),
+            allowed: vec![AccessMode::ReadOnly, AccessMode::Constant],
+            found: AccessMode::ReadWrite
+        }
+        .to_string(),
+        "Address {0x1018.0} has access mode rw but must be ro or const"
+    );
+
+    //Vendor files declare arrays as records, the sample file still gets
+    //the number of entries wrong
+    let file = load_file("res/micro-motor.eds").expect("Failed to load eds file!");
+    assert_eq!(
+        validate_standard_objects(&file),
+        vec![
+            StandardObjectIssue::WrongDataType {
+                addr: Address::new(0x1003, 0),
+                expected: DataType::UInt8,
+                found: DataType::UInt32
+            },
+            StandardObjectIssue::WrongAccessMode {
+                addr: Address::new(0x1003, 0),
+                allowed: vec![AccessMode::ReadWrite],
+                found: AccessMode::ReadOnly
+            },
+            StandardObjectIssue::WrongDataType {
+                addr: Address::new(0x1016, 0),
+                expected: DataType::UInt8,
+                found: DataType::UInt32
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_validate_pdo_compatibility_entry() {
+    let lines = vec![
+        "[MandatoryObjects]",
+        "SupportedObjects=0",
+        "[OptionalObjects]",
+        "SupportedObjects=2",
+        "1=0x1400",
+        "2=0x1800",
+        "[1400]",
+        "ParameterName=RPDO communication parameter",
+        "ObjectType=0x9",
+        "SubNumber=4",
+        "[1400sub0]",
+        "ParameterName=Highest sub-index supported",
+        "ObjectType=0x7",
+        "DataType=0x0005",
+        "AccessType=ro",
+        "DefaultValue=4",
+        "[1400sub1]",
+        "ParameterName=COB-ID used by RPDO",
+        "ObjectType=0x7",
+        "DataType=0x0007",
+        "AccessType=rw",
+        "[1400sub2]",
+        "ParameterName=Transmission type",
+        "ObjectType=0x7",
+        "DataType=0x0005",
+        "AccessType=rw",
+        "[1400sub4]",
+        "ParameterName=Compatibility entry",
+        "ObjectType=0x7",
+        "DataType=0x0005",
+        "AccessType=rw",
+        "[1800]",
+        "ParameterName=TPDO communication parameter",
+        "ObjectType=0x9",
+        "SubNumber=4",
+        "[1800sub0]",
+        "ParameterName=Highest sub-index supported",
+        "ObjectType=0x7",
+        "DataType=0x0005",
+        "AccessType=ro",
+        "DefaultValue=4",
+        "[1800sub1]",
+        "ParameterName=COB-ID used by TPDO",
+        "ObjectType=0x7",
+        "DataType=0x0007",
+        "AccessType=rw",
+        "[1800sub2]",
+        "ParameterName=Transmission type",
+        "ObjectType=0x7",
+        "DataType=0x0005",
+        "AccessType=rw",
+        "[1800sub4]",
+        "ParameterName=Compatibility entry",
+        "ObjectType=0x7",
+        "DataType=0x0005",
+        "AccessType=rw",
+        "[ManufacturerObjects]",
+        "SupportedObjects=0",
+    ];
+    let file = make_eds_file(&lines).expect("Failed to parse eds file!");
+    assert_eq!(
+        validate_standard_objects(&file),
+        vec![
+            StandardObjectIssue::MissingObject { index: 0x1000 },
+            StandardObjectIssue::MissingObject { index: 0x1001 },
+            StandardObjectIssue::MissingObject { index: 0x1018 },
+        ]
+    );
+}
+
+#[test]
+fn test_insert_standard_object() {
+    let lines = vec![
+        "[MandatoryObjects]",
+        "SupportedObjects=0",
+        "[OptionalObjects]",
+        "SupportedObjects=0",
+        "[ManufacturerObjects]",
+        "SupportedObjects=0",
+    ];
+    let mut file = make_eds_file(&lines).expect("Failed to parse eds file!");
+    assert_eq!(
+        get_standard_object(0x1A05).map(|o| o.name),
+        Some("TPDO mapping parameter")
+    );
+    assert!(get_standard_object(0x1004).is_none());
+    let names: Vec<&str> = search_standard_objects("heartbeat")
+        .iter()
+        .map(|o| o.name)
+        .collect();
+    assert_eq!(
+        names,
+        vec!["Consumer heartbeat time", "Producer heartbeat time"]
+    );
+
+    for index in [0x1000, 0x1001, 0x1018, 0x1801, 0x1A01, 0x1016] {
+        insert_standard_object(&mut file, index).expect("Failed to insert object!");
+    }
+    assert_eq!(
+        insert_standard_object(&mut file, 0x1018),
+        Err(EditError::ObjectExists { index: 0x1018 })
+    );
+    assert_eq!(
+        insert_standard_object(&mut file, 0x1004),
+        Err(EditError::UnknownStandardObject { index: 0x1004 })
+    );
+    assert!(validate_standard_objects(&file).is_empty());
+    assert!(file.mandatory_object.contains_key(&Address::new(0x1018, 0)));
+
+    let tpdo = file.get_index_object(0x1801).expect("Missing TPDO2!");
+    assert_eq!(*tpdo.get_obj_type(), ObjectType::Record);
+    let subs: Vec<u8> = tpdo
+        .get_entries()
+        .expect("Missing entries!")
+        .iter()
+        .map(|e| e.get_address().subindex)
+        .collect();
+    assert_eq!(subs, vec![0, 1, 2, 3, 5, 6]);
+    assert_eq!(file.get_default_u64(&Address::new(0x1801, 0), 0), Some(6));
+    let cob_id = file
+        .get_object(&Address::new(0x1801, 1))
+        .and_then(|o| o.get_default())
+        .and_then(|v| v.as_any().downcast_ref::<NodeIdOffset>().copied());
+    assert_eq!(cob_id, Some(NodeIdOffset { offset: 0x280 }));
+
+    let mapping = file.get_index_object(0x1A01).expect("Missing mapping!");
+    assert_eq!(mapping.get_entries().map(|e| e.len()), Some(9));
+    assert_eq!(
+        file.get_object(&Address::new(0x1A01, 8))
+            .map(|o| o.get_name().as_str()),
+        Some("Application object 8")
+    );
+    assert_eq!(file.get_default_u64(&Address::new(0x1A01, 0), 0), Some(0));
+    assert_eq!(file.get_default_u64(&Address::new(0x1016, 0), 0), Some(1));
+}
@@ -0,0 +1,43 @@
+use super::ObjectWrite;
+use crate::DcfError;
+use crate::eds_file::Address;
+
+//Binary format from CiA 302-3, all values little endian:
+//UNSIGNED32 entry count, then per entry UNSIGNED16 index, UNSIGNED8 subindex,
+//UNSIGNED32 data size and the data itself
+pub fn encode_concise_dcf(writes: &[ObjectWrite]) -> Result<Vec<u8>, DcfError> {
+    let mut out = Vec::new();
+    out.extend_from_slice(&(writes.len() as u32).to_le_bytes());
+    for w in writes {
+        let data = w
+            .get_bytes()
+            .ok_or(DcfError::InvalidValue { addr: w.address })?;
+        out.extend_from_slice(&w.address.index.to_le_bytes());
+        out.push(w.address.subindex);
+        out.extend_from_slice(&(data.len() as u32).to_le_bytes());
+        out.extend_from_slice(&data);
+    }
+    Ok(out)
+}
+
+//Returns the raw entries, the data types are not part of the format
+pub fn decode_concise_dcf(data: &[u8]) -> Result<Vec<(Address, Vec<u8>)>, DcfError> {
+    let truncated = DcfError::TruncatedConciseDcf { length: data.len() };
+    let read_u32 = |pos: usize| -> Option<u32> {
+        Some(u32::from_le_bytes(data.get(pos..pos + 4)?.try_into().ok()?))
+    };
+    let count = read_u32(0).ok_or(truncated.clone())?;
+    let mut pos = 4;
+    let mut out = Vec::new();
+    for _ in 0..count {
+        let header = data.get(pos..pos + 7).ok_or(truncated.clone())?;
+        let index = u16::from_le_bytes([header[0], header[1]]);
+        let subindex = header[2];
+        let size = read_u32(pos + 3).ok_or(truncated.clone())? as usize;
+        pos += 7;
+        let value = data.get(pos..pos + size).ok_or(truncated.clone())?;
+        pos += size;
+        out.push((Address::new(index, subindex), value.to_vec()));
+    }
+    Ok(out)
+}
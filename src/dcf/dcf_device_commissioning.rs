@@ -0,0 +1,90 @@
+use crate::ParseError;
+use crate::eds_file::bool_str;
+use crate::eds_file::utils::{parse_required_bool, parse_required_str, parse_required_uint};
+use crate::structured_file::StructuredFileObject;
+
+#[derive(Debug, Clone)]
+pub struct DCFDeviceCommissioning {
+    pub node_id: u8,
+    pub node_name: String,
+    pub baudrate: u16,
+    pub net_number: u32,
+    pub network_name: String,
+    pub canopen_manager: bool,
+    pub lss_serial_number: Option<u32>,
+}
+
+impl DCFDeviceCommissioning {
+    pub fn new(node_id: u8) -> DCFDeviceCommissioning {
+        DCFDeviceCommissioning {
+            node_id,
+            node_name: String::new(),
+            baudrate: 0,
+            net_number: 0,
+            network_name: String::new(),
+            canopen_manager: false,
+            lss_serial_number: None,
+        }
+    }
+
+    pub(crate) fn parse(obj: &StructuredFileObject) -> Result<DCFDeviceCommissioning, ParseError> {
+        let node_id = parse_required_uint(obj, "NodeID")?;
+        if !(1..=127).contains(&node_id) {
+            return Err(ParseError::InvalidValueFormat {
+                object: "NodeID".to_string(),
+                section: obj.get_name().to_string(),
+            });
+        }
+        let optional_str = |name: &str| -> Result<String, ParseError> {
+            match obj.get_value(name) {
+                Some(_) => parse_required_str(obj, name).cloned(),
+                None => Ok(String::new()),
+            }
+        };
+        let baudrate = match obj.get_value("Baudrate") {
+            Some(_) => parse_required_uint(obj, "Baudrate")?,
+            None => 0,
+        };
+        let net_number = match obj.get_value("NetNumber") {
+            Some(_) => parse_required_uint(obj, "NetNumber")?,
+            None => 0,
+        };
+        let canopen_manager = match obj.get_value("CANopenManager") {
+            Some(_) => parse_required_bool(obj, "CANopenManager")?,
+            None => false,
+        };
+        let lss_serial_number = match obj.get_value("LSS_SerialNumber") {
+            Some(_) => Some(parse_required_uint(obj, "LSS_SerialNumber")?),
+            None => None,
+        };
+        Ok(DCFDeviceCommissioning {
+            node_id,
+            node_name: optional_str("NodeName")?,
+            baudrate,
+            net_number,
+            network_name: optional_str("NetworkName")?,
+            canopen_manager,
+            lss_serial_number,
+        })
+    }
+
+    //Empty names can not be written and are left out
+    pub(crate) fn keys(&self) -> Vec<(&'static str, String)> {
+        let mut keys = vec![("NodeID", format!("0x{:X}", self.node_id))];
+        if !self.node_name.is_empty() {
+            keys.push(("NodeName", self.node_name.clone()));
+        }
+        keys.extend([
+            ("Baudrate", self.baudrate.to_string()),
+            ("NetNumber", self.net_number.to_string()),
+        ]);
+        if !self.network_name.is_empty() {
+            keys.push(("NetworkName", self.network_name.clone()));
+        }
+        keys.push(("CANopenManager", bool_str(self.canopen_manager)));
+        if let Some(serial) = self.lss_serial_number {
+            keys.push(("LSS_SerialNumber", format!("0x{:08X}", serial)));
+        }
+        keys
+    }
+}
@@ -0,0 +1,145 @@
+use std::collections::HashMap;
+use std::io::Write;
+
+use super::DCFDeviceCommissioning;
+use crate::ParseError;
+use crate::eds_file::{
+    Address, EDSFile, EDSObject, EDSValue, NodeIdOffset, write_info_sections,
+    write_object_sections, write_section,
+};
+use crate::structured_file::StructuredFile;
+
+#[derive(Debug)]
+pub struct DCFFile {
+    pub eds: EDSFile,
+    pub commissioning: DCFDeviceCommissioning,
+    pub parameter_values: HashMap<Address, Box<dyn EDSValue>>,
+}
+
+impl Clone for DCFFile {
+    fn clone(&self) -> Self {
+        DCFFile {
+            eds: self.eds.clone(),
+            commissioning: self.commissioning.clone(),
+            parameter_values: self
+                .parameter_values
+                .iter()
+                .map(|(k, v)| (*k, v.clone_box()))
+                .collect(),
+        }
+    }
+}
+
+impl DCFFile {
+    //Turns an EDS into a DCF for the given node without any configured values
+    pub fn from_eds(eds: EDSFile, node_id: u8) -> DCFFile {
+        DCFFile {
+            eds,
+            commissioning: DCFDeviceCommissioning::new(node_id),
+            parameter_values: HashMap::new(),
+        }
+    }
+
+    fn parse_parameter_value(
+        sfile: &StructuredFile,
+        obj: &EDSObject,
+        with_subindex: bool,
+        out: &mut HashMap<Address, Box<dyn EDSValue>>,
+    ) -> Result<(), ParseError> {
+        let Some(data_type) = obj.get_data_type() else {
+            return Ok(());
+        };
+        let section = EDSObject::make_addr_string(obj.get_address(), with_subindex);
+        let Some(sobj) = sfile.get_object(&section) else {
+            return Ok(());
+        };
+        if let Some(value) = EDSObject::parse_data(sobj, data_type, "ParameterValue")? {
+            out.insert(*obj.get_address(), value);
+        }
+        Ok(())
+    }
+
+    pub(crate) fn parse(sfile: &StructuredFile) -> Result<DCFFile, ParseError> {
+        let eds = EDSFile::parse(sfile)?;
+        let commissioning_obj = sfile
+            .get_object("DeviceComissioning")
+            .or_else(|| sfile.get_object("DeviceCommissioning"))
+            .ok_or(ParseError::MissingRequiredSection {
+                section: "DeviceComissioning".to_string(),
+            })?;
+        let commissioning = DCFDeviceCommissioning::parse(commissioning_obj)?;
+        let mut parameter_values = HashMap::new();
+        for obj in eds.get_objects() {
+            match obj.get_entries() {
+                Some(entries) => {
+                    for e in entries {
+                        Self::parse_parameter_value(sfile, e, true, &mut parameter_values)?;
+                    }
+                }
+                None => Self::parse_parameter_value(sfile, obj, false, &mut parameter_values)?,
+            }
+        }
+        Ok(DCFFile {
+            eds,
+            commissioning,
+            parameter_values,
+        })
+    }
+
+    pub fn get_node_id(&self) -> u8 {
+        self.commissioning.node_id
+    }
+
+    //The configured value falls back to the default if no ParameterValue is set
+    pub fn get_value(&self, addr: &Address) -> Option<&dyn EDSValue> {
+        self.parameter_values
+            .get(addr)
+            .map(|v| v.as_ref())
+            .or_else(|| self.eds.get_object(addr).and_then(|obj| obj.get_default()))
+    }
+
+    //Returns the EDS with every default replaced by the configured value and
+    //node id relative values resolved for this node
+    pub fn get_configured_eds(&self) -> EDSFile {
+        let mut out = self.eds.clone();
+        let node_id = self.get_node_id();
+        let mut addrs: Vec<Address> = Vec::new();
+        for obj in self.eds.get_objects() {
+            match obj.get_entries() {
+                Some(entries) => addrs.extend(entries.iter().map(|e| *e.get_address())),
+                None => addrs.push(*obj.get_address()),
+            }
+        }
+        for addr in addrs {
+            let Some(value) = self.get_value(&addr) else {
+                continue;
+            };
+            let resolved = match value.as_any().downcast_ref::<NodeIdOffset>() {
+                Some(offset) => out
+                    .get_object(&addr)
+                    .and_then(|o| o.get_data_type())
+                    .and_then(|t| offset.resolve_value(node_id, t)),
+                None => Some(value.clone_box()),
+            };
+            if let Some(obj) = out.get_object_mut(&addr) {
+                obj.set_default(resolved);
+            }
+        }
+        out
+    }
+
+    //Writes the file in DCF format, the EDS with the commissioning section
+    //and the configured values as ParameterValue of their objects
+    pub fn to_dcf_string(&self) -> String {
+        let mut out = String::new();
+        write_info_sections(&mut out, &self.eds);
+        write_section(&mut out, "DeviceComissioning", &self.commissioning.keys());
+        write_object_sections(&mut out, &self.eds, &self.parameter_values);
+        out
+    }
+
+    pub fn save(&self, filename: &str) -> Result<(), std::io::Error> {
+        let mut file = std::fs::File::create(filename)?;
+        file.write_all(self.to_dcf_string().as_bytes())
+    }
+}
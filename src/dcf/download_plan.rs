@@ -0,0 +1,286 @@
+use std::collections::HashMap;
+
+use super::{DCFFile, ObjectWrite, encode_concise_dcf};
+use crate::DcfError;
+use crate::eds_file::{
+    Address, DataType, EDSFile, EDSObject, eds_value_to_bytes, eds_value_to_u64,
+};
+use crate::pdo::{MAX_PDO_NUMBER, PdoDirection};
+use crate::sdo::SdoObjectStore;
+
+const PDO_COB_ID_INVALID: u32 = 0x80000000;
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum SkipReason {
+    //Bit 0 of ObjFlags in the EDS
+    RefuseWriteOnDownload,
+    NotWritable,
+    UnknownObject,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct SkippedWrite {
+    pub address: Address,
+    pub reason: SkipReason,
+}
+
+//Values the DCF is compared against, either the EDS defaults or whatever the
+//device reports
+struct Baseline<'a> {
+    defaults: EDSFile,
+    device: Option<&'a mut dyn SdoObjectStore>,
+    cache: HashMap<Address, Option<Vec<u8>>>,
+}
+
+impl Baseline<'_> {
+    fn get(&mut self, addr: &Address) -> Option<Vec<u8>> {
+        if let Some(cached) = self.cache.get(addr) {
+            return cached.clone();
+        }
+        let value = match &mut self.device {
+            Some(device) => device.read(addr).ok(),
+            None => {
+                let obj = self.defaults.get_object(addr)?;
+                eds_value_to_bytes(obj.get_default()?, obj.get_data_type()?)
+            }
+        };
+        self.cache.insert(*addr, value.clone());
+        value
+    }
+
+    fn get_u32(&mut self, addr: &Address) -> Option<u32> {
+        let data = self.get(addr)?;
+        let mut raw = [0u8; 4];
+        let len = data.len().min(4);
+        raw[..len].copy_from_slice(&data[..len]);
+        Some(u32::from_le_bytes(raw))
+    }
+}
+
+fn pdo_group(index: u16) -> Option<(PdoDirection, u16)> {
+    for direction in [PdoDirection::Receive, PdoDirection::Transmit] {
+        for base in [direction.communication_index(), direction.mapping_index()] {
+            if (base..base + MAX_PDO_NUMBER).contains(&index) {
+                return Some((direction, index - base + 1));
+            }
+        }
+    }
+    None
+}
+
+fn is_refused(file: &EDSFile, addr: &Address) -> bool {
+    file.get_object(addr)
+        .is_some_and(|o| o.is_write_on_download_refused())
+        || file
+            .get_index_object(addr.index)
+            .is_some_and(|o| o.is_write_on_download_refused())
+}
+
+fn is_writable(file: &EDSFile, addr: &Address) -> bool {
+    !is_refused(file, addr)
+        && file
+            .get_object(addr)
+            .and_then(|o| o.get_access_mode())
+            .is_some_and(|m| m.is_writable())
+}
+
+fn get_u32(write: &ObjectWrite) -> Option<u32> {
+    eds_value_to_u64(write.value.as_ref(), None).map(|v| v as u32)
+}
+
+//Ordered SDO writes bringing a device to the configuration of a DCF
+#[derive(Debug, Clone)]
+pub struct DownloadPlan {
+    pub node_id: u8,
+    pub writes: Vec<ObjectWrite>,
+    pub skipped: Vec<SkippedWrite>,
+}
+
+impl DownloadPlan {
+    //Writes every ParameterValue that differs from the EDS default
+    pub fn from_defaults(dcf: &DCFFile) -> DownloadPlan {
+        Self::plan(dcf, None)
+    }
+
+    //Writes every ParameterValue that differs from the value read from the
+    //device, values that can not be read are always written
+    pub fn from_device(dcf: &DCFFile, device: &mut dyn SdoObjectStore) -> DownloadPlan {
+        Self::plan(dcf, Some(device))
+    }
+
+    fn plan(dcf: &DCFFile, device: Option<&mut dyn SdoObjectStore>) -> DownloadPlan {
+        let node_id = dcf.get_node_id();
+        let configured = dcf.get_configured_eds();
+        let mut baseline = Baseline {
+            defaults: DCFFile::from_eds(dcf.eds.clone(), node_id).get_configured_eds(),
+            device,
+            cache: HashMap::new(),
+        };
+        let mut addrs: Vec<Address> = dcf.parameter_values.keys().copied().collect();
+        addrs.sort_by_key(|a| (a.index, a.subindex));
+
+        let mut changes = Vec::new();
+        let mut skipped = Vec::new();
+        for addr in addrs {
+            let reason = match dcf.eds.get_object(&addr) {
+                None => Some(SkipReason::UnknownObject),
+                Some(_) if is_refused(&dcf.eds, &addr) => Some(SkipReason::RefuseWriteOnDownload),
+                Some(_) if !is_writable(&dcf.eds, &addr) => Some(SkipReason::NotWritable),
+                Some(_) => None,
+            };
+            if let Some(reason) = reason {
+                skipped.push(SkippedWrite {
+                    address: addr,
+                    reason,
+                });
+                continue;
+            }
+            let Some(obj) = configured.get_object(&addr) else {
+                continue;
+            };
+            let (Some(data_type), Some(value)) = (obj.get_data_type(), obj.get_default()) else {
+                continue;
+            };
+            let bytes = eds_value_to_bytes(value, data_type);
+            if bytes.is_some() && bytes == baseline.get(&addr) {
+                continue;
+            }
+            changes.push(ObjectWrite::new(addr, data_type.clone(), value.clone_box()));
+        }
+
+        let mut writes = Vec::new();
+        let mut done = Vec::new();
+        for change in &changes {
+            match pdo_group(change.address.index) {
+                None => writes.push(change.clone()),
+                Some(group) if !done.contains(&group) => {
+                    done.push(group);
+                    writes.extend(Self::plan_pdo(dcf, &changes, &mut baseline, group));
+                }
+                Some(_) => {}
+            }
+        }
+        DownloadPlan {
+            node_id,
+            writes,
+            skipped,
+        }
+    }
+
+    //Applies the CiA 301 order for PDO reconfiguration: invalidate the PDO via
+    //bit 31 of its COB-ID, change the communication parameters, clear mapping
+    //sub0, write the entries, restore sub0 and finally the COB-ID
+    fn plan_pdo(
+        dcf: &DCFFile,
+        changes: &[ObjectWrite],
+        baseline: &mut Baseline,
+        (direction, number): (PdoDirection, u16),
+    ) -> Vec<ObjectWrite> {
+        let comm_index = direction.communication_index() + number - 1;
+        let map_index = direction.mapping_index() + number - 1;
+        let cob_addr = Address::new(comm_index, 1);
+        let count_addr = Address::new(map_index, 0);
+        let data_type = |addr: &Address, fallback: DataType| {
+            dcf.eds
+                .get_object(addr)
+                .and_then(EDSObject::get_data_type)
+                .cloned()
+                .unwrap_or(fallback)
+        };
+
+        let cob_change = changes.iter().find(|w| w.address == cob_addr);
+        let comm_changes: Vec<&ObjectWrite> = changes
+            .iter()
+            .filter(|w| w.address.index == comm_index && w.address != cob_addr)
+            .collect();
+        let map_changes: Vec<&ObjectWrite> = changes
+            .iter()
+            .filter(|w| w.address.index == map_index)
+            .collect();
+        let target_cob = match cob_change {
+            Some(w) => get_u32(w),
+            None => baseline.get_u32(&cob_addr),
+        };
+        let current_cob = baseline.get_u32(&cob_addr).or(target_cob);
+
+        //Changing anything but the COB-ID itself to an invalid one requires
+        //the PDO to be invalid first
+        let needs_disable = !comm_changes.is_empty()
+            || !map_changes.is_empty()
+            || target_cob.is_some_and(|c| c & PDO_COB_ID_INVALID == 0);
+        let mut out = Vec::new();
+        let mut disabled = None;
+        if needs_disable
+            && is_writable(&dcf.eds, &cob_addr)
+            && let Some(current) = current_cob
+            && current & PDO_COB_ID_INVALID == 0
+        {
+            let value = current | PDO_COB_ID_INVALID;
+            out.push(ObjectWrite::new(
+                cob_addr,
+                data_type(&cob_addr, DataType::UInt32),
+                Box::new(value),
+            ));
+            disabled = Some(value);
+        }
+        out.extend(comm_changes.into_iter().cloned());
+
+        if !map_changes.is_empty() {
+            let count_type = data_type(&count_addr, DataType::UInt8);
+            let count = match map_changes.iter().find(|w| w.address == count_addr) {
+                Some(w) => get_u32(w),
+                None => baseline.get_u32(&count_addr),
+            };
+            out.push(ObjectWrite::new(
+                count_addr,
+                count_type.clone(),
+                Box::new(0u8),
+            ));
+            out.extend(
+                map_changes
+                    .into_iter()
+                    .filter(|w| w.address != count_addr)
+                    .cloned(),
+            );
+            if let Some(count) = count {
+                out.push(ObjectWrite::new(
+                    count_addr,
+                    count_type,
+                    Box::new(count as u8),
+                ));
+            }
+        }
+
+        match (cob_change, disabled, target_cob) {
+            (_, Some(disabled), Some(target)) if target != disabled => out.push(ObjectWrite::new(
+                cob_addr,
+                data_type(&cob_addr, DataType::UInt32),
+                Box::new(target),
+            )),
+            (Some(w), None, _) => out.push(w.clone()),
+            _ => {}
+        }
+        out
+    }
+
+    pub fn to_concise_dcf(&self) -> Result<Vec<u8>, DcfError> {
+        encode_concise_dcf(&self.writes)
+    }
+
+    //Performs the writes in order and stops at the first one the device
+    //rejects
+    pub fn execute(&self, target: &mut dyn SdoObjectStore) -> Result<(), DcfError> {
+        for w in &self.writes {
+            let data = w
+                .get_bytes()
+                .ok_or(DcfError::InvalidValue { addr: w.address })?;
+            target
+                .write(&w.address, &data)
+                .map_err(|code| DcfError::DownloadFailed {
+                    addr: w.address,
+                    code,
+                })?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,14 @@
+mod concise_dcf;
+mod dcf_device_commissioning;
+mod dcf_file;
+mod download_plan;
+mod object_write;
+
+#[cfg(test)]
+mod tests;
+
+pub use concise_dcf::*;
+pub use dcf_device_commissioning::DCFDeviceCommissioning;
+pub use dcf_file::DCFFile;
+pub use download_plan::*;
+pub use object_write::ObjectWrite;
@@ -0,0 +1,72 @@
+use std::fmt::Debug;
+
+use crate::communication_profile::get_standard_object;
+use crate::eds_file::{
+    Address, DataType, EDSFile, EDSValue, ObjectType, eds_value_to_bytes, eds_value_to_string,
+    section_name,
+};
+
+#[derive(Debug)]
+pub struct ObjectWrite {
+    pub address: Address,
+    pub data_type: DataType,
+    pub value: Box<dyn EDSValue>,
+}
+
+impl Clone for ObjectWrite {
+    fn clone(&self) -> Self {
+        ObjectWrite {
+            address: self.address,
+            data_type: self.data_type.clone(),
+            value: self.value.clone_box(),
+        }
+    }
+}
+
+impl ObjectWrite {
+    pub fn new(address: Address, data_type: DataType, value: Box<dyn EDSValue>) -> ObjectWrite {
+        ObjectWrite {
+            address,
+            data_type,
+            value,
+        }
+    }
+
+    pub fn get_bytes(&self) -> Option<Vec<u8>> {
+        eds_value_to_bytes(self.value.as_ref(), &self.data_type)
+    }
+
+    pub fn get_value_string(&self) -> Option<String> {
+        eds_value_to_string(self.value.as_ref(), &self.data_type)
+    }
+
+    //Variables and domains have a section of their own, entries of arrays and
+    //records are written to their sub-object section. The object type is
+    //taken from the EDS and from CiA 301 for standard objects the EDS lacks.
+    pub fn get_section_name(&self, eds: &EDSFile) -> Option<String> {
+        let index = self.address.index;
+        let object_type = match eds.get_index_object(index) {
+            Some(obj) => obj.get_obj_type().clone(),
+            None => get_standard_object(index)?.object_type.clone(),
+        };
+        let is_sub = !matches!(object_type, ObjectType::Variable | ObjectType::Domain);
+        Some(section_name(&self.address, is_sub))
+    }
+
+    //Writes are listed in order, repeated writes to the same object keep the
+    //last value as a DCF can only hold one value per object
+    pub fn to_dcf_string(writes: &[ObjectWrite], eds: &EDSFile) -> Option<String> {
+        let mut out = String::new();
+        for (i, w) in writes.iter().enumerate() {
+            if writes[i + 1..].iter().any(|o| o.address == w.address) {
+                continue;
+            }
+            out += &format!(
+                "[{}]\nParameterValue={}\n\n",
+                w.get_section_name(eds)?,
+                w.get_value_string()?
+            );
+        }
+        Some(out)
+    }
+}
@@ -0,0 +1,244 @@
+use std::collections::HashMap;
+
+use crate::dcf::{
+    DCFFile, DownloadPlan, ObjectWrite, SkipReason, SkippedWrite, decode_concise_dcf,
+    encode_concise_dcf,
+};
+use crate::eds_file::{Address, DataType, NodeIdOffset, eds_value_to_u64};
+use crate::object_dictionary::ObjectDictionary;
+use crate::sdo::SdoAbortCode;
+use crate::tests::utils::*;
+use crate::{DcfError, load_dcf_string};
+
+#[test]
+fn test_concise_dcf() {
+    let writes = vec![
+        ObjectWrite::new(Address::new(0x1017, 0), DataType::UInt16, Box::new(500u16)),
+        ObjectWrite::new(
+            Address::new(0x1A00, 1),
+            DataType::UInt32,
+            Box::new(0x60410010u32),
+        ),
+    ];
+    let data = encode_concise_dcf(&writes).expect("Failed to encode concise dcf!");
+    assert_eq!(
+        data,
+        vec![
+            0x02, 0x00, 0x00, 0x00, 0x17, 0x10, 0x00, 0x02, 0x00, 0x00, 0x00, 0xF4, 0x01, 0x00,
+            0x1A, 0x01, 0x04, 0x00, 0x00, 0x00, 0x10, 0x00, 0x41, 0x60
+        ]
+    );
+    let decoded = decode_concise_dcf(&data).expect("Failed to decode concise dcf!");
+    assert_eq!(
+        decoded,
+        vec![
+            (Address::new(0x1017, 0), vec![0xF4, 0x01]),
+            (Address::new(0x1A00, 1), vec![0x10, 0x00, 0x41, 0x60])
+        ]
+    );
+    assert_eq!(
+        decode_concise_dcf(&data[..20]),
+        Err(DcfError::TruncatedConciseDcf { length: 20 })
+    );
+}
+
+#[test]
+fn test_concise_dcf_unresolved() {
+    let writes = vec![ObjectWrite::new(
+        Address::new(0x1014, 0),
+        DataType::UInt32,
+        Box::new(NodeIdOffset { offset: 0x80 }),
+    )];
+    assert_eq!(
+        encode_concise_dcf(&writes),
+        Err(DcfError::InvalidValue {
+            addr: Address::new(0x1014, 0)
+        })
+    );
+    let eds = make_dcf_file(&PDO_DCF.to_vec())
+        .expect("Failed to parse dcf file!")
+        .eds;
+    assert_eq!(
+        ObjectWrite::to_dcf_string(&writes, &eds),
+        Some("[1014]\nParameterValue=$NODEID+0x80\n\n".to_string())
+    );
+}
+
+//PDO_DCF with extra lines inserted after the given section headers
+fn make_download_dcf(extra: &[(&str, &str)]) -> DCFFile {
+    let mut lines = Vec::new();
+    for line in PDO_DCF {
+        lines.push(line);
+        for (section, extra_line) in extra {
+            if line == *section {
+                lines.push(*extra_line);
+            }
+        }
+    }
+    make_dcf_file(&lines).expect("Failed to parse dcf file!")
+}
+
+#[test]
+fn test_dcf_fragment() {
+    let eds = make_download_dcf(&[]).eds;
+    let writes = vec![
+        ObjectWrite::new(Address::new(0x1017, 0), DataType::UInt16, Box::new(500u16)),
+        ObjectWrite::new(
+            Address::new(0x1800, 1),
+            DataType::UInt32,
+            Box::new(0x80000182u32),
+        ),
+    ];
+    let fragment = ObjectWrite::to_dcf_string(&writes, &eds).expect("Failed to write dcf!");
+    assert_eq!(
+        fragment,
+        "[1017]\nParameterValue=0x01F4\n\n[1800sub1]\nParameterValue=0x80000182\n\n"
+    );
+
+    //The sections of the fragment are the ones the DCF parser reads values from
+    let lines: Vec<&str> = fragment.lines().filter(|l| !l.is_empty()).collect();
+    let extra: Vec<(&str, &str)> = lines.chunks(2).map(|c| (c[0], c[1])).collect();
+    let dcf = make_download_dcf(&extra);
+    let value = |addr: Address| {
+        dcf.parameter_values
+            .get(&addr)
+            .and_then(|v| eds_value_to_u64(v.as_ref(), None))
+    };
+    assert_eq!(value(Address::new(0x1017, 0)), Some(500));
+    assert_eq!(value(Address::new(0x1800, 1)), Some(0x80000182));
+}
+
+fn plan_values(plan: &DownloadPlan) -> Vec<(u16, u8, u64)> {
+    plan.writes
+        .iter()
+        .map(|w| {
+            let value = eds_value_to_u64(w.value.as_ref(), None).expect("Invalid value!");
+            (w.address.index, w.address.subindex, value)
+        })
+        .collect()
+}
+
+#[test]
+fn test_download_plan() {
+    let dcf = make_download_dcf(&[
+        ("[1017]", "ObjFlags=0x1"),
+        ("[1017]", "ParameterValue=200"),
+        ("[1800sub2]", "ParameterValue=1"),
+        ("[1A00sub1]", "ParameterValue=0x20000008"),
+        ("[2000]", "ParameterValue=5"),
+    ]);
+    let plan = DownloadPlan::from_defaults(&dcf);
+    assert_eq!(
+        plan_values(&plan),
+        vec![
+            (0x1005, 0, 0x40000080),
+            (0x1006, 0, 10000),
+            (0x1800, 1, 0x80000182),
+            (0x1800, 2, 1),
+            (0x1A00, 0, 0),
+            (0x1A00, 1, 0x20000008),
+            (0x1A00, 0, 1),
+            (0x1800, 1, 0x182),
+        ]
+    );
+    assert_eq!(
+        plan.skipped,
+        vec![
+            SkippedWrite {
+                address: Address::new(0x1017, 0),
+                reason: SkipReason::RefuseWriteOnDownload
+            },
+            SkippedWrite {
+                address: Address::new(0x2000, 0),
+                reason: SkipReason::NotWritable
+            },
+        ]
+    );
+
+    let mut device: HashMap<Address, Vec<u8>> = HashMap::new();
+    plan.execute(&mut device).expect("Failed to execute plan!");
+    assert_eq!(
+        device[&Address::new(0x1800, 1)],
+        vec![0x82, 0x01, 0x00, 0x00]
+    );
+    assert_eq!(device[&Address::new(0x1A00, 0)], vec![1]);
+
+    //Nothing left to do once the device holds the configuration
+    let plan = DownloadPlan::from_device(&dcf, &mut device);
+    assert!(plan.writes.is_empty());
+}
+
+#[test]
+fn test_download_plan_from_device() {
+    let dcf = make_download_dcf(&[("[1800sub1]", "ParameterValue=0x80000182")]);
+    let mut device: HashMap<Address, Vec<u8>> = HashMap::new();
+    device.insert(
+        Address::new(0x1005, 0),
+        0x40000080u32.to_le_bytes().to_vec(),
+    );
+    device.insert(Address::new(0x1006, 0), 10000u32.to_le_bytes().to_vec());
+    device.insert(Address::new(0x1800, 1), 0x182u32.to_le_bytes().to_vec());
+
+    //Invalidating a PDO is a single write
+    let plan = DownloadPlan::from_device(&dcf, &mut device);
+    assert_eq!(plan_values(&plan), vec![(0x1800, 1, 0x80000182)]);
+
+    //Changing the COB-ID of a valid PDO invalidates it first
+    let dcf = make_download_dcf(&[("[1800sub1]", "ParameterValue=0x282")]);
+    let plan = DownloadPlan::from_device(&dcf, &mut device);
+    assert_eq!(
+        plan_values(&plan),
+        vec![(0x1800, 1, 0x80000182), (0x1800, 1, 0x282)]
+    );
+
+    //The device rejects writes to read only objects
+    let mut store = ObjectDictionary::new(&dcf.eds, 2);
+    plan.execute(&mut store).expect("Failed to execute plan!");
+    let plan = DownloadPlan {
+        node_id: 2,
+        writes: vec![ObjectWrite::new(
+            Address::new(0x2000, 0),
+            DataType::UInt16,
+            Box::new(5u16),
+        )],
+        skipped: Vec::new(),
+    };
+    assert_eq!(
+        plan.execute(&mut store),
+        Err(DcfError::DownloadFailed {
+            addr: Address::new(0x2000, 0),
+            code: SdoAbortCode::WriteReadOnly
+        })
+    );
+}
+
+#[test]
+fn test_write_dcf() {
+    let mut dcf = make_dcf_file(&PDO_DCF.to_vec()).expect("Failed to parse dcf file!");
+    dcf.commissioning.node_name = "Left wheel".to_string();
+    dcf.parameter_values.insert(
+        Address::new(0x1800, 1),
+        Box::new(NodeIdOffset { offset: 0x180 }),
+    );
+    let data = dcf.to_dcf_string();
+    assert!(data.contains("[DeviceComissioning]\nNodeID=0x2\nNodeName=Left wheel\nBaudrate=500\n"));
+    assert!(data.contains("ParameterValue=$NODEID+0x180\n"));
+
+    let reparsed = load_dcf_string(&data).expect("Failed to parse written dcf file!");
+    assert_eq!(reparsed.get_node_id(), 2);
+    assert_eq!(reparsed.commissioning.node_name, "Left wheel");
+    assert_eq!(reparsed.commissioning.baudrate, 500);
+    assert_eq!(reparsed.parameter_values.len(), 3);
+    for addr in [Address::new(0x1005, 0), Address::new(0x1006, 0)] {
+        assert_eq!(
+            reparsed
+                .get_value(&addr)
+                .and_then(|v| eds_value_to_u64(v, None)),
+            dcf.get_value(&addr).and_then(|v| eds_value_to_u64(v, None))
+        );
+    }
+    let offset = reparsed
+        .get_value(&Address::new(0x1800, 1))
+        .and_then(|v| v.as_any().downcast_ref::<NodeIdOffset>());
+    assert_eq!(offset.map(|o| o.offset), Some(0x180));
+}
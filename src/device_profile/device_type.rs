@@ -0,0 +1,65 @@
+use std::fmt::Display;
+
+use crate::eds_file::{Address, EDSFile};
+
+pub const DEVICE_PROFILE_IO: u16 = 401;
+pub const DEVICE_PROFILE_DRIVE: u16 = 402;
+
+//Additional information bits of CiA 401 devices
+pub const IO_DIGITAL_INPUT: u16 = 0x0001;
+pub const IO_DIGITAL_OUTPUT: u16 = 0x0002;
+pub const IO_ANALOG_INPUT: u16 = 0x0004;
+pub const IO_ANALOG_OUTPUT: u16 = 0x0008;
+
+//Content of object 0x1000, the device profile number in the low word and
+//profile specific additional information in the high word
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct DeviceType {
+    pub profile: u16,
+    pub additional_info: u16,
+}
+
+impl DeviceType {
+    pub fn decode(raw: u32) -> DeviceType {
+        DeviceType {
+            profile: raw as u16,
+            additional_info: (raw >> 16) as u16,
+        }
+    }
+
+    pub fn encode(&self) -> u32 {
+        ((self.additional_info as u32) << 16) | self.profile as u32
+    }
+
+    //Decodes the default of 0x1000
+    pub fn read(file: &EDSFile) -> Option<DeviceType> {
+        file.get_default_u64(&Address::new(0x1000, 0), 0)
+            .map(|raw| Self::decode(raw as u32))
+    }
+
+    pub fn get_profile_name(&self) -> Option<&'static str> {
+        match self.profile {
+            0 => Some("No standardized device profile"),
+            DEVICE_PROFILE_IO => Some("CiA 401 generic I/O modules"),
+            DEVICE_PROFILE_DRIVE => Some("CiA 402 drives and motion control"),
+            _ => None,
+        }
+    }
+}
+
+impl Display for DeviceType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.get_profile_name() {
+            Some(name) => write!(
+                f,
+                "{} (profile {}, additional info 0x{:04X})",
+                name, self.profile, self.additional_info
+            ),
+            None => write!(
+                f,
+                "Profile {}, additional info 0x{:04X}",
+                self.profile, self.additional_info
+            ),
+        }
+    }
+}
@@ -0,0 +1,48 @@
+mod device_type;
+mod profile_objects;
+
+#[cfg(test)]
+mod tests;
+
+pub use device_type::*;
+pub use profile_objects::*;
+
+use crate::communication_profile::{Presence, StandardObjectIssue, validate_object};
+use crate::eds_file::EDSFile;
+
+//Result of checking a file against the device profile declared in 0x1000
+#[derive(Debug, PartialEq, Clone)]
+pub struct ProfileCheck {
+    pub device_type: Option<DeviceType>,
+    pub known_profile: bool,
+    pub issues: Vec<StandardObjectIssue>,
+}
+
+impl ProfileCheck {
+    //Only files declaring a profile with a built-in table can conform
+    pub fn is_conformant(&self) -> bool {
+        self.known_profile && self.issues.is_empty()
+    }
+}
+
+pub fn check_device_profile(file: &EDSFile) -> ProfileCheck {
+    let device_type = DeviceType::read(file);
+    let objects = device_type.as_ref().and_then(get_profile_objects);
+    let mut issues = Vec::new();
+    for standard in objects.iter().flatten() {
+        match file.get_index_object(standard.index) {
+            None if standard.presence == Presence::Mandatory => {
+                issues.push(StandardObjectIssue::MissingObject {
+                    index: standard.index,
+                })
+            }
+            None => {}
+            Some(obj) => issues.extend(validate_object(obj, standard)),
+        }
+    }
+    ProfileCheck {
+        device_type,
+        known_profile: objects.is_some(),
+        issues,
+    }
+}
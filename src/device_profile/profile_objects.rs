@@ -0,0 +1,137 @@
+use super::{
+    DEVICE_PROFILE_DRIVE, DEVICE_PROFILE_IO, DeviceType, IO_ANALOG_INPUT, IO_ANALOG_OUTPUT,
+    IO_DIGITAL_INPUT, IO_DIGITAL_OUTPUT,
+};
+use crate::communication_profile::{
+    Presence, RO, StandardObject, ZERO, entries, highest_subindex, value,
+};
+use crate::eds_file::{AccessMode, DataType, ObjectType};
+
+//Written by the master, usually through an RPDO
+const RX: &[AccessMode] = &[
+    AccessMode::ReadWrite,
+    AccessMode::ReadWritePDOWrite,
+    AccessMode::WriteOnly,
+];
+//Reported by the device, usually through a TPDO
+const TX: &[AccessMode] = &[AccessMode::ReadOnly, AccessMode::ReadWritePDORead];
+
+pub static DRIVE_OBJECTS: [StandardObject; 5] = [
+    StandardObject {
+        index: 0x6040,
+        count: 1,
+        name: "Controlword",
+        object_type: ObjectType::Variable,
+        presence: Presence::Mandatory,
+        entries: &[value(DataType::UInt16, RX, ZERO)],
+    },
+    StandardObject {
+        index: 0x6041,
+        count: 1,
+        name: "Statusword",
+        object_type: ObjectType::Variable,
+        presence: Presence::Mandatory,
+        entries: &[value(DataType::UInt16, TX, ZERO)],
+    },
+    StandardObject {
+        index: 0x6060,
+        count: 1,
+        name: "Modes of operation",
+        object_type: ObjectType::Variable,
+        presence: Presence::Mandatory,
+        entries: &[value(DataType::Int8, RX, ZERO)],
+    },
+    StandardObject {
+        index: 0x6061,
+        count: 1,
+        name: "Modes of operation display",
+        object_type: ObjectType::Variable,
+        presence: Presence::Mandatory,
+        entries: &[value(DataType::Int8, TX, ZERO)],
+    },
+    StandardObject {
+        index: 0x6502,
+        count: 1,
+        name: "Supported drive modes",
+        object_type: ObjectType::Variable,
+        presence: Presence::Optional,
+        entries: &[value(DataType::UInt32, RO, ZERO)],
+    },
+];
+
+//Each I/O kind announced in the additional information of 0x1000 requires its
+//8 or 16 bit access object
+pub static IO_OBJECTS: [(u16, StandardObject); 4] = [
+    (
+        IO_DIGITAL_INPUT,
+        StandardObject {
+            index: 0x6000,
+            count: 1,
+            name: "Read input 8-bit",
+            object_type: ObjectType::Array,
+            presence: Presence::Mandatory,
+            entries: &[
+                highest_subindex(),
+                entries(254, "Read input", DataType::UInt8, TX, 1),
+            ],
+        },
+    ),
+    (
+        IO_DIGITAL_OUTPUT,
+        StandardObject {
+            index: 0x6200,
+            count: 1,
+            name: "Write output 8-bit",
+            object_type: ObjectType::Array,
+            presence: Presence::Mandatory,
+            entries: &[
+                highest_subindex(),
+                entries(254, "Write output", DataType::UInt8, RX, 1),
+            ],
+        },
+    ),
+    (
+        IO_ANALOG_INPUT,
+        StandardObject {
+            index: 0x6401,
+            count: 1,
+            name: "Read analog input 16-bit",
+            object_type: ObjectType::Array,
+            presence: Presence::Mandatory,
+            entries: &[
+                highest_subindex(),
+                entries(254, "Read analog input", DataType::Int16, TX, 1),
+            ],
+        },
+    ),
+    (
+        IO_ANALOG_OUTPUT,
+        StandardObject {
+            index: 0x6411,
+            count: 1,
+            name: "Write analog output 16-bit",
+            object_type: ObjectType::Array,
+            presence: Presence::Mandatory,
+            entries: &[
+                highest_subindex(),
+                entries(254, "Write analog output", DataType::Int16, RX, 1),
+            ],
+        },
+    ),
+];
+
+//Objects of the profile declared by the device type, None for
+//profiles without a built-in table
+pub fn get_profile_objects(device_type: &DeviceType) -> Option<Vec<&'static StandardObject>> {
+    match device_type.profile {
+        DEVICE_PROFILE_DRIVE => Some(DRIVE_OBJECTS.iter().collect()),
+        DEVICE_PROFILE_IO => Some(
+            IO_OBJECTS
+                .iter()
+                .filter(|(bit, _)| device_type.additional_info & bit != 0)
+                .map(|(_, obj)| obj)
+                .collect(),
+        ),
+        _ => None,
+    }
+}
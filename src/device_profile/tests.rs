@@ -0,0 +1,99 @@
+use crate::communication_profile::StandardObjectIssue;
+use crate::device_profile::{
+    DEVICE_PROFILE_DRIVE, DeviceType, IO_ANALOG_INPUT, IO_ANALOG_OUTPUT, IO_DIGITAL_OUTPUT,
+    check_device_profile, get_profile_objects,
+};
+use crate::eds_file::{Address, DataType};
+use crate::load_file;
+use crate::tests::utils::*;
+
+#[test]
+fn test_device_type() {
+    let device_type = DeviceType::decode(0x00060192);
+    assert_eq!(device_type.profile, DEVICE_PROFILE_DRIVE);
+    assert_eq!(device_type.additional_info, 6);
+    assert_eq!(device_type.encode(), 0x00060192);
+    assert_eq!(
+        device_type.to_string(),
+        "CiA 402 drives and motion control (profile 402, additional info 0x0006)"
+    );
+    assert_eq!(
+        DeviceType::decode(0x12345678).to_string(),
+        "Profile 22136, additional info 0x1234"
+    );
+
+    let io = DeviceType::decode(0x000A0191);
+    let indices: Vec<u16> = get_profile_objects(&io)
+        .expect("Missing profile objects!")
+        .iter()
+        .map(|o| o.index)
+        .collect();
+    assert_eq!(indices, vec![0x6200, 0x6411]);
+    assert_eq!(io.additional_info, IO_DIGITAL_OUTPUT | IO_ANALOG_OUTPUT);
+    assert!(get_profile_objects(&DeviceType::decode(0x0195)).is_none());
+}
+
+#[test]
+fn test_drive_profile() {
+    let file = load_file("res/micro-motor.eds").expect("Failed to load eds file!");
+    let check = check_device_profile(&file);
+    assert_eq!(check.device_type, Some(DeviceType::decode(0x60192)));
+    assert!(check.is_conformant());
+}
+
+#[test]
+fn test_io_profile() {
+    let lines = vec![
+        "[MandatoryObjects]",
+        "SupportedObjects=1",
+        "1=0x1000",
+        "[1000]",
+        "ParameterName=Device type",
+        "ObjectType=0x7",
+        "DataType=0x0007",
+        "AccessType=ro",
+        "DefaultValue=0x00050191",
+        "[OptionalObjects]",
+        "SupportedObjects=0",
+        "[ManufacturerObjects]",
+        "SupportedObjects=1",
+        "1=0x6000",
+        "[6000]",
+        "ParameterName=Read input 8-bit",
+        "ObjectType=0x8",
+        "SubNumber=2",
+        "[6000sub0]",
+        "ParameterName=Number of inputs",
+        "ObjectType=0x7",
+        "DataType=0x0005",
+        "AccessType=ro",
+        "DefaultValue=1",
+        "[6000sub1]",
+        "ParameterName=Read input 1h to 8h",
+        "ObjectType=0x7",
+        "DataType=0x0006",
+        "AccessType=ro",
+        "PDOMapping=1",
+    ];
+    let file = make_eds_file(&lines).expect("Failed to parse eds file!");
+    let check = check_device_profile(&file);
+    assert!(check.known_profile);
+    assert!(!check.is_conformant());
+    assert_eq!(
+        check
+            .device_type
+            .map(|t| t.additional_info & IO_ANALOG_INPUT),
+        Some(IO_ANALOG_INPUT)
+    );
+    assert_eq!(
+        check.issues,
+        vec![
+            StandardObjectIssue::WrongDataType {
+                addr: Address::new(0x6000, 1),
+                expected: DataType::UInt8,
+                found: DataType::UInt16
+            },
+            StandardObjectIssue::MissingObject { index: 0x6401 },
+        ]
+    );
+}
@@ -0,0 +1,116 @@
+mod scan_entry;
+
+#[cfg(test)]
+mod tests;
+
+pub use scan_entry::*;
+
+use std::fmt::Display;
+
+use crate::eds_file::{Address, EDSFile};
+use crate::sdo::SdoObjectStore;
+
+pub const DEVICE_TYPE_INDEX: u16 = 0x1000;
+pub const IDENTITY_INDEX: u16 = 0x1018;
+
+//Identity objects read on scan with their fallback names
+const SCAN_OBJECTS: [(u16, u8, &str); 5] = [
+    (DEVICE_TYPE_INDEX, 0, "Device type"),
+    (IDENTITY_INDEX, 1, "Vendor-ID"),
+    (IDENTITY_INDEX, 2, "Product code"),
+    (IDENTITY_INDEX, 3, "Revision number"),
+    (IDENTITY_INDEX, 4, "Serial number"),
+];
+
+//Result of comparing the identity read from a node to its EDS
+#[derive(Debug, PartialEq, Clone)]
+pub struct DeviceScanReport {
+    pub node_id: u8,
+    pub entries: Vec<ScanEntry>,
+}
+
+impl DeviceScanReport {
+    //Reads device type and identity from source, which may be a map of values
+    //read earlier or an SDO client on the bus
+    pub fn scan(eds: &EDSFile, node_id: u8, source: &mut dyn SdoObjectStore) -> DeviceScanReport {
+        let info = &eds.device_info;
+        let mut entries = Vec::new();
+        for (index, subindex, default_name) in SCAN_OBJECTS {
+            let address = Address::new(index, subindex);
+            let obj = eds.get_object(&address);
+            let name = obj
+                .map(|o| o.get_name().clone())
+                .unwrap_or(default_name.to_string());
+            let expected = match (index, subindex) {
+                (DEVICE_TYPE_INDEX, _) => eds.get_default_u64(&address, node_id).map(|v| v as u32),
+                (_, 1) => Some(info.vendor_number),
+                (_, 2) => Some(info.product_number),
+                (_, 3) => Some(info.revision_number),
+                _ => None,
+            };
+            let refused = obj.is_some_and(|o| o.is_read_on_scan_refused())
+                || eds
+                    .get_index_object(index)
+                    .is_some_and(|o| o.is_read_on_scan_refused());
+            let (actual, status) = if refused {
+                (None, ScanStatus::Skipped)
+            } else {
+                match source.read(&address) {
+                    Err(code) => (None, ScanStatus::ReadFailed { code }),
+                    Ok(data) if data.len() != 4 => {
+                        (None, ScanStatus::InvalidLength { length: data.len() })
+                    }
+                    Ok(data) => {
+                        let value = u32::from_le_bytes([data[0], data[1], data[2], data[3]]);
+                        let status = match expected {
+                            None => ScanStatus::NotCompared,
+                            Some(e) if e == value => ScanStatus::Match,
+                            Some(_) => ScanStatus::Mismatch,
+                        };
+                        (Some(value), status)
+                    }
+                }
+            };
+            entries.push(ScanEntry {
+                address,
+                name,
+                expected,
+                actual,
+                status,
+            });
+        }
+        DeviceScanReport { node_id, entries }
+    }
+
+    pub fn get_mismatches(&self) -> Vec<&ScanEntry> {
+        self.entries.iter().filter(|e| e.is_mismatch()).collect()
+    }
+
+    //True if nothing read contradicts the EDS, skipped and failed reads are
+    //not counted as mismatches
+    pub fn is_match(&self) -> bool {
+        self.get_mismatches().is_empty()
+    }
+
+    pub fn get_serial_number(&self) -> Option<u32> {
+        self.entries
+            .iter()
+            .find(|e| e.address == Address::new(IDENTITY_INDEX, 4))
+            .and_then(|e| e.actual)
+    }
+}
+
+impl Display for DeviceScanReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let result = if self.is_match() {
+            "matches"
+        } else {
+            "does not match"
+        };
+        writeln!(f, "Node {} {} its EDS", self.node_id, result)?;
+        for entry in &self.entries {
+            writeln!(f, "  {}", entry)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,61 @@
+use std::fmt::Display;
+
+use crate::eds_file::Address;
+use crate::sdo::SdoAbortCode;
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum ScanStatus {
+    Match,
+    Mismatch,
+    //Read but nothing to compare against, e.g. the serial number
+    NotCompared,
+    //Bit 1 of ObjFlags in the EDS refuses reading the object on scan
+    Skipped,
+    ReadFailed { code: SdoAbortCode },
+    InvalidLength { length: usize },
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct ScanEntry {
+    pub address: Address,
+    pub name: String,
+    pub expected: Option<u32>,
+    pub actual: Option<u32>,
+    pub status: ScanStatus,
+}
+
+impl ScanEntry {
+    pub fn is_mismatch(&self) -> bool {
+        self.status == ScanStatus::Mismatch
+    }
+}
+
+fn format_value(value: Option<u32>) -> String {
+    match value {
+        Some(v) => format!("0x{:08X}", v),
+        None => "-".to_string(),
+    }
+}
+
+impl Display for ScanEntry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let addr = format!("0x{:04X}.{}", self.address.index, self.address.subindex);
+        let (expected, actual) = (format_value(self.expected), format_value(self.actual));
+        match &self.status {
+            ScanStatus::Match => write!(f, "{} {}: {} matches", addr, self.name, actual),
+            ScanStatus::Mismatch => write!(
+                f,
+                "{} {}: expected {} but read {}",
+                addr, self.name, expected, actual
+            ),
+            ScanStatus::NotCompared => write!(f, "{} {}: read {}", addr, self.name, actual),
+            ScanStatus::Skipped => write!(f, "{} {}: skipped", addr, self.name),
+            ScanStatus::ReadFailed { code } => {
+                write!(f, "{} {}: read failed with {}", addr, self.name, code)
+            }
+            ScanStatus::InvalidLength { length } => {
+                write!(f, "{} {}: read {} bytes", addr, self.name, length)
+            }
+        }
+    }
+}
@@ -0,0 +1,157 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use std::time::Instant;
+
+use crate::can_bus::LoopbackBus;
+use crate::device_scan::{DeviceScanReport, ScanStatus};
+use crate::eds_file::{Address, EDSFile};
+use crate::sdo::{SdoAbortCode, SdoBusClient};
+use crate::tests::utils::*;
+use crate::virtual_slave::VirtualSlave;
+
+fn make_identity_file() -> EDSFile {
+    let lines = vec![
+        "[MandatoryObjects]",
+        "SupportedObjects=2",
+        "1=0x1000",
+        "2=0x1018",
+        "[1000]",
+        "ParameterName=Device type",
+        "ObjectType=0x7",
+        "DataType=0x0007",
+        "AccessType=ro",
+        "DefaultValue=0x00020192",
+        "[1018]",
+        "ParameterName=Identity object",
+        "ObjectType=0x9",
+        "SubNumber=5",
+        "[1018sub0]",
+        "ParameterName=Highest sub-index supported",
+        "ObjectType=0x7",
+        "DataType=0x0005",
+        "AccessType=const",
+        "DefaultValue=4",
+        "[1018sub1]",
+        "ParameterName=Vendor-ID",
+        "ObjectType=0x7",
+        "DataType=0x0007",
+        "AccessType=ro",
+        "DefaultValue=0x286",
+        "[1018sub2]",
+        "ParameterName=Product code",
+        "ObjectType=0x7",
+        "DataType=0x0007",
+        "AccessType=ro",
+        "DefaultValue=0x488",
+        "[1018sub3]",
+        "ParameterName=Revision number",
+        "ObjectType=0x7",
+        "DataType=0x0007",
+        "AccessType=ro",
+        "DefaultValue=0x00030012",
+        "[1018sub4]",
+        "ParameterName=Serial number",
+        "ObjectType=0x7",
+        "DataType=0x0007",
+        "AccessType=ro",
+        "DefaultValue=0x55",
+        "ObjFlags=0x2",
+        "[OptionalObjects]",
+        "SupportedObjects=0",
+        "[ManufacturerObjects]",
+        "SupportedObjects=0",
+    ];
+    make_eds_file(&lines).expect("Failed to parse eds file!")
+}
+
+#[test]
+fn test_scan_values() {
+    let eds = make_identity_file();
+    let mut values: HashMap<Address, Vec<u8>> = HashMap::new();
+    values.insert(
+        Address::new(0x1000, 0),
+        0x00020192u32.to_le_bytes().to_vec(),
+    );
+    values.insert(Address::new(0x1018, 1), 0x286u32.to_le_bytes().to_vec());
+    values.insert(Address::new(0x1018, 2), 0x489u32.to_le_bytes().to_vec());
+    values.insert(Address::new(0x1018, 3), vec![0x12, 0x00]);
+    values.insert(Address::new(0x1018, 4), 0x55u32.to_le_bytes().to_vec());
+
+    let report = DeviceScanReport::scan(&eds, 4, &mut values);
+    let status: Vec<ScanStatus> = report.entries.iter().map(|e| e.status.clone()).collect();
+    assert_eq!(
+        status,
+        vec![
+            ScanStatus::Match,
+            ScanStatus::Match,
+            ScanStatus::Mismatch,
+            ScanStatus::InvalidLength { length: 2 },
+            ScanStatus::Skipped,
+        ]
+    );
+    assert!(!report.is_match());
+    let mismatches = report.get_mismatches();
+    assert_eq!(mismatches.len(), 1);
+    assert_eq!(mismatches[0].name, "Product code");
+    assert_eq!(mismatches[0].expected, Some(0x488));
+    assert_eq!(mismatches[0].actual, Some(0x489));
+    assert_eq!(report.get_serial_number(), None);
+    assert!(
+        report
+            .to_string()
+            .contains("0x1018.2 Product code: expected 0x00000488 but read 0x00000489")
+    );
+
+    values.clear();
+    let report = DeviceScanReport::scan(&eds, 4, &mut values);
+    assert!(report.is_match());
+    assert_eq!(
+        report.entries[0].status,
+        ScanStatus::ReadFailed {
+            code: SdoAbortCode::NoDataAvailable
+        }
+    );
+}
+
+#[test]
+fn test_scan_over_bus() {
+    let mut eds = make_identity_file();
+    eds.device_info.revision_number = 0x00040000;
+    let bus = LoopbackBus::new();
+    let mut master = bus.connect();
+    let mut slave = VirtualSlave::from_eds(&make_identity_file(), 4, bus.connect())
+        .expect("Failed to create slave!");
+    slave.start(Instant::now()).expect("Failed to start slave!");
+
+    let running = Arc::new(AtomicBool::new(true));
+    let flag = running.clone();
+    let handle = thread::spawn(move || {
+        while flag.load(Ordering::Relaxed) {
+            slave.poll(Instant::now()).expect("Failed to poll slave!");
+            thread::yield_now();
+        }
+    });
+
+    let report = {
+        let mut client = SdoBusClient::new(&mut master, 4);
+        DeviceScanReport::scan(&eds, 4, &mut client)
+    };
+    running.store(false, Ordering::Relaxed);
+    handle.join().expect("Failed to join slave thread!");
+
+    assert_eq!(report.node_id, 4);
+    let status: Vec<ScanStatus> = report.entries.iter().map(|e| e.status.clone()).collect();
+    assert_eq!(
+        status,
+        vec![
+            ScanStatus::Match,
+            ScanStatus::Match,
+            ScanStatus::Match,
+            ScanStatus::Mismatch,
+            ScanStatus::Skipped,
+        ]
+    );
+    assert_eq!(report.entries[3].actual, Some(0x00030012));
+}
@@ -0,0 +1,246 @@
+use crate::EditError;
+use crate::communication_profile::{StandardObjectIssue, get_standard_object, validate_object};
+use crate::eds_file::{
+    AccessMode, Address, DataType, EDSFile, EDSObject, EDSValue, NodeIdOffset, ObjectList,
+    utils::eds_now,
+};
+
+fn standard_issues(obj: &EDSObject) -> Vec<StandardObjectIssue> {
+    get_standard_object(obj.get_address().index)
+        .map(|standard| validate_object(obj, standard))
+        .unwrap_or_default()
+}
+
+//An edited object has to follow the parser rules and may not add issues with
+//its CiA 301 definition, issues it already had are kept
+fn revalidate(old: Option<&EDSObject>, new: &EDSObject) -> Result<(), EditError> {
+    new.check()?;
+    let before = old.map(standard_issues).unwrap_or_default();
+    let issues: Vec<StandardObjectIssue> = standard_issues(new)
+        .into_iter()
+        .filter(|i| !before.contains(i))
+        .collect();
+    if !issues.is_empty() {
+        return Err(EditError::NonconformingObjects { issues });
+    }
+    Ok(())
+}
+
+//Sub 0 of arrays and records holds the highest subindex in use
+pub(crate) fn update_highest_subindex(obj: &mut EDSObject) {
+    let Some(entries) = obj.get_entries_mut() else {
+        return;
+    };
+    let highest = entries
+        .iter()
+        .map(|e| e.get_address().subindex)
+        .max()
+        .unwrap_or(0);
+    let Some(sub0) = entries.iter_mut().find(|e| e.get_address().subindex == 0) else {
+        return;
+    };
+    let is_node_relative = sub0
+        .get_default()
+        .is_some_and(|v| v.as_any().is::<NodeIdOffset>());
+    if sub0.get_data_type() == Some(&DataType::UInt8) && !is_node_relative {
+        sub0.set_default(Some(Box::new(highest)));
+    }
+}
+
+fn missing(addr: Address) -> EditError {
+    EditError::ObjectDoesNotExist { addr }
+}
+
+impl EDSFile {
+    //Every edit counts as a new revision of the file
+    pub(crate) fn touch(&mut self) {
+        self.file_info.file_revision = self.file_info.file_revision.saturating_add(1);
+        self.file_info.modification = eds_now();
+    }
+
+    //Changes a copy of an object and only keeps it if it is still valid
+    fn edit_object<F>(&mut self, index: u16, edit: F) -> Result<(), EditError>
+    where
+        F: FnOnce(&mut EDSObject) -> Result<(), EditError>,
+    {
+        let addr = Address::new(index, 0);
+        let list = self.get_object_list(index).ok_or(missing(addr))?;
+        let old = &self.get_list(list)[&addr];
+        let mut obj = old.clone();
+        edit(&mut obj)?;
+        obj.update_sub_number();
+        revalidate(Some(old), &obj)?;
+        self.get_list_mut(list).insert(addr, obj);
+        self.touch();
+        Ok(())
+    }
+
+    fn edit_sub_object<F>(&mut self, addr: &Address, edit: F) -> Result<(), EditError>
+    where
+        F: FnOnce(&mut EDSObject) -> Result<(), EditError>,
+    {
+        self.edit_object(addr.index, |obj| {
+            let sub = obj
+                .get_sub_object_mut(addr.subindex)
+                .ok_or(missing(*addr))?;
+            edit(sub)
+        })
+    }
+
+    pub fn add_object(&mut self, list: ObjectList, obj: EDSObject) -> Result<(), EditError> {
+        let addr = *obj.get_address();
+        if addr.subindex != 0 {
+            return Err(EditError::InvalidSubAddress { addr });
+        }
+        if self.get_index_object(addr.index).is_some() {
+            return Err(EditError::ObjectExists { index: addr.index });
+        }
+        let mut obj = obj;
+        obj.update_sub_number();
+        revalidate(None, &obj)?;
+        self.get_list_mut(list).insert(addr, obj);
+        self.touch();
+        Ok(())
+    }
+
+    pub fn remove_object(&mut self, index: u16) -> Result<EDSObject, EditError> {
+        let addr = Address::new(index, 0);
+        let list = self.get_object_list(index).ok_or(missing(addr))?;
+        let obj = self.get_list_mut(list).remove(&addr).ok_or(missing(addr))?;
+        self.touch();
+        Ok(obj)
+    }
+
+    //Adds an entry to an array or record, SubNumber and sub 0 are updated
+    pub fn add_sub_object(&mut self, sub: EDSObject) -> Result<(), EditError> {
+        let addr = *sub.get_address();
+        self.edit_object(addr.index, |obj| {
+            let entries = obj
+                .get_entries_mut()
+                .ok_or(EditError::NoSubObjects { index: addr.index })?;
+            if entries
+                .iter()
+                .any(|e| e.get_address().subindex == addr.subindex)
+            {
+                return Err(EditError::SubObjectExists { addr });
+            }
+            let position = entries
+                .iter()
+                .position(|e| e.get_address().subindex > addr.subindex)
+                .unwrap_or(entries.len());
+            entries.insert(position, sub);
+            update_highest_subindex(obj);
+            Ok(())
+        })
+    }
+
+    pub fn remove_sub_object(&mut self, addr: &Address) -> Result<EDSObject, EditError> {
+        if addr.subindex == 0 {
+            return Err(EditError::NumberOfEntries { addr: *addr });
+        }
+        let mut removed = None;
+        self.edit_object(addr.index, |obj| {
+            let entries = obj
+                .get_entries_mut()
+                .ok_or(EditError::NoSubObjects { index: addr.index })?;
+            let position = entries
+                .iter()
+                .position(|e| e.get_address() == addr)
+                .ok_or(missing(*addr))?;
+            removed = Some(entries.remove(position));
+            update_highest_subindex(obj);
+            Ok(())
+        })?;
+        removed.ok_or(missing(*addr))
+    }
+
+    pub fn set_default(
+        &mut self,
+        addr: &Address,
+        value: Option<Box<dyn EDSValue>>,
+    ) -> Result<(), EditError> {
+        self.edit_sub_object(addr, |obj| {
+            if !obj.set_default(value) {
+                return Err(EditError::NoValue { addr: *addr });
+            }
+            Ok(())
+        })
+    }
+
+    pub fn set_limits(
+        &mut self,
+        addr: &Address,
+        low: Option<Box<dyn EDSValue>>,
+        high: Option<Box<dyn EDSValue>>,
+    ) -> Result<(), EditError> {
+        self.edit_sub_object(addr, |obj| {
+            if !obj.set_limits(low, high) {
+                return Err(EditError::NoValue { addr: *addr });
+            }
+            Ok(())
+        })
+    }
+
+    pub fn set_access_mode(
+        &mut self,
+        addr: &Address,
+        access_mode: AccessMode,
+    ) -> Result<(), EditError> {
+        self.edit_sub_object(addr, |obj| {
+            if !obj.set_access_mode(access_mode) {
+                return Err(EditError::NoValue { addr: *addr });
+            }
+            Ok(())
+        })
+    }
+
+    //Renames a variable or a sub-entry, sub 0 of an array or record is its
+    //number of entries
+    pub fn set_name(&mut self, addr: &Address, name: &str) -> Result<(), EditError> {
+        self.edit_sub_object(addr, |obj| {
+            obj.set_name(name.to_string());
+            Ok(())
+        })
+    }
+
+    //Renames the object at an index itself
+    pub fn set_object_name(&mut self, index: u16, name: &str) -> Result<(), EditError> {
+        self.edit_object(index, |obj| {
+            obj.set_name(name.to_string());
+            Ok(())
+        })
+    }
+
+    pub fn move_object(&mut self, index: u16, list: ObjectList) -> Result<(), EditError> {
+        let addr = Address::new(index, 0);
+        let current = self.get_object_list(index).ok_or(missing(addr))?;
+        if current == list {
+            return Ok(());
+        }
+        let obj = self
+            .get_list_mut(current)
+            .remove(&addr)
+            .ok_or(missing(addr))?;
+        self.get_list_mut(list).insert(addr, obj);
+        self.touch();
+        Ok(())
+    }
+
+    //Moves an object and its sub-entries to a free index in the same list,
+    //it has to fit a standard object defined there
+    pub fn renumber_object(&mut self, index: u16, new_index: u16) -> Result<(), EditError> {
+        let addr = Address::new(index, 0);
+        let list = self.get_object_list(index).ok_or(missing(addr))?;
+        if self.get_index_object(new_index).is_some() {
+            return Err(EditError::ObjectExists { index: new_index });
+        }
+        let mut obj = self.get_list(list)[&addr].clone();
+        obj.set_index(new_index);
+        revalidate(None, &obj)?;
+        self.get_list_mut(list).remove(&addr);
+        self.get_list_mut(list)
+            .insert(Address::new(new_index, 0), obj);
+        self.touch();
+        Ok(())
+    }
+}
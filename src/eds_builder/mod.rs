@@ -0,0 +1,374 @@
+mod file_edit;
+mod variable_builder;
+mod version_conversion;
+
+#[cfg(test)]
+mod tests;
+
+pub(crate) use file_edit::update_highest_subindex;
+pub use variable_builder::VariableBuilder;
+pub use version_conversion::*;
+
+use std::collections::HashMap;
+
+use crate::EditError;
+use crate::communication_profile::{
+    Presence, get_standard_object, make_standard_object, validate_standard_objects,
+};
+use crate::device_scan::IDENTITY_INDEX;
+use crate::eds_file::{
+    AccessMode, Address, DataType, EDSDeviceInfo, EDSDummyUsage, EDSFile, EDSFileInfo, EDSObject,
+    EDSVersion, ObjectList, ObjectType, utils::eds_now,
+};
+use crate::pdo::{MAX_PDO_NUMBER, PdoDirection};
+
+fn highest_subindex(index: u16, count: u8) -> EDSObject {
+    VariableBuilder::new(
+        "Highest sub-index supported",
+        DataType::UInt8,
+        AccessMode::ReadOnly,
+    )
+    .default_value(Box::new(count))
+    .build(Address::new(index, 0))
+}
+
+fn make_list(
+    index: u16,
+    name: &str,
+    object_type: ObjectType,
+    entries: Vec<(u8, VariableBuilder)>,
+) -> EDSObject {
+    let highest = entries.iter().map(|(s, _)| *s).max().unwrap_or(0);
+    let mut objects = vec![highest_subindex(index, highest)];
+    objects.extend(
+        entries
+            .into_iter()
+            .map(|(subindex, e)| e.build(Address::new(index, subindex))),
+    );
+    objects.sort_by_key(|e| e.get_address().subindex);
+    EDSObject::EDSArray {
+        address: Address::new(index, 0),
+        name: name.to_string(),
+        object_type,
+        sub_number: objects.len() as u8,
+        entries: objects,
+        refuse_write_on_download: false,
+        refuse_read_on_scan: false,
+    }
+}
+
+//Constructs an EDS file from code, problems are collected and reported by
+//build so that calls can be chained
+#[derive(Debug)]
+pub struct EDSFileBuilder {
+    file_info: EDSFileInfo,
+    device_info: EDSDeviceInfo,
+    dummy_usage: EDSDummyUsage,
+    objects: Vec<(ObjectList, EDSObject)>,
+    pdo_counts: Option<(u16, u16)>,
+    identity_from_device_info: bool,
+    error: Option<EditError>,
+}
+
+impl Default for EDSFileBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EDSFileBuilder {
+    pub fn new() -> EDSFileBuilder {
+        let now = eds_now();
+        EDSFileBuilder {
+            file_info: EDSFileInfo {
+                file_name: String::new(),
+                file_version: 1,
+                file_revision: 0,
+                eds_version: EDSVersion::V4_0,
+                description: String::new(),
+                creation: now,
+                created_by: String::new(),
+                modification: now,
+                modified_by: String::new(),
+            },
+            device_info: EDSDeviceInfo {
+                vendor_name: String::new(),
+                vendor_number: 0,
+                product_name: String::new(),
+                product_number: 0,
+                revision_number: 0,
+                order_code: String::new(),
+                baudrate_10khz: false,
+                baudrate_20khz: false,
+                baudrate_50khz: false,
+                baudrate_125khz: true,
+                baudrate_250khz: true,
+                baudrate_500khz: true,
+                baudrate_800khz: false,
+                baudrate_1000khz: true,
+                simple_bootup_master: false,
+                simple_bootup_slave: true,
+                granularity: 8,
+                dynamic_channel_supported: false,
+                group_messaging: false,
+                nr_rpdo: 0,
+                nr_tpdo: 0,
+                lss_supported: false,
+            },
+            dummy_usage: EDSDummyUsage::default(),
+            objects: Vec::new(),
+            pdo_counts: None,
+            identity_from_device_info: false,
+            error: None,
+        }
+    }
+
+    pub fn file_info(mut self, file_info: EDSFileInfo) -> EDSFileBuilder {
+        self.file_info = file_info;
+        self
+    }
+
+    pub fn file_name(mut self, file_name: &str) -> EDSFileBuilder {
+        self.file_info.file_name = file_name.to_string();
+        self
+    }
+
+    pub fn file_version(mut self, version: u8, revision: u8) -> EDSFileBuilder {
+        self.file_info.file_version = version;
+        self.file_info.file_revision = revision;
+        self
+    }
+
+    pub fn eds_version(mut self, eds_version: EDSVersion) -> EDSFileBuilder {
+        self.file_info.eds_version = eds_version;
+        self
+    }
+
+    pub fn description(mut self, description: &str) -> EDSFileBuilder {
+        self.file_info.description = description.to_string();
+        self
+    }
+
+    //Sets both the creator and the last modifier
+    pub fn created_by(mut self, name: &str) -> EDSFileBuilder {
+        self.file_info.created_by = name.to_string();
+        self.file_info.modified_by = name.to_string();
+        self
+    }
+
+    pub fn device_info(mut self, device_info: EDSDeviceInfo) -> EDSFileBuilder {
+        self.device_info = device_info;
+        self
+    }
+
+    pub fn vendor(mut self, name: &str, number: u32) -> EDSFileBuilder {
+        self.device_info.vendor_name = name.to_string();
+        self.device_info.vendor_number = number;
+        self
+    }
+
+    //The order code defaults to the product name
+    pub fn product(mut self, name: &str, number: u32) -> EDSFileBuilder {
+        self.device_info.product_name = name.to_string();
+        self.device_info.product_number = number;
+        if self.device_info.order_code.is_empty() {
+            self.device_info.order_code = name.to_string();
+        }
+        self
+    }
+
+    pub fn revision_number(mut self, revision_number: u32) -> EDSFileBuilder {
+        self.device_info.revision_number = revision_number;
+        self
+    }
+
+    pub fn order_code(mut self, order_code: &str) -> EDSFileBuilder {
+        self.device_info.order_code = order_code.to_string();
+        self
+    }
+
+    //Supported bit rates in kbit/s, others are ignored
+    pub fn baudrates(mut self, rates: &[u16]) -> EDSFileBuilder {
+        let info = &mut self.device_info;
+        info.baudrate_10khz = rates.contains(&10);
+        info.baudrate_20khz = rates.contains(&20);
+        info.baudrate_50khz = rates.contains(&50);
+        info.baudrate_125khz = rates.contains(&125);
+        info.baudrate_250khz = rates.contains(&250);
+        info.baudrate_500khz = rates.contains(&500);
+        info.baudrate_800khz = rates.contains(&800);
+        info.baudrate_1000khz = rates.contains(&1000);
+        self
+    }
+
+    pub fn lss_supported(mut self, supported: bool) -> EDSFileBuilder {
+        self.device_info.lss_supported = supported;
+        self
+    }
+
+    //Overrides the PDO counts that are otherwise taken from the PDO
+    //communication parameter objects
+    pub fn pdo_counts(mut self, nr_rpdo: u16, nr_tpdo: u16) -> EDSFileBuilder {
+        self.pdo_counts = Some((nr_rpdo, nr_tpdo));
+        self
+    }
+
+    pub fn dummy(mut self, index: u16, supported: bool) -> EDSFileBuilder {
+        self.dummy_usage.dummies.insert(index, supported);
+        self
+    }
+
+    pub fn object(mut self, list: ObjectList, obj: EDSObject) -> EDSFileBuilder {
+        self.objects.push((list, obj));
+        self
+    }
+
+    pub fn variable(self, list: ObjectList, index: u16, var: VariableBuilder) -> EDSFileBuilder {
+        let obj = var.build(Address::new(index, 0));
+        self.object(list, obj)
+    }
+
+    //Entries get subindex 1 onwards, sub 0 holds their number
+    pub fn array(
+        self,
+        list: ObjectList,
+        index: u16,
+        name: &str,
+        entries: Vec<VariableBuilder>,
+    ) -> EDSFileBuilder {
+        let entries = (1..).zip(entries).collect();
+        let obj = make_list(index, name, ObjectType::Array, entries);
+        self.object(list, obj)
+    }
+
+    //Entries are given with their subindex, sub 0 holds the highest one
+    pub fn record(
+        self,
+        list: ObjectList,
+        index: u16,
+        name: &str,
+        entries: Vec<(u8, VariableBuilder)>,
+    ) -> EDSFileBuilder {
+        let obj = make_list(index, name, ObjectType::Record, entries);
+        self.object(list, obj)
+    }
+
+    pub fn domain(
+        self,
+        list: ObjectList,
+        index: u16,
+        name: &str,
+        access_mode: AccessMode,
+    ) -> EDSFileBuilder {
+        let obj = EDSObject::EDSDomain {
+            address: Address::new(index, 0),
+            name: name.to_string(),
+            object_type: ObjectType::Domain,
+            data_type: DataType::Domain,
+            access_mode,
+            default: None,
+            refuse_write_on_download: false,
+            refuse_read_on_scan: false,
+        };
+        self.object(list, obj)
+    }
+
+    //Adds a CiA 301 object with its usual layout to the mandatory or optional
+    //objects, the identity object takes its values from the device info
+    pub fn standard_object(mut self, index: u16) -> EDSFileBuilder {
+        match make_standard_object(index) {
+            Ok(obj) => {
+                let mandatory =
+                    get_standard_object(index).is_some_and(|s| s.presence == Presence::Mandatory);
+                let list = if mandatory {
+                    ObjectList::Mandatory
+                } else {
+                    ObjectList::Optional
+                };
+                self.identity_from_device_info |= index == IDENTITY_INDEX;
+                self.object(list, obj)
+            }
+            Err(e) => {
+                self.error.get_or_insert(e);
+                self
+            }
+        }
+    }
+
+    fn check_info(&self) -> Result<(), EditError> {
+        let required = [
+            ("FileName", &self.file_info.file_name),
+            ("Description", &self.file_info.description),
+            ("CreatedBy", &self.file_info.created_by),
+            ("ModifiedBy", &self.file_info.modified_by),
+            ("VendorName", &self.device_info.vendor_name),
+            ("ProductName", &self.device_info.product_name),
+            ("OrderCode", &self.device_info.order_code),
+        ];
+        for (key, value) in required {
+            if value.is_empty() {
+                return Err(EditError::MissingInfo {
+                    key: key.to_string(),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    fn count_pdos(file: &EDSFile, direction: PdoDirection) -> u16 {
+        let base = direction.communication_index();
+        (base..base + MAX_PDO_NUMBER)
+            .filter(|i| file.get_index_object(*i).is_some())
+            .count() as u16
+    }
+
+    //Checks every object, fills in the object lists and runs the CiA 301
+    //validation on the result
+    pub fn build(self) -> Result<EDSFile, EditError> {
+        if let Some(e) = self.error {
+            return Err(e);
+        }
+        self.check_info()?;
+        let mut file = EDSFile {
+            file_info: self.file_info,
+            device_info: self.device_info,
+            dummy_usage: self.dummy_usage,
+            mandatory_object: HashMap::new(),
+            optional_object: HashMap::new(),
+            manufacturer_object: HashMap::new(),
+        };
+        for (list, obj) in self.objects {
+            let index = obj.get_address().index;
+            if file.get_index_object(index).is_some() {
+                return Err(EditError::ObjectExists { index });
+            }
+            obj.check()?;
+            file.get_list_mut(list).insert(Address::new(index, 0), obj);
+        }
+        if self.identity_from_device_info {
+            let info = file.device_info.clone();
+            let values = [
+                info.vendor_number,
+                info.product_number,
+                info.revision_number,
+            ];
+            for (subindex, value) in (1..).zip(values) {
+                if let Some(obj) = file.get_object_mut(&Address::new(IDENTITY_INDEX, subindex)) {
+                    obj.set_default(Some(Box::new(value)));
+                }
+            }
+        }
+        let (nr_rpdo, nr_tpdo) = self.pdo_counts.unwrap_or((
+            Self::count_pdos(&file, PdoDirection::Receive),
+            Self::count_pdos(&file, PdoDirection::Transmit),
+        ));
+        file.device_info.nr_rpdo = nr_rpdo;
+        file.device_info.nr_tpdo = nr_tpdo;
+
+        let issues = validate_standard_objects(&file);
+        if !issues.is_empty() {
+            return Err(EditError::NonconformingObjects { issues });
+        }
+        Ok(file)
+    }
+}
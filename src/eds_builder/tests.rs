@@ -0,0 +1,419 @@
+use crate::eds_builder::{EDSFileBuilder, LossyConstruct, VariableBuilder};
+use crate::eds_file::{
+    AccessMode, Address, DataType, EDSObject, EDSVersion, NodeIdOffset, ObjectList, ObjectType,
+};
+use crate::{EditError, StandardObjectIssue, load_string};
+
+fn make_builder() -> EDSFileBuilder {
+    EDSFileBuilder::new()
+        .file_name("motor.eds")
+        .description("Generated motor")
+        .created_by("Firmware")
+        .vendor("Aperture Science", 0x286)
+        .product("ED201", 0x488)
+        .revision_number(0x00030012)
+        .standard_object(0x1000)
+        .standard_object(0x1001)
+        .standard_object(0x1018)
+}
+
+#[test]
+fn test_build_round_trip() {
+    let file = make_builder()
+        .standard_object(0x1800)
+        .standard_object(0x1A00)
+        .variable(
+            ObjectList::Manufacturer,
+            0x2000,
+            VariableBuilder::new("Speed", DataType::Int16, AccessMode::ReadWritePDOWrite)
+                .default_value(Box::new(-5i16))
+                .limits(Box::new(-100i16), Box::new(100i16))
+                .pdo_mappable(),
+        )
+        .array(
+            ObjectList::Manufacturer,
+            0x2001,
+            "Gains",
+            vec![
+                VariableBuilder::new("P", DataType::Real32, AccessMode::ReadWrite),
+                VariableBuilder::new("I", DataType::Real32, AccessMode::ReadWrite)
+                    .default_value(Box::new(0.5f32)),
+            ],
+        )
+        .record(
+            ObjectList::Manufacturer,
+            0x2002,
+            "Calibration",
+            vec![
+                (
+                    1,
+                    VariableBuilder::new("Offset", DataType::UInt32, AccessMode::ReadOnly)
+                        .default_value(Box::new(NodeIdOffset { offset: 0x100 })),
+                ),
+                (
+                    3,
+                    VariableBuilder::new("Label", DataType::VisibleString, AccessMode::Constant)
+                        .default_value(Box::new("left".to_string()))
+                        .obj_flags(true, false),
+                ),
+            ],
+        )
+        .domain(
+            ObjectList::Manufacturer,
+            0x2003,
+            "Firmware",
+            AccessMode::WriteOnly,
+        )
+        .build()
+        .expect("Failed to build eds file!");
+
+    assert_eq!(file.device_info.nr_tpdo, 1);
+    assert_eq!(file.device_info.nr_rpdo, 0);
+    assert_eq!(
+        file.get_default_u64(&Address::new(0x1018, 1), 0),
+        Some(0x286)
+    );
+    assert_eq!(
+        file.get_default_u64(&Address::new(0x1018, 3), 0),
+        Some(0x00030012)
+    );
+
+    let parsed = load_string(&file.to_eds_string()).expect("Failed to parse written file!");
+    assert_eq!(parsed.file_info.file_name, "motor.eds");
+    assert_eq!(parsed.file_info.creation, file.file_info.creation);
+    assert_eq!(parsed.device_info.product_number, 0x488);
+    assert_eq!(parsed.mandatory_object.len(), 3);
+    assert_eq!(parsed.optional_object.len(), 2);
+    assert_eq!(parsed.manufacturer_object.len(), 4);
+    assert_eq!(parsed.get_object_list(0x1800), Some(ObjectList::Optional));
+
+    let speed = parsed
+        .get_object(&Address::new(0x2000, 0))
+        .expect("Missing speed object!");
+    assert!(speed.is_pdo_mappable());
+    let (low, _) = speed.get_limits();
+    let low = low.expect("Missing low limit!");
+    assert_eq!(low.as_any().downcast_ref::<i16>(), Some(&-100));
+    assert_eq!(
+        parsed.get_default_u64(&Address::new(0x2000, 0), 0),
+        Some(0xFFFB)
+    );
+
+    let gains = parsed
+        .get_index_object(0x2001)
+        .expect("Missing gains object!");
+    assert_eq!(*gains.get_obj_type(), ObjectType::Array);
+    assert_eq!(parsed.get_default_u64(&Address::new(0x2001, 0), 0), Some(2));
+    let i = parsed
+        .get_object(&Address::new(0x2001, 2))
+        .and_then(|o| o.get_default())
+        .expect("Missing gain default!");
+    assert_eq!(i.as_any().downcast_ref::<f32>(), Some(&0.5));
+
+    assert_eq!(parsed.get_default_u64(&Address::new(0x2002, 0), 0), Some(3));
+    assert_eq!(
+        parsed.get_default_u64(&Address::new(0x2002, 1), 2),
+        Some(0x102)
+    );
+    let label = parsed
+        .get_object(&Address::new(0x2002, 3))
+        .expect("Missing label object!");
+    assert!(label.is_write_on_download_refused());
+    assert!(parsed.get_object(&Address::new(0x2002, 2)).is_none());
+    assert_eq!(
+        *parsed
+            .get_index_object(0x2003)
+            .expect("Missing domain object!")
+            .get_obj_type(),
+        ObjectType::Domain
+    );
+}
+
+#[test]
+fn test_build_errors() {
+    assert_eq!(
+        EDSFileBuilder::new().file_name("a.eds").build().err(),
+        Some(EditError::MissingInfo {
+            key: "Description".to_string()
+        })
+    );
+    assert_eq!(
+        make_builder().standard_object(0x1000).build().err(),
+        Some(EditError::ObjectExists { index: 0x1000 })
+    );
+    assert_eq!(
+        make_builder().standard_object(0x2000).build().err(),
+        Some(EditError::UnknownStandardObject { index: 0x2000 })
+    );
+
+    //Mappable objects can not use the plain rw access type
+    let speed = Address::new(0x2000, 0);
+    assert_eq!(
+        make_builder()
+            .variable(
+                ObjectList::Manufacturer,
+                0x2000,
+                VariableBuilder::new("Speed", DataType::Int16, AccessMode::ReadWrite)
+                    .pdo_mappable(),
+            )
+            .build()
+            .err(),
+        Some(EditError::PdoMappingNotAllowed {
+            addr: speed,
+            access_mode: AccessMode::ReadWrite
+        })
+    );
+    assert_eq!(
+        make_builder()
+            .variable(
+                ObjectList::Manufacturer,
+                0x2000,
+                VariableBuilder::new("Speed", DataType::Int16, AccessMode::ReadWrite)
+                    .default_value(Box::new(5u16)),
+            )
+            .build()
+            .err(),
+        Some(EditError::ValueTypeMismatch {
+            addr: speed,
+            data_type: DataType::Int16
+        })
+    );
+
+    //Standard objects are validated against CiA 301
+    let result = EDSFileBuilder::new()
+        .file_name("motor.eds")
+        .description("Generated motor")
+        .created_by("Firmware")
+        .vendor("Aperture Science", 0x286)
+        .product("ED201", 0x488)
+        .standard_object(0x1000)
+        .standard_object(0x1001)
+        .variable(
+            ObjectList::Optional,
+            0x1017,
+            VariableBuilder::new(
+                "Producer heartbeat time",
+                DataType::UInt32,
+                AccessMode::ReadWrite,
+            ),
+        )
+        .build();
+    assert_eq!(
+        result.err(),
+        Some(EditError::NonconformingObjects {
+            issues: vec![
+                StandardObjectIssue::MissingObject { index: 0x1018 },
+                StandardObjectIssue::WrongDataType {
+                    addr: Address::new(0x1017, 0),
+                    expected: DataType::UInt16,
+                    found: DataType::UInt32
+                },
+            ]
+        })
+    );
+}
+
+#[test]
+fn test_edit_objects() {
+    let mut file = make_builder()
+        .standard_object(0x1017)
+        .record(
+            ObjectList::Manufacturer,
+            0x2002,
+            "Calibration",
+            vec![(
+                1,
+                VariableBuilder::new("Offset", DataType::UInt32, AccessMode::ReadOnly),
+            )],
+        )
+        .build()
+        .expect("Failed to build eds file!");
+    let revision = file.file_info.file_revision;
+
+    let speed = Address::new(0x2000, 0);
+    file.add_object(
+        ObjectList::Manufacturer,
+        VariableBuilder::new("Speed", DataType::Int16, AccessMode::ReadWrite).build(speed),
+    )
+    .expect("Failed to add object!");
+    assert_eq!(file.file_info.file_revision, revision + 1);
+    assert_eq!(
+        file.add_object(
+            ObjectList::Optional,
+            VariableBuilder::new("Speed", DataType::Int16, AccessMode::ReadWrite).build(speed),
+        ),
+        Err(EditError::ObjectExists { index: 0x2000 })
+    );
+
+    //Sub-entries keep SubNumber and sub 0 up to date
+    let gain = Address::new(0x2002, 4);
+    file.add_sub_object(
+        VariableBuilder::new("Gain", DataType::Real32, AccessMode::ReadWrite).build(gain),
+    )
+    .expect("Failed to add sub-object!");
+    let record = file
+        .get_index_object(0x2002)
+        .expect("Missing record object!");
+    assert!(matches!(record, EDSObject::EDSArray { sub_number: 3, .. }));
+    assert_eq!(file.get_default_u64(&Address::new(0x2002, 0), 0), Some(4));
+    assert_eq!(
+        file.add_sub_object(
+            VariableBuilder::new("Gain", DataType::Real32, AccessMode::ReadWrite).build(gain)
+        ),
+        Err(EditError::SubObjectExists { addr: gain })
+    );
+    assert_eq!(
+        file.add_sub_object(
+            VariableBuilder::new("Gain", DataType::Real32, AccessMode::ReadWrite)
+                .build(Address::new(0x2000, 1))
+        ),
+        Err(EditError::NoSubObjects { index: 0x2000 })
+    );
+    assert_eq!(
+        file.remove_sub_object(&Address::new(0x2002, 0)).err(),
+        Some(EditError::NumberOfEntries {
+            addr: Address::new(0x2002, 0)
+        })
+    );
+    file.remove_sub_object(&gain)
+        .expect("Failed to remove sub-object!");
+    assert_eq!(file.get_default_u64(&Address::new(0x2002, 0), 0), Some(1));
+
+    //Failed edits leave the file untouched
+    let revision = file.file_info.file_revision;
+    assert_eq!(
+        file.set_default(&speed, Some(Box::new(5u16))),
+        Err(EditError::ValueTypeMismatch {
+            addr: speed,
+            data_type: DataType::Int16
+        })
+    );
+    let heartbeat = Address::new(0x1017, 0);
+    assert_eq!(
+        file.set_access_mode(&heartbeat, AccessMode::WriteOnly),
+        Err(EditError::NonconformingObjects {
+            issues: vec![StandardObjectIssue::WrongAccessMode {
+                addr: heartbeat,
+                allowed: vec![AccessMode::ReadWrite],
+                found: AccessMode::WriteOnly
+            }]
+        })
+    );
+    assert_eq!(file.file_info.file_revision, revision);
+    assert_eq!(file.get_default_u64(&speed, 0), None);
+
+    file.set_default(&speed, Some(Box::new(5i16)))
+        .expect("Failed to set default!");
+    file.set_limits(&speed, Some(Box::new(0i16)), Some(Box::new(10i16)))
+        .expect("Failed to set limits!");
+    file.set_access_mode(&speed, AccessMode::ReadOnly)
+        .expect("Failed to set access mode!");
+    file.set_name(&speed, "Target speed")
+        .expect("Failed to set name!");
+    file.set_object_name(0x2002, "Calibration data")
+        .expect("Failed to set name!");
+    assert_eq!(file.file_info.file_revision, revision + 5);
+
+    //The revision stops at its highest value
+    file.file_info.file_revision = u8::MAX;
+    file.set_name(&speed, "Target speed")
+        .expect("Failed to set name!");
+    assert_eq!(file.file_info.file_revision, u8::MAX);
+
+    file.move_object(0x2000, ObjectList::Optional)
+        .expect("Failed to move object!");
+    file.renumber_object(0x2002, 0x2100)
+        .expect("Failed to renumber object!");
+    assert_eq!(
+        file.renumber_object(0x2000, 0x1017),
+        Err(EditError::ObjectExists { index: 0x1017 })
+    );
+    let removed = file
+        .remove_object(0x2100)
+        .expect("Failed to remove object!");
+    assert_eq!(*removed.get_address(), Address::new(0x2100, 0));
+    file.add_object(ObjectList::Manufacturer, removed)
+        .expect("Failed to add object!");
+
+    let parsed = load_string(&file.to_eds_string()).expect("Failed to parse written file!");
+    assert_eq!(parsed.get_object_list(0x2000), Some(ObjectList::Optional));
+    assert!(parsed.get_index_object(0x2002).is_none());
+    let offset = parsed
+        .get_object(&Address::new(0x2100, 1))
+        .expect("Missing renumbered object!");
+    assert_eq!(offset.get_name(), "Offset");
+    assert_eq!(
+        parsed
+            .get_index_object(0x2100)
+            .expect("Missing renumbered object!")
+            .get_name(),
+        "Calibration data"
+    );
+    let speed = parsed.get_object(&speed).expect("Missing speed object!");
+    assert_eq!(speed.get_name(), "Target speed");
+    assert_eq!(speed.get_access_mode(), Some(&AccessMode::ReadOnly));
+    assert_eq!(parsed.get_default_u64(&Address::new(0x2000, 0), 0), Some(5));
+}
+
+#[test]
+fn test_convert_version() {
+    let mut file = make_builder().build().expect("Failed to build eds file!");
+    file.add_object(
+        ObjectList::Manufacturer,
+        EDSObject::CompactEDSArray {
+            address: Address::new(0x2000, 0),
+            name: "Gains".to_string(),
+            object_type: ObjectType::Array,
+            data_type: DataType::UInt32,
+            access_mode: AccessMode::ReadWrite,
+            default: Some(Box::new(10u32)),
+            pdo_mappable: false,
+            low_limit: None,
+            high_limit: None,
+            refuse_write_on_download: true,
+            refuse_read_on_scan: false,
+        },
+    )
+    .expect("Failed to add object!");
+    file.device_info.dynamic_channel_supported = true;
+
+    let result = file
+        .convert_version(EDSVersion::V3_0)
+        .expect("Failed to downgrade!");
+    assert_eq!(
+        result.lossy,
+        vec![
+            LossyConstruct::DynamicChannels,
+            LossyConstruct::ObjFlags {
+                addr: Address::new(0x2000, 0)
+            },
+            LossyConstruct::CompactArray { index: 0x2000 },
+        ]
+    );
+    let old = result.file;
+    assert_eq!(old.file_info.eds_version, EDSVersion::V3_0);
+    let entry = old
+        .get_object(&Address::new(0x2000, 1))
+        .expect("Missing expanded entry!");
+    assert_eq!(entry.get_name(), "Gains1");
+    assert_eq!(old.get_default_u64(&Address::new(0x2000, 0), 0), Some(1));
+    //The downgraded file is written and parsed by the rules of 3.0
+    let text = old.to_eds_string();
+    assert!(!text.contains("CompactSubObj") && !text.contains("ObjFlags"));
+    let reparsed = load_string(&text).expect("Failed to parse downgraded file!");
+    assert_eq!(reparsed.file_info.eds_version, EDSVersion::V3_0);
+
+    let result = reparsed
+        .convert_version(EDSVersion::V4_0)
+        .expect("Failed to upgrade!");
+    assert!(result.lossy.is_empty());
+    assert_eq!(result.file.file_info.eds_version, EDSVersion::V4_0);
+    assert_eq!(
+        result.file.file_info.file_revision,
+        reparsed.file_info.file_revision + 1
+    );
+    assert!(matches!(
+        file.convert_version(EDSVersion::Unsupported),
+        Err(EditError::UnsupportedVersion { .. })
+    ));
+}
@@ -0,0 +1,75 @@
+use crate::eds_file::{AccessMode, Address, DataType, EDSObject, EDSValue, ObjectType};
+
+//Describes a variable or a sub-entry of an array or record, the address is
+//given when it is added to a file
+#[derive(Debug)]
+pub struct VariableBuilder {
+    name: String,
+    data_type: DataType,
+    access_mode: AccessMode,
+    default: Option<Box<dyn EDSValue>>,
+    pdo_mappable: bool,
+    low_limit: Option<Box<dyn EDSValue>>,
+    high_limit: Option<Box<dyn EDSValue>>,
+    refuse_write_on_download: bool,
+    refuse_read_on_scan: bool,
+}
+
+impl VariableBuilder {
+    pub fn new(name: &str, data_type: DataType, access_mode: AccessMode) -> VariableBuilder {
+        VariableBuilder {
+            name: name.to_string(),
+            data_type,
+            access_mode,
+            default: None,
+            pdo_mappable: false,
+            low_limit: None,
+            high_limit: None,
+            refuse_write_on_download: false,
+            refuse_read_on_scan: false,
+        }
+    }
+
+    pub fn default_value(mut self, value: Box<dyn EDSValue>) -> VariableBuilder {
+        self.default = Some(value);
+        self
+    }
+
+    pub fn limits(mut self, low: Box<dyn EDSValue>, high: Box<dyn EDSValue>) -> VariableBuilder {
+        self.low_limit = Some(low);
+        self.high_limit = Some(high);
+        self
+    }
+
+    //Mappable objects need the rww or rwr access type
+    pub fn pdo_mappable(mut self) -> VariableBuilder {
+        self.pdo_mappable = true;
+        self
+    }
+
+    pub fn obj_flags(
+        mut self,
+        refuse_write_on_download: bool,
+        refuse_read_on_scan: bool,
+    ) -> VariableBuilder {
+        self.refuse_write_on_download = refuse_write_on_download;
+        self.refuse_read_on_scan = refuse_read_on_scan;
+        self
+    }
+
+    pub fn build(self, address: Address) -> EDSObject {
+        EDSObject::EDSVariable {
+            address,
+            name: self.name,
+            object_type: ObjectType::Variable,
+            data_type: self.data_type,
+            access_mode: self.access_mode,
+            default: self.default,
+            pdo_mappable: self.pdo_mappable,
+            low_limit: self.low_limit,
+            high_limit: self.high_limit,
+            refuse_write_on_download: self.refuse_write_on_download,
+            refuse_read_on_scan: self.refuse_read_on_scan,
+        }
+    }
+}
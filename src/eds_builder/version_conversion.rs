@@ -0,0 +1,147 @@
+use std::fmt::Display;
+
+use crate::EditError;
+use crate::eds_file::{
+    AccessMode, Address, DataType, EDSFile, EDSObject, EDSVersion, ObjectType, clone_eds_value,
+};
+
+//Constructs of EDS 4.0 that were changed or dropped to write a file as 3.0
+#[derive(Debug, PartialEq, Clone)]
+pub enum LossyConstruct {
+    //Compact arrays are written out as sub-objects with generated names
+    CompactArray { index: u16 },
+    ObjFlags { addr: Address },
+    DynamicChannels,
+}
+
+impl Display for LossyConstruct {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self {
+            LossyConstruct::CompactArray { index } => {
+                write!(f, "Compact array 0x{:04X} was expanded", index)
+            }
+            LossyConstruct::ObjFlags { addr } => write!(f, "ObjFlags of {} were dropped", addr),
+            LossyConstruct::DynamicChannels => write!(f, "Dynamic channels were dropped"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct VersionConversion {
+    pub file: EDSFile,
+    pub lossy: Vec<LossyConstruct>,
+}
+
+//Sub-objects are named the way CiA 306 names them for compact arrays
+fn expand_compact_array(obj: &EDSObject) -> Option<EDSObject> {
+    let EDSObject::CompactEDSArray {
+        address,
+        name,
+        object_type,
+        data_type,
+        access_mode,
+        default,
+        pdo_mappable,
+        low_limit,
+        high_limit,
+        ..
+    } = obj
+    else {
+        return None;
+    };
+    let count = EDSObject::EDSVariable {
+        address: Address::new(address.index, 0),
+        name: "NrOfObjects".to_string(),
+        object_type: ObjectType::Variable,
+        data_type: DataType::UInt8,
+        access_mode: AccessMode::ReadOnly,
+        default: Some(Box::new(1u8)),
+        pdo_mappable: false,
+        low_limit: None,
+        high_limit: None,
+        refuse_write_on_download: false,
+        refuse_read_on_scan: false,
+    };
+    let entry = EDSObject::EDSVariable {
+        address: Address::new(address.index, 1),
+        name: format!("{}1", name),
+        object_type: ObjectType::Variable,
+        data_type: data_type.clone(),
+        access_mode: access_mode.clone(),
+        default: clone_eds_value(default),
+        pdo_mappable: *pdo_mappable,
+        low_limit: clone_eds_value(low_limit),
+        high_limit: clone_eds_value(high_limit),
+        refuse_write_on_download: false,
+        refuse_read_on_scan: false,
+    };
+    Some(EDSObject::EDSArray {
+        address: *address,
+        name: name.clone(),
+        object_type: object_type.clone(),
+        sub_number: 2,
+        entries: vec![count, entry],
+        refuse_write_on_download: false,
+        refuse_read_on_scan: false,
+    })
+}
+
+fn drop_obj_flags(obj: &mut EDSObject, lossy: &mut Vec<LossyConstruct>) {
+    if obj.is_write_on_download_refused() || obj.is_read_on_scan_refused() {
+        obj.set_obj_flags(false, false);
+        lossy.push(LossyConstruct::ObjFlags {
+            addr: *obj.get_address(),
+        });
+    }
+    if let Some(entries) = obj.get_entries_mut() {
+        for entry in entries {
+            drop_obj_flags(entry, lossy);
+        }
+    }
+}
+
+impl EDSFile {
+    //Files of 3.0 are valid 4.0 files, going back to 3.0 rewrites or drops
+    //the constructs of 4.0 and reports them. Object links and dynamic
+    //channel sections are not kept when parsing and are not reported.
+    pub fn convert_version(&self, version: EDSVersion) -> Result<VersionConversion, EditError> {
+        for v in [&self.file_info.eds_version, &version] {
+            if *v == EDSVersion::Unsupported {
+                return Err(EditError::UnsupportedVersion { version: v.clone() });
+            }
+        }
+        let mut file = self.clone();
+        let mut lossy = Vec::new();
+        if self.file_info.eds_version == version {
+            return Ok(VersionConversion { file, lossy });
+        }
+        if version == EDSVersion::V3_0 {
+            if file.device_info.dynamic_channel_supported {
+                file.device_info.dynamic_channel_supported = false;
+                lossy.push(LossyConstruct::DynamicChannels);
+            }
+            let indices: Vec<u16> = self
+                .get_objects()
+                .iter()
+                .map(|o| o.get_address().index)
+                .collect();
+            for index in indices {
+                let addr = Address::new(index, 0);
+                let Some(list) = file.get_object_list(index) else {
+                    continue;
+                };
+                let Some(obj) = file.get_list_mut(list).get_mut(&addr) else {
+                    continue;
+                };
+                drop_obj_flags(obj, &mut lossy);
+                if let Some(expanded) = expand_compact_array(obj) {
+                    *obj = expanded;
+                    lossy.push(LossyConstruct::CompactArray { index });
+                }
+            }
+        }
+        file.file_info.eds_version = version;
+        file.touch();
+        Ok(VersionConversion { file, lossy })
+    }
+}
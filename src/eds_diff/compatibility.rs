@@ -0,0 +1,300 @@
+use std::cmp::Ordering;
+use std::fmt::Display;
+
+use super::{ChangeKind, DiffField, EDSDiff, ObjectChange, RevisionNumber, diff};
+use crate::device_scan::IDENTITY_INDEX;
+use crate::eds_file::{AccessMode, Address, EDSFile, EDSObject, EDSValue, eds_value_compare};
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+pub enum Compatibility {
+    Identical,
+    Compatible,
+    Breaking,
+}
+
+impl Display for Compatibility {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self {
+            Compatibility::Identical => write!(f, "identical"),
+            Compatibility::Compatible => write!(f, "compatible"),
+            Compatibility::Breaking => write!(f, "breaking"),
+        }
+    }
+}
+
+//Reasons a DCF or master written for the old file may fail with the new one
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum BreakingReason {
+    Removed,
+    ObjectTypeChanged,
+    DataTypeChanged,
+    NoLongerReadable,
+    NoLongerWritable,
+    LowLimitRaised,
+    HighLimitLowered,
+    PdoMappingLost,
+    //Values configured in a DCF are no longer downloaded
+    WriteOnDownloadRefused,
+}
+
+impl Display for BreakingReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self {
+            BreakingReason::Removed => write!(f, "removed"),
+            BreakingReason::ObjectTypeChanged => write!(f, "object type changed"),
+            BreakingReason::DataTypeChanged => write!(f, "data type changed"),
+            BreakingReason::NoLongerReadable => write!(f, "no longer readable"),
+            BreakingReason::NoLongerWritable => write!(f, "no longer writable"),
+            BreakingReason::LowLimitRaised => write!(f, "low limit raised"),
+            BreakingReason::HighLimitLowered => write!(f, "high limit lowered"),
+            BreakingReason::PdoMappingLost => write!(f, "no longer PDO mappable"),
+            BreakingReason::WriteOnDownloadRefused => write!(f, "refuses write on download"),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct ClassifiedChange {
+    pub change: ObjectChange,
+    pub reasons: Vec<BreakingReason>,
+}
+
+impl ClassifiedChange {
+    pub fn is_breaking(&self) -> bool {
+        !self.reasons.is_empty()
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum RevisionIssue {
+    //RevisionNumber of the DeviceInfo and the default of 0x1018 sub 3 differ
+    IdentityMismatch { device_info: u32, identity: u32 },
+    RevisionDecreased,
+    MajorRevisionRequired,
+    MinorRevisionRequired,
+}
+
+impl Display for RevisionIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self {
+            RevisionIssue::IdentityMismatch {
+                device_info,
+                identity,
+            } => write!(
+                f,
+                "RevisionNumber 0x{:08X} does not match 0x1018 sub 3 0x{:08X}",
+                device_info, identity
+            ),
+            RevisionIssue::RevisionDecreased => write!(f, "Revision number decreased"),
+            RevisionIssue::MajorRevisionRequired => {
+                write!(f, "Breaking changes require a new major revision")
+            }
+            RevisionIssue::MinorRevisionRequired => {
+                write!(f, "Changes to the object dictionary require a new revision")
+            }
+        }
+    }
+}
+
+fn find_object<'a>(file: &'a EDSFile, change: &ObjectChange) -> Option<&'a EDSObject> {
+    if change.is_sub_object {
+        file.get_object(&change.address)
+    } else {
+        file.get_index_object(change.address.index)
+    }
+}
+
+fn parse_access_mode(value: &Option<String>) -> Option<AccessMode> {
+    value.as_deref().and_then(AccessMode::parse)
+}
+
+fn pick_limit(obj: &EDSObject, low: bool) -> Option<&dyn EDSValue> {
+    let (l, h) = obj.get_limits();
+    if low { l } else { h }
+}
+
+//A new limit is narrower if there was none before or if it moved inwards
+fn is_narrower(old: &EDSObject, new: &EDSObject, low: bool) -> bool {
+    let (Some(new_limit), Some(data_type)) = (pick_limit(new, low), new.get_data_type()) else {
+        return false;
+    };
+    let Some(old_limit) = pick_limit(old, low) else {
+        return true;
+    };
+    let narrower = if low {
+        Ordering::Greater
+    } else {
+        Ordering::Less
+    };
+    eds_value_compare(new_limit, old_limit, data_type) == Some(narrower)
+}
+
+fn classify(old: &EDSFile, new: &EDSFile, change: &ObjectChange) -> Vec<BreakingReason> {
+    let mut out = Vec::new();
+    match change.kind {
+        ChangeKind::Added => return out,
+        ChangeKind::Removed => return vec![BreakingReason::Removed],
+        ChangeKind::Changed => {}
+    }
+    for field in &change.fields {
+        match field.field {
+            DiffField::ObjectType => out.push(BreakingReason::ObjectTypeChanged),
+            DiffField::DataType => out.push(BreakingReason::DataTypeChanged),
+            DiffField::AccessMode => {
+                let (Some(before), Some(after)) =
+                    (parse_access_mode(&field.old), parse_access_mode(&field.new))
+                else {
+                    continue;
+                };
+                if before.is_readable() && !after.is_readable() {
+                    out.push(BreakingReason::NoLongerReadable);
+                }
+                if before.is_writable() && !after.is_writable() {
+                    out.push(BreakingReason::NoLongerWritable);
+                }
+            }
+            DiffField::PdoMappable if field.new.as_deref() == Some("0") => {
+                out.push(BreakingReason::PdoMappingLost)
+            }
+            DiffField::RefuseWriteOnDownload if field.new.as_deref() == Some("1") => {
+                out.push(BreakingReason::WriteOnDownloadRefused)
+            }
+            DiffField::LowLimit | DiffField::HighLimit => {
+                let low = field.field == DiffField::LowLimit;
+                if let (Some(before), Some(after)) =
+                    (find_object(old, change), find_object(new, change))
+                    && is_narrower(before, after, low)
+                {
+                    out.push(if low {
+                        BreakingReason::LowLimitRaised
+                    } else {
+                        BreakingReason::HighLimitLowered
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+    out
+}
+
+//The revision number in the identity object changes with every release and
+//is checked against the changes instead
+fn is_revision_update(change: &ObjectChange) -> bool {
+    change.is_sub_object
+        && change.address == Address::new(IDENTITY_INDEX, 3)
+        && change.kind == ChangeKind::Changed
+        && change.fields.iter().all(|f| f.field == DiffField::Default)
+}
+
+//Revision number of the file, the identity object overrides the DeviceInfo
+//if it has a default
+fn get_revision(file: &EDSFile) -> RevisionNumber {
+    let identity = file.get_default_u64(&Address::new(IDENTITY_INDEX, 3), 0);
+    RevisionNumber::decode(identity.map_or(file.device_info.revision_number, |v| v as u32))
+}
+
+//Whether a DCF or master application built against the old file still works
+//with the new one, and whether the revision number reflects that
+#[derive(Debug, PartialEq, Clone)]
+pub struct CompatibilityReport {
+    pub old_revision: RevisionNumber,
+    pub new_revision: RevisionNumber,
+    pub changes: Vec<ClassifiedChange>,
+    pub revision_issues: Vec<RevisionIssue>,
+}
+
+impl CompatibilityReport {
+    pub fn new(old: &EDSFile, new: &EDSFile, diff: &EDSDiff) -> CompatibilityReport {
+        let changes: Vec<ClassifiedChange> = diff
+            .objects
+            .iter()
+            .filter(|c| !is_revision_update(c))
+            .map(|c| ClassifiedChange {
+                change: c.clone(),
+                reasons: classify(old, new, c),
+            })
+            .collect();
+        let old_revision = get_revision(old);
+        let new_revision = get_revision(new);
+
+        let mut revision_issues = Vec::new();
+        if let Some(identity) = new.get_default_u64(&Address::new(IDENTITY_INDEX, 3), 0)
+            && identity as u32 != new.device_info.revision_number
+        {
+            revision_issues.push(RevisionIssue::IdentityMismatch {
+                device_info: new.device_info.revision_number,
+                identity: identity as u32,
+            });
+        }
+        let mut report = CompatibilityReport {
+            old_revision,
+            new_revision,
+            changes,
+            revision_issues,
+        };
+        let required = match report.get_compatibility() {
+            _ if new_revision < old_revision => Some(RevisionIssue::RevisionDecreased),
+            Compatibility::Breaking if new_revision.major <= old_revision.major => {
+                Some(RevisionIssue::MajorRevisionRequired)
+            }
+            Compatibility::Compatible if new_revision <= old_revision => {
+                Some(RevisionIssue::MinorRevisionRequired)
+            }
+            _ => None,
+        };
+        report.revision_issues.extend(required);
+        report
+    }
+
+    pub fn get_compatibility(&self) -> Compatibility {
+        if self.changes.iter().any(|c| c.is_breaking()) {
+            Compatibility::Breaking
+        } else if !self.changes.is_empty() {
+            Compatibility::Compatible
+        } else {
+            Compatibility::Identical
+        }
+    }
+
+    pub fn get_breaking_changes(&self) -> Vec<&ClassifiedChange> {
+        self.changes.iter().filter(|c| c.is_breaking()).collect()
+    }
+
+    //True if the revision number fits the changes, meant as a release gate
+    pub fn is_release_allowed(&self) -> bool {
+        self.revision_issues.is_empty()
+    }
+}
+
+impl Display for CompatibilityReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "Revision {} -> {}: {}",
+            self.old_revision,
+            self.new_revision,
+            self.get_compatibility()
+        )?;
+        for c in self.get_breaking_changes() {
+            let reasons: Vec<String> = c.reasons.iter().map(|r| r.to_string()).collect();
+            let addr = if c.change.is_sub_object {
+                format!(
+                    "0x{:04X}.{}",
+                    c.change.address.index, c.change.address.subindex
+                )
+            } else {
+                format!("0x{:04X}", c.change.address.index)
+            };
+            writeln!(f, "  {} {}: {}", addr, c.change.name, reasons.join(", "))?;
+        }
+        for issue in &self.revision_issues {
+            writeln!(f, "{}", issue)?;
+        }
+        Ok(())
+    }
+}
+
+pub fn check_compatibility(old: &EDSFile, new: &EDSFile) -> CompatibilityReport {
+    CompatibilityReport::new(old, new, &diff(old, new))
+}
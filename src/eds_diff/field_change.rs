@@ -0,0 +1,83 @@
+use std::fmt::Display;
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum DiffField {
+    ObjectList,
+    Name,
+    ObjectType,
+    SubNumber,
+    DataType,
+    AccessMode,
+    Default,
+    LowLimit,
+    HighLimit,
+    PdoMappable,
+    RefuseWriteOnDownload,
+    RefuseReadOnScan,
+}
+
+impl Display for DiffField {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self {
+            DiffField::ObjectList => write!(f, "ObjectList"),
+            DiffField::Name => write!(f, "ParameterName"),
+            DiffField::ObjectType => write!(f, "ObjectType"),
+            DiffField::SubNumber => write!(f, "SubNumber"),
+            DiffField::DataType => write!(f, "DataType"),
+            DiffField::AccessMode => write!(f, "AccessType"),
+            DiffField::Default => write!(f, "DefaultValue"),
+            DiffField::LowLimit => write!(f, "LowLimit"),
+            DiffField::HighLimit => write!(f, "HighLimit"),
+            DiffField::PdoMappable => write!(f, "PDOMapping"),
+            DiffField::RefuseWriteOnDownload => write!(f, "RefuseWriteOnDownload"),
+            DiffField::RefuseReadOnScan => write!(f, "RefuseReadOnScan"),
+        }
+    }
+}
+
+fn format_value(value: &Option<String>) -> &str {
+    value.as_deref().unwrap_or("-")
+}
+
+//Values are given as written in an EDS, fields an object does not have are
+//None
+#[derive(Debug, PartialEq, Clone)]
+pub struct FieldChange {
+    pub field: DiffField,
+    pub old: Option<String>,
+    pub new: Option<String>,
+}
+
+impl Display for FieldChange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}: {} -> {}",
+            self.field,
+            format_value(&self.old),
+            format_value(&self.new)
+        )
+    }
+}
+
+//A changed key of the FileInfo or DeviceInfo section
+#[derive(Debug, PartialEq, Clone)]
+pub struct InfoChange {
+    pub section: String,
+    pub key: String,
+    pub old: Option<String>,
+    pub new: Option<String>,
+}
+
+impl Display for InfoChange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}.{}: {} -> {}",
+            self.section,
+            self.key,
+            format_value(&self.old),
+            format_value(&self.new)
+        )
+    }
+}
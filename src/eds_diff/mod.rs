@@ -0,0 +1,248 @@
+mod compatibility;
+mod field_change;
+mod object_change;
+mod revision_number;
+
+#[cfg(test)]
+mod tests;
+
+pub use compatibility::*;
+pub use field_change::*;
+pub use object_change::*;
+pub use revision_number::RevisionNumber;
+
+use std::fmt::Display;
+
+use crate::dcf::DCFFile;
+use crate::eds_file::{
+    Address, EDSFile, EDSObject, EDSValue, ObjectList, device_info_keys, eds_value_to_string,
+    file_info_keys,
+};
+
+type Fields = Vec<(DiffField, Option<String>)>;
+
+fn flag_str(value: bool) -> Option<String> {
+    Some((value as u8).to_string())
+}
+
+fn object_fields(obj: &EDSObject, list: Option<ObjectList>) -> Fields {
+    let value_str = |value: Option<&dyn EDSValue>| {
+        value.and_then(|v| eds_value_to_string(v, obj.get_data_type()?))
+    };
+    let (low, high) = obj.get_limits();
+    let sub_number = match obj {
+        EDSObject::EDSArray { sub_number, .. } => Some(sub_number.to_string()),
+        _ => None,
+    };
+    let pdo_mappable = match obj {
+        EDSObject::EDSVariable { .. } | EDSObject::CompactEDSArray { .. } => {
+            flag_str(obj.is_pdo_mappable())
+        }
+        _ => None,
+    };
+    vec![
+        (DiffField::ObjectList, list.map(|l| l.to_string())),
+        (DiffField::Name, Some(obj.get_name().clone())),
+        (DiffField::ObjectType, Some(obj.get_obj_type().to_string())),
+        (DiffField::SubNumber, sub_number),
+        (
+            DiffField::DataType,
+            obj.get_data_type().map(|t| format!("{:?}", t)),
+        ),
+        (
+            DiffField::AccessMode,
+            obj.get_access_mode().map(|m| m.to_string()),
+        ),
+        (DiffField::Default, value_str(obj.get_default())),
+        (DiffField::LowLimit, value_str(low)),
+        (DiffField::HighLimit, value_str(high)),
+        (DiffField::PdoMappable, pdo_mappable),
+        (
+            DiffField::RefuseWriteOnDownload,
+            flag_str(obj.is_write_on_download_refused()),
+        ),
+        (
+            DiffField::RefuseReadOnScan,
+            flag_str(obj.is_read_on_scan_refused()),
+        ),
+    ]
+}
+
+fn compare_fields(old: Fields, new: Fields) -> Vec<FieldChange> {
+    old.into_iter()
+        .zip(new)
+        .filter(|((_, a), (_, b))| a != b)
+        .map(|((field, old), (_, new))| FieldChange { field, old, new })
+        .collect()
+}
+
+fn compare_info(
+    section: &str,
+    old: Vec<(&'static str, String)>,
+    new: Vec<(&'static str, String)>,
+) -> Vec<InfoChange> {
+    let mut keys: Vec<&str> = old.iter().map(|(k, _)| *k).collect();
+    for (key, _) in &new {
+        if !keys.contains(key) {
+            keys.push(key);
+        }
+    }
+    let get = |values: &Vec<(&str, String)>, key: &str| {
+        values
+            .iter()
+            .find(|(k, _)| *k == key)
+            .map(|(_, v)| v.clone())
+    };
+    keys.into_iter()
+        .filter_map(|key| {
+            let (old, new) = (get(&old, key), get(&new, key));
+            (old != new).then(|| InfoChange {
+                section: section.to_string(),
+                key: key.to_string(),
+                old,
+                new,
+            })
+        })
+        .collect()
+}
+
+fn make_change(obj: &EDSObject, is_sub_object: bool, kind: ChangeKind) -> ObjectChange {
+    ObjectChange {
+        address: *obj.get_address(),
+        is_sub_object,
+        name: obj.get_name().clone(),
+        kind,
+        fields: Vec::new(),
+    }
+}
+
+fn find_entry(entries: &[EDSObject], subindex: u8) -> Option<&EDSObject> {
+    entries
+        .iter()
+        .find(|e| e.get_address().subindex == subindex)
+}
+
+fn compare_entries(old: &EDSObject, new: &EDSObject, out: &mut Vec<ObjectChange>) {
+    let empty = Vec::new();
+    let old_entries = old.get_entries().unwrap_or(&empty);
+    let new_entries = new.get_entries().unwrap_or(&empty);
+    let mut subindices: Vec<u8> = old_entries
+        .iter()
+        .chain(new_entries)
+        .map(|e| e.get_address().subindex)
+        .collect();
+    subindices.sort();
+    subindices.dedup();
+    for subindex in subindices {
+        match (
+            find_entry(old_entries, subindex),
+            find_entry(new_entries, subindex),
+        ) {
+            (Some(old), None) => out.push(make_change(old, true, ChangeKind::Removed)),
+            (None, Some(new)) => out.push(make_change(new, true, ChangeKind::Added)),
+            (Some(old), Some(new)) => {
+                let fields = compare_fields(object_fields(old, None), object_fields(new, None));
+                if !fields.is_empty() {
+                    out.push(ObjectChange {
+                        fields,
+                        ..make_change(new, true, ChangeKind::Changed)
+                    });
+                }
+            }
+            (None, None) => {}
+        }
+    }
+}
+
+//Differences between two versions of a file, objects are listed by
+//ascending address with their sub-objects after them
+#[derive(Debug, PartialEq, Clone)]
+pub struct EDSDiff {
+    pub file_info: Vec<InfoChange>,
+    pub device_info: Vec<InfoChange>,
+    pub objects: Vec<ObjectChange>,
+}
+
+impl EDSDiff {
+    pub fn is_empty(&self) -> bool {
+        self.file_info.is_empty() && self.device_info.is_empty() && self.objects.is_empty()
+    }
+
+    pub fn get_changes(&self, kind: ChangeKind) -> Vec<&ObjectChange> {
+        self.objects.iter().filter(|c| c.kind == kind).collect()
+    }
+
+    //Change of an object itself or of one of its sub-objects
+    pub fn get_change(&self, addr: &Address, is_sub_object: bool) -> Option<&ObjectChange> {
+        self.objects
+            .iter()
+            .find(|c| c.address == *addr && c.is_sub_object == is_sub_object)
+    }
+}
+
+impl Display for EDSDiff {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.is_empty() {
+            return writeln!(f, "No differences");
+        }
+        for change in self.file_info.iter().chain(&self.device_info) {
+            writeln!(f, "{}", change)?;
+        }
+        for change in &self.objects {
+            writeln!(f, "{}", change)?;
+        }
+        Ok(())
+    }
+}
+
+pub fn diff(old: &EDSFile, new: &EDSFile) -> EDSDiff {
+    let mut indices: Vec<u16> = old
+        .get_objects()
+        .into_iter()
+        .chain(new.get_objects())
+        .map(|o| o.get_address().index)
+        .collect();
+    indices.sort();
+    indices.dedup();
+
+    let mut objects = Vec::new();
+    for index in indices {
+        match (old.get_index_object(index), new.get_index_object(index)) {
+            (Some(obj), None) => objects.push(make_change(obj, false, ChangeKind::Removed)),
+            (None, Some(obj)) => objects.push(make_change(obj, false, ChangeKind::Added)),
+            (Some(old_obj), Some(new_obj)) => {
+                let fields = compare_fields(
+                    object_fields(old_obj, old.get_object_list(index)),
+                    object_fields(new_obj, new.get_object_list(index)),
+                );
+                if !fields.is_empty() {
+                    objects.push(ObjectChange {
+                        fields,
+                        ..make_change(new_obj, false, ChangeKind::Changed)
+                    });
+                }
+                compare_entries(old_obj, new_obj, &mut objects);
+            }
+            (None, None) => {}
+        }
+    }
+    EDSDiff {
+        file_info: compare_info(
+            "FileInfo",
+            file_info_keys(&old.file_info),
+            file_info_keys(&new.file_info),
+        ),
+        device_info: compare_info(
+            "DeviceInfo",
+            device_info_keys(&old.device_info, &old.file_info.eds_version),
+            device_info_keys(&new.device_info, &new.file_info.eds_version),
+        ),
+        objects,
+    }
+}
+
+//Compares the configured values of two DCFs, parameter values take the place
+//of the defaults
+pub fn diff_dcf(old: &DCFFile, new: &DCFFile) -> EDSDiff {
+    diff(&old.get_configured_eds(), &new.get_configured_eds())
+}
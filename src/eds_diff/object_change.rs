@@ -0,0 +1,48 @@
+use std::fmt::Display;
+
+use super::{DiffField, FieldChange};
+use crate::eds_file::Address;
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    Changed,
+}
+
+//Change of an object or sub-object, the name is taken from the newer file
+//unless the object was removed
+#[derive(Debug, PartialEq, Clone)]
+pub struct ObjectChange {
+    pub address: Address,
+    pub is_sub_object: bool,
+    pub name: String,
+    pub kind: ChangeKind,
+    pub fields: Vec<FieldChange>,
+}
+
+impl ObjectChange {
+    pub fn get_field(&self, field: DiffField) -> Option<&FieldChange> {
+        self.fields.iter().find(|c| c.field == field)
+    }
+}
+
+impl Display for ObjectChange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let marker = match self.kind {
+            ChangeKind::Added => '+',
+            ChangeKind::Removed => '-',
+            ChangeKind::Changed => '~',
+        };
+        let addr = if self.is_sub_object {
+            format!("0x{:04X}.{}", self.address.index, self.address.subindex)
+        } else {
+            format!("0x{:04X}", self.address.index)
+        };
+        write!(f, "{} {} {}", marker, addr, self.name)?;
+        for change in &self.fields {
+            write!(f, "\n    {}", change)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,29 @@
+use std::fmt::Display;
+
+//CiA 301 splits the revision number of the identity object into a major
+//revision for changed behavior in the upper and a minor revision in the lower
+//16 bits
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+pub struct RevisionNumber {
+    pub major: u16,
+    pub minor: u16,
+}
+
+impl RevisionNumber {
+    pub fn decode(value: u32) -> RevisionNumber {
+        RevisionNumber {
+            major: (value >> 16) as u16,
+            minor: value as u16,
+        }
+    }
+
+    pub fn encode(&self) -> u32 {
+        (self.major as u32) << 16 | self.minor as u32
+    }
+}
+
+impl Display for RevisionNumber {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}", self.major, self.minor)
+    }
+}
@@ -0,0 +1,238 @@
+use crate::eds_builder::VariableBuilder;
+use crate::eds_diff::{
+    BreakingReason, ChangeKind, Compatibility, DiffField, FieldChange, RevisionIssue,
+    RevisionNumber, check_compatibility, diff, diff_dcf,
+};
+use crate::eds_file::{AccessMode, Address, DataType, EDSFile, ObjectList};
+use crate::load_file;
+use crate::tests::utils::*;
+
+fn make_changed_file(file: &EDSFile) -> EDSFile {
+    let mut file = file.clone();
+    file.set_default(&Address::new(0x1017, 0), Some(Box::new(1000u16)))
+        .expect("Failed to set default!");
+    file.set_limits(
+        &Address::new(0x2002, 0),
+        Some(Box::new(-100i16)),
+        Some(Box::new(100i16)),
+    )
+    .expect("Failed to set limits!");
+    file.remove_object(0x2003)
+        .expect("Failed to remove object!");
+    file.add_object(
+        ObjectList::Manufacturer,
+        VariableBuilder::new("Temperature", DataType::Int16, AccessMode::ReadOnly)
+            .build(Address::new(0x2100, 0)),
+    )
+    .expect("Failed to add object!");
+    file.add_sub_object(
+        VariableBuilder::new("Position Error", DataType::Int32, AccessMode::ReadOnly)
+            .pdo_mappable()
+            .build(Address::new(0x2004, 2)),
+    )
+    .expect("Failed to add sub-object!");
+    file.move_object(0x2001, ObjectList::Optional)
+        .expect("Failed to move object!");
+    file.device_info.revision_number = 0x00010000;
+    file
+}
+
+#[test]
+fn test_diff() {
+    let old = load_file("res/micro-motor.eds").expect("Failed to parse eds file!");
+    assert!(diff(&old, &old).is_empty());
+    assert_eq!(diff(&old, &old).to_string(), "No differences\n");
+
+    let new = make_changed_file(&old);
+    let result = diff(&old, &new);
+    let keys: Vec<&str> = result.file_info.iter().map(|c| c.key.as_str()).collect();
+    assert!(keys.contains(&"FileRevision"));
+    assert!(keys.contains(&"ModificationDate"));
+    assert_eq!(result.device_info.len(), 1);
+    assert_eq!(result.device_info[0].key, "RevisionNumber");
+
+    let changed: Vec<(u16, u8, bool, ChangeKind)> = result
+        .objects
+        .iter()
+        .map(|c| (c.address.index, c.address.subindex, c.is_sub_object, c.kind))
+        .collect();
+    assert_eq!(
+        changed,
+        vec![
+            (0x1017, 0, false, ChangeKind::Changed),
+            (0x2001, 0, false, ChangeKind::Changed),
+            (0x2002, 0, false, ChangeKind::Changed),
+            (0x2003, 0, false, ChangeKind::Removed),
+            (0x2004, 0, false, ChangeKind::Changed),
+            (0x2004, 2, true, ChangeKind::Added),
+            (0x2100, 0, false, ChangeKind::Added),
+        ]
+    );
+
+    let heartbeat = result
+        .get_change(&Address::new(0x1017, 0), false)
+        .expect("Missing heartbeat change!");
+    assert_eq!(
+        heartbeat.fields,
+        vec![FieldChange {
+            field: DiffField::Default,
+            old: Some("0x0000".to_string()),
+            new: Some("0x03E8".to_string())
+        }]
+    );
+    let pwm = result
+        .get_change(&Address::new(0x2002, 0), false)
+        .expect("Missing pwm change!");
+    let high = pwm
+        .get_field(DiffField::HighLimit)
+        .expect("Missing limit change!");
+    assert_eq!(
+        (high.old.as_deref(), high.new.as_deref()),
+        (None, Some("100"))
+    );
+    let moved = result
+        .get_change(&Address::new(0x2001, 0), false)
+        .expect("Missing moved object!");
+    assert_eq!(
+        moved.get_field(DiffField::ObjectList),
+        Some(&FieldChange {
+            field: DiffField::ObjectList,
+            old: Some("Manufacturer".to_string()),
+            new: Some("Optional".to_string())
+        })
+    );
+    assert_eq!(result.get_changes(ChangeKind::Added).len(), 2);
+
+    let text = result.to_string();
+    assert!(text.contains("DeviceInfo.RevisionNumber: 0x00000001 -> 0x00010000\n"));
+    assert!(
+        text.contains("~ 0x1017 Producer heartbeat time\n    DefaultValue: 0x0000 -> 0x03E8\n")
+    );
+    assert!(text.contains("~ 0x2004 Pid Error Values\n    SubNumber: 1 -> 2\n"));
+    assert!(text.contains("+ 0x2004.2 Position Error\n"));
+    assert!(text.contains("- 0x2003 Output PWM\n"));
+
+    //The reverse diff swaps additions and removals
+    let reverse = diff(&new, &old);
+    assert_eq!(reverse.get_changes(ChangeKind::Removed).len(), 2);
+    assert_eq!(reverse.get_changes(ChangeKind::Added).len(), 1);
+}
+
+#[test]
+fn test_diff_dcf() {
+    let old = make_dcf_file(&PDO_DCF.to_vec()).expect("Failed to parse dcf file!");
+    let mut lines = PDO_DCF.to_vec();
+    let value = lines
+        .iter()
+        .position(|l| *l == "ParameterValue=10000")
+        .expect("Missing cycle period!");
+    lines[value] = "ParameterValue=20000";
+    let new = make_dcf_file(&lines).expect("Failed to parse dcf file!");
+
+    let result = diff_dcf(&old, &new);
+    assert_eq!(result.objects.len(), 1);
+    assert_eq!(result.objects[0].address, Address::new(0x1006, 0));
+    assert_eq!(
+        result.objects[0].fields,
+        vec![FieldChange {
+            field: DiffField::Default,
+            old: Some("0x00002710".to_string()),
+            new: Some("0x00004E20".to_string())
+        }]
+    );
+}
+
+#[test]
+fn test_compatibility() {
+    let old = load_file("res/micro-motor.eds").expect("Failed to parse eds file!");
+    let report = check_compatibility(&old, &old);
+    assert_eq!(report.get_compatibility(), Compatibility::Identical);
+    assert!(report.is_release_allowed());
+    assert_eq!(report.old_revision, RevisionNumber { major: 0, minor: 1 });
+
+    //Updating the revision in the identity object is no change
+    let identity = Address::new(0x1018, 3);
+    let mut released = old.clone();
+    released
+        .set_default(&identity, Some(Box::new(0x00000001u32)))
+        .expect("Failed to set default!");
+    let mut bumped = released.clone();
+    bumped
+        .set_default(&identity, Some(Box::new(0x00000002u32)))
+        .expect("Failed to set default!");
+    bumped.device_info.revision_number = 0x00000002;
+    let report = check_compatibility(&released, &bumped);
+    assert_eq!(report.get_compatibility(), Compatibility::Identical);
+    assert!(report.changes.is_empty());
+    assert_eq!(report.new_revision, RevisionNumber { major: 0, minor: 2 });
+    assert!(report.is_release_allowed());
+
+    //Removing an object and narrowing limits needs a major revision
+    let new = make_changed_file(&old);
+    let report = check_compatibility(&old, &new);
+    assert_eq!(report.get_compatibility(), Compatibility::Breaking);
+    let breaking: Vec<(u16, Vec<BreakingReason>)> = report
+        .get_breaking_changes()
+        .iter()
+        .map(|c| (c.change.address.index, c.reasons.clone()))
+        .collect();
+    assert_eq!(
+        breaking,
+        vec![
+            (
+                0x2002,
+                vec![
+                    BreakingReason::LowLimitRaised,
+                    BreakingReason::HighLimitLowered
+                ]
+            ),
+            (0x2003, vec![BreakingReason::Removed]),
+        ]
+    );
+    assert_eq!(report.new_revision, RevisionNumber::decode(0x00010000));
+    assert!(report.is_release_allowed());
+
+    let mut minor = new.clone();
+    minor.device_info.revision_number = 0x00000002;
+    let report = check_compatibility(&old, &minor);
+    assert_eq!(
+        report.revision_issues,
+        vec![RevisionIssue::MajorRevisionRequired]
+    );
+    assert!(
+        report
+            .to_string()
+            .contains("  0x2003 Output PWM: removed\n")
+    );
+
+    //Additions only need a minor revision
+    let mut added = old.clone();
+    added
+        .add_object(
+            ObjectList::Manufacturer,
+            VariableBuilder::new("Temperature", DataType::Int16, AccessMode::ReadOnly)
+                .build(Address::new(0x2100, 0)),
+        )
+        .expect("Failed to add object!");
+    let report = check_compatibility(&old, &added);
+    assert_eq!(report.get_compatibility(), Compatibility::Compatible);
+    assert_eq!(
+        report.revision_issues,
+        vec![RevisionIssue::MinorRevisionRequired]
+    );
+    added.device_info.revision_number = 0x00000002;
+    assert!(check_compatibility(&old, &added).is_release_allowed());
+
+    //Access and mapping restrictions break existing configurations
+    let mut restricted = old.clone();
+    restricted
+        .set_access_mode(&Address::new(0x2002, 0), AccessMode::ReadOnly)
+        .expect("Failed to set access mode!");
+    restricted.device_info.revision_number = 0x00010000;
+    let report = check_compatibility(&old, &restricted);
+    assert_eq!(
+        report.changes[0].reasons,
+        vec![BreakingReason::NoLongerWritable]
+    );
+    assert!(report.is_release_allowed());
+}
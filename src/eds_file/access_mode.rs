@@ -1,11 +1,18 @@
 use std::fmt::Display;
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum AccessMode {
+    #[cfg_attr(feature = "serde", serde(rename = "ro"))]
     ReadOnly,
+    #[cfg_attr(feature = "serde", serde(rename = "wo"))]
     WriteOnly,
+    #[cfg_attr(feature = "serde", serde(rename = "rw"))]
     ReadWrite,
+    #[cfg_attr(feature = "serde", serde(rename = "rww"))]
     ReadWritePDOWrite,
+    #[cfg_attr(feature = "serde", serde(rename = "rwr"))]
     ReadWritePDORead,
+    #[cfg_attr(feature = "serde", serde(rename = "const"))]
     Constant,
 }
 
@@ -49,4 +56,29 @@ impl AccessMode {
             }
         }
     }
+
+    pub fn is_rpdo_mappable(&self) -> bool {
+        matches!(
+            self,
+            AccessMode::WriteOnly | AccessMode::ReadWrite | AccessMode::ReadWritePDOWrite
+        )
+    }
+
+    pub fn is_tpdo_mappable(&self) -> bool {
+        matches!(
+            self,
+            AccessMode::ReadOnly
+                | AccessMode::Constant
+                | AccessMode::ReadWrite
+                | AccessMode::ReadWritePDORead
+        )
+    }
+
+    pub fn is_readable(&self) -> bool {
+        !matches!(self, AccessMode::WriteOnly)
+    }
+
+    pub fn is_writable(&self) -> bool {
+        !matches!(self, AccessMode::ReadOnly | AccessMode::Constant)
+    }
 }
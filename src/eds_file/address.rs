@@ -48,3 +48,28 @@ impl PartialOrd for Address {
         cmp1.partial_cmp(&cmp2)
     }
 }
+
+//Addresses are written as "0x1018sub2", sub-index 0 included, so they can
+//be used as keys of JSON objects
+#[cfg(feature = "serde")]
+impl serde::Serialize for Address {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&format!("0x{:04X}sub{:X}", self.index, self.subindex))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Address {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Address, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        let lower = value.to_lowercase();
+        lower
+            .strip_prefix("0x")
+            .and_then(|v| v.split_once("sub"))
+            .and_then(|(index, subindex)| {
+                let index = u16::from_str_radix(index, 16).ok()?;
+                Some(Address::new(index, u8::from_str_radix(subindex, 16).ok()?))
+            })
+            .ok_or_else(|| serde::de::Error::custom(format!("Invalid address {}", value)))
+    }
+}
@@ -1,74 +1,142 @@
+use std::any::Any;
+use std::cmp::Ordering;
 use std::fmt::Debug;
 use std::marker::Send;
 
 pub trait EDSValue: Debug + Send {
     fn clone_box(&self) -> Box<dyn EDSValue>;
+    fn as_any(&self) -> &dyn Any;
+}
+
+//Value given relative to the node id, e.g. "$NODEID+0x180"
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct NodeIdOffset {
+    pub offset: u64,
+}
+
+impl NodeIdOffset {
+    pub fn resolve(&self, node_id: u8) -> u64 {
+        self.offset.wrapping_add(node_id as u64)
+    }
+
+    pub fn resolve_value(&self, node_id: u8, data_type: &DataType) -> Option<Box<dyn EDSValue>> {
+        let size = data_type.bit_size()?.div_ceil(8) as usize;
+        eds_value_from_bytes(&self.resolve(node_id).to_le_bytes()[..size], data_type)
+    }
 }
 
 impl EDSValue for bool {
     fn clone_box(&self) -> Box<dyn EDSValue> {
         Box::new(self.clone())
     }
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
 }
 impl EDSValue for u8 {
     fn clone_box(&self) -> Box<dyn EDSValue> {
         Box::new(self.clone())
     }
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
 }
 impl EDSValue for u16 {
     fn clone_box(&self) -> Box<dyn EDSValue> {
         Box::new(self.clone())
     }
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
 }
 impl EDSValue for u32 {
     fn clone_box(&self) -> Box<dyn EDSValue> {
         Box::new(self.clone())
     }
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
 }
 impl EDSValue for u64 {
     fn clone_box(&self) -> Box<dyn EDSValue> {
         Box::new(self.clone())
     }
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
 }
 impl EDSValue for i8 {
     fn clone_box(&self) -> Box<dyn EDSValue> {
         Box::new(self.clone())
     }
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
 }
 impl EDSValue for i16 {
     fn clone_box(&self) -> Box<dyn EDSValue> {
         Box::new(self.clone())
     }
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
 }
 impl EDSValue for i32 {
     fn clone_box(&self) -> Box<dyn EDSValue> {
         Box::new(self.clone())
     }
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
 }
 impl EDSValue for i64 {
     fn clone_box(&self) -> Box<dyn EDSValue> {
         Box::new(self.clone())
     }
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
 }
 impl EDSValue for f32 {
     fn clone_box(&self) -> Box<dyn EDSValue> {
         Box::new(self.clone())
     }
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
 }
 impl EDSValue for f64 {
     fn clone_box(&self) -> Box<dyn EDSValue> {
         Box::new(self.clone())
     }
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
 }
 impl EDSValue for String {
     fn clone_box(&self) -> Box<dyn EDSValue> {
         Box::new(self.clone())
     }
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
 }
 impl EDSValue for Vec<u8> {
     fn clone_box(&self) -> Box<dyn EDSValue> {
         Box::new(self.clone())
     }
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+impl EDSValue for NodeIdOffset {
+    fn clone_box(&self) -> Box<dyn EDSValue> {
+        Box::new(*self)
+    }
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
 }
 
 pub fn clone_eds_value(x: &Option<Box<dyn EDSValue>>) -> Option<Box<dyn EDSValue>> {
@@ -78,7 +146,183 @@ pub fn clone_eds_value(x: &Option<Box<dyn EDSValue>>) -> Option<Box<dyn EDSValue
     }
 }
 
+//Returns the raw bits of an integer value, node id relative values are only
+//resolved if a node id is given
+pub fn eds_value_to_u64(value: &dyn EDSValue, node_id: Option<u8>) -> Option<u64> {
+    let any = value.as_any();
+    if let Some(v) = any.downcast_ref::<bool>() {
+        Some(*v as u64)
+    } else if let Some(v) = any.downcast_ref::<u8>() {
+        Some(*v as u64)
+    } else if let Some(v) = any.downcast_ref::<u16>() {
+        Some(*v as u64)
+    } else if let Some(v) = any.downcast_ref::<u32>() {
+        Some(*v as u64)
+    } else if let Some(v) = any.downcast_ref::<u64>() {
+        Some(*v)
+    } else if let Some(v) = any.downcast_ref::<i8>() {
+        Some(*v as u8 as u64)
+    } else if let Some(v) = any.downcast_ref::<i16>() {
+        Some(*v as u16 as u64)
+    } else if let Some(v) = any.downcast_ref::<i32>() {
+        Some(*v as u32 as u64)
+    } else if let Some(v) = any.downcast_ref::<i64>() {
+        Some(*v as u64)
+    } else if let Some(v) = any.downcast_ref::<NodeIdOffset>() {
+        node_id.map(|id| v.resolve(id))
+    } else {
+        None
+    }
+}
+
+//Checks that a value is stored with the Rust type used for the data type
+pub fn eds_value_matches_type(value: &dyn EDSValue, data_type: &DataType) -> bool {
+    let any = value.as_any();
+    match data_type {
+        DataType::Boolean => any.is::<bool>(),
+        DataType::UInt8 => any.is::<u8>(),
+        DataType::UInt16 => any.is::<u16>(),
+        DataType::UInt32 => any.is::<u32>(),
+        DataType::UInt64 => any.is::<u64>(),
+        DataType::Int8 => any.is::<i8>(),
+        DataType::Int16 => any.is::<i16>(),
+        DataType::Int32 => any.is::<i32>(),
+        DataType::Int64 => any.is::<i64>(),
+        DataType::Real32 => any.is::<f32>(),
+        DataType::Real64 => any.is::<f64>(),
+        DataType::VisibleString | DataType::UnicodeString => any.is::<String>(),
+        DataType::OctettString | DataType::Domain => any.is::<Vec<u8>>(),
+    }
+}
+
+//Compares two numeric values of the same data type, signed values are
+//compared after sign extension
+pub fn eds_value_compare(
+    a: &dyn EDSValue,
+    b: &dyn EDSValue,
+    data_type: &DataType,
+) -> Option<Ordering> {
+    match data_type {
+        DataType::Real32 => {
+            let a = a.as_any().downcast_ref::<f32>()?;
+            let b = b.as_any().downcast_ref::<f32>()?;
+            a.partial_cmp(b)
+        }
+        DataType::Real64 => {
+            let a = a.as_any().downcast_ref::<f64>()?;
+            let b = b.as_any().downcast_ref::<f64>()?;
+            a.partial_cmp(b)
+        }
+        DataType::Int8 | DataType::Int16 | DataType::Int32 | DataType::Int64 => {
+            let shift = 64 - data_type.bit_size()?;
+            let a = ((eds_value_to_u64(a, None)? << shift) as i64) >> shift;
+            let b = ((eds_value_to_u64(b, None)? << shift) as i64) >> shift;
+            Some(a.cmp(&b))
+        }
+        DataType::Boolean
+        | DataType::UInt8
+        | DataType::UInt16
+        | DataType::UInt32
+        | DataType::UInt64 => Some(eds_value_to_u64(a, None)?.cmp(&eds_value_to_u64(b, None)?)),
+        _ => None,
+    }
+}
+
+//Little endian encoding as used in SDO and PDO transfers
+pub fn eds_value_to_bytes(value: &dyn EDSValue, data_type: &DataType) -> Option<Vec<u8>> {
+    let any = value.as_any();
+    match data_type {
+        DataType::Real32 => any.downcast_ref::<f32>().map(|v| v.to_le_bytes().to_vec()),
+        DataType::Real64 => any.downcast_ref::<f64>().map(|v| v.to_le_bytes().to_vec()),
+        DataType::VisibleString => any.downcast_ref::<String>().map(|v| v.as_bytes().to_vec()),
+        DataType::UnicodeString => any
+            .downcast_ref::<String>()
+            .map(|v| v.encode_utf16().flat_map(|c| c.to_le_bytes()).collect()),
+        DataType::OctettString | DataType::Domain => any.downcast_ref::<Vec<u8>>().cloned(),
+        _ => {
+            let size = data_type.bit_size()?.div_ceil(8) as usize;
+            let raw = eds_value_to_u64(value, None)?;
+            Some(raw.to_le_bytes()[..size].to_vec())
+        }
+    }
+}
+
+pub fn eds_value_from_bytes(data: &[u8], data_type: &DataType) -> Option<Box<dyn EDSValue>> {
+    if let Some(size) = data_type.bit_size()
+        && data.len() != size.div_ceil(8) as usize
+    {
+        return None;
+    }
+    let mut raw = [0u8; 8];
+    raw[..data.len().min(8)].copy_from_slice(&data[..data.len().min(8)]);
+    let raw = u64::from_le_bytes(raw);
+    match data_type {
+        DataType::Boolean => match raw {
+            0 => Some(Box::new(false)),
+            1 => Some(Box::new(true)),
+            _ => None,
+        },
+        DataType::UInt8 => Some(Box::new(raw as u8)),
+        DataType::UInt16 => Some(Box::new(raw as u16)),
+        DataType::UInt32 => Some(Box::new(raw as u32)),
+        DataType::UInt64 => Some(Box::new(raw)),
+        DataType::Int8 => Some(Box::new(raw as i8)),
+        DataType::Int16 => Some(Box::new(raw as i16)),
+        DataType::Int32 => Some(Box::new(raw as i32)),
+        DataType::Int64 => Some(Box::new(raw as i64)),
+        DataType::Real32 => Some(Box::new(f32::from_bits(raw as u32))),
+        DataType::Real64 => Some(Box::new(f64::from_bits(raw))),
+        DataType::VisibleString => {
+            let val = String::from_utf8(data.to_vec()).ok()?;
+            if val.is_ascii() {
+                Some(Box::new(val))
+            } else {
+                None
+            }
+        }
+        DataType::UnicodeString => {
+            if data.len() % 2 != 0 {
+                return None;
+            }
+            let chars: Vec<u16> = data
+                .chunks(2)
+                .map(|c| u16::from_le_bytes([c[0], c[1]]))
+                .collect();
+            String::from_utf16(&chars)
+                .ok()
+                .map(|v| Box::new(v) as Box<dyn EDSValue>)
+        }
+        DataType::OctettString | DataType::Domain => Some(Box::new(data.to_vec())),
+    }
+}
+
+//Formats a value the way it is written in an EDS or DCF file
+pub fn eds_value_to_string(value: &dyn EDSValue, data_type: &DataType) -> Option<String> {
+    let any = value.as_any();
+    if let Some(v) = any.downcast_ref::<NodeIdOffset>() {
+        return Some(format!("$NODEID+0x{:X}", v.offset));
+    }
+    match data_type {
+        DataType::Boolean => any.downcast_ref::<bool>().map(|v| (*v as u8).to_string()),
+        DataType::UInt8 | DataType::UInt16 | DataType::UInt32 | DataType::UInt64 => {
+            let width = data_type.bit_size()? as usize / 4;
+            eds_value_to_u64(value, None).map(|v| format!("0x{:0width$X}", v, width = width))
+        }
+        DataType::Int8 => any.downcast_ref::<i8>().map(|v| v.to_string()),
+        DataType::Int16 => any.downcast_ref::<i16>().map(|v| v.to_string()),
+        DataType::Int32 => any.downcast_ref::<i32>().map(|v| v.to_string()),
+        DataType::Int64 => any.downcast_ref::<i64>().map(|v| v.to_string()),
+        DataType::Real32 => any.downcast_ref::<f32>().map(|v| v.to_string()),
+        DataType::Real64 => any.downcast_ref::<f64>().map(|v| v.to_string()),
+        DataType::VisibleString | DataType::UnicodeString => any.downcast_ref::<String>().cloned(),
+        DataType::OctettString | DataType::Domain => any
+            .downcast_ref::<Vec<u8>>()
+            .map(|v| v.iter().map(|b| format!("{:02X}", b)).collect()),
+    }
+}
+
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum DataType {
     Boolean,
     UInt8,
@@ -118,4 +362,52 @@ impl DataType {
             _ => None,
         }
     }
+
+    pub fn encode(&self) -> u16 {
+        match self {
+            DataType::Boolean => 0x1,
+            DataType::Int8 => 0x2,
+            DataType::Int16 => 0x3,
+            DataType::Int32 => 0x4,
+            DataType::UInt8 => 0x5,
+            DataType::UInt16 => 0x6,
+            DataType::UInt32 => 0x7,
+            DataType::Real32 => 0x8,
+            DataType::VisibleString => 0x9,
+            DataType::OctettString => 0xA,
+            DataType::UnicodeString => 0xB,
+            DataType::Domain => 0xF,
+            DataType::Real64 => 0x11,
+            DataType::Int64 => 0x15,
+            DataType::UInt64 => 0x1B,
+        }
+    }
+
+    pub fn bit_size(&self) -> Option<u32> {
+        match self {
+            DataType::Boolean => Some(1),
+            DataType::Int8 | DataType::UInt8 => Some(8),
+            DataType::Int16 | DataType::UInt16 => Some(16),
+            DataType::Int32 | DataType::UInt32 | DataType::Real32 => Some(32),
+            DataType::Int64 | DataType::UInt64 | DataType::Real64 => Some(64),
+            DataType::UnicodeString
+            | DataType::VisibleString
+            | DataType::OctettString
+            | DataType::Domain => None,
+        }
+    }
+
+    pub fn is_integer(&self) -> bool {
+        matches!(
+            self,
+            DataType::UInt8
+                | DataType::UInt16
+                | DataType::UInt32
+                | DataType::UInt64
+                | DataType::Int8
+                | DataType::Int16
+                | DataType::Int32
+                | DataType::Int64
+        )
+    }
 }
@@ -1,8 +1,10 @@
 use crate::{ParseError, structured_file::StructuredFileObject};
 
+use super::EDSVersion;
 use super::utils::{parse_required_bool, parse_required_str, parse_required_uint};
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct EDSDeviceInfo {
     pub vendor_name: String,
     pub vendor_number: u32, //0x1000.1
@@ -29,7 +31,10 @@ pub struct EDSDeviceInfo {
 }
 
 impl EDSDeviceInfo {
-    pub fn parse(obj: &StructuredFileObject) -> Result<EDSDeviceInfo, ParseError> {
+    pub fn parse(
+        obj: &StructuredFileObject,
+        version: &EDSVersion,
+    ) -> Result<EDSDeviceInfo, ParseError> {
         let vendor_name = parse_required_str(obj, "VendorName")?;
         let vendor_number = parse_required_uint(obj, "VendorNumber")?;
         let product_name = parse_required_str(obj, "ProductName")?;
@@ -53,7 +58,11 @@ impl EDSDeviceInfo {
                 section: obj.get_name().to_string(),
             });
         }
-        let dynamic_channels = parse_required_bool(obj, "DynamicChannelsSupported")?;
+        //Dynamic channels were added with EDS 4.0
+        let dynamic_channels = match version {
+            EDSVersion::V3_0 => false,
+            _ => parse_required_bool(obj, "DynamicChannelsSupported")?,
+        };
         let group_messaging = parse_required_bool(obj, "GroupMessaging")?;
         let nr_rpdo = parse_required_uint(obj, "NrOfRXPDO")?;
         let nr_tpdo = parse_required_uint(obj, "NrOfTXPDO")?;
@@ -83,4 +92,23 @@ impl EDSDeviceInfo {
             lss_supported: lss_supported,
         })
     }
+
+    //Supported bit rates in kbit/s
+    pub fn get_baudrates(&self) -> Vec<u16> {
+        let rates = [
+            (self.baudrate_10khz, 10),
+            (self.baudrate_20khz, 20),
+            (self.baudrate_50khz, 50),
+            (self.baudrate_125khz, 125),
+            (self.baudrate_250khz, 250),
+            (self.baudrate_500khz, 500),
+            (self.baudrate_800khz, 800),
+            (self.baudrate_1000khz, 1000),
+        ];
+        rates
+            .iter()
+            .filter(|(supported, _)| *supported)
+            .map(|(_, rate)| *rate)
+            .collect()
+    }
 }
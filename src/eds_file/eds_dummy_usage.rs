@@ -0,0 +1,33 @@
+use std::collections::HashMap;
+
+use crate::{ParseError, structured_file::StructuredFileObject};
+
+use super::utils::{parse_hex_str, parse_required_bool};
+
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EDSDummyUsage {
+    pub dummies: HashMap<u16, bool>,
+}
+
+impl EDSDummyUsage {
+    pub fn parse(obj: &StructuredFileObject) -> Result<EDSDummyUsage, ParseError> {
+        let mut dummies = HashMap::new();
+        for key in obj.get_values().keys() {
+            let lower = key.to_lowercase();
+            let index = lower
+                .strip_prefix("dummy")
+                .and_then(parse_hex_str::<u16, 4>)
+                .ok_or(ParseError::InvalidValueFormat {
+                    object: key.clone(),
+                    section: obj.get_name().clone(),
+                })?;
+            dummies.insert(index, parse_required_bool(obj, key)?);
+        }
+        Ok(EDSDummyUsage { dummies })
+    }
+
+    pub fn is_supported(&self, index: u16) -> bool {
+        self.dummies.get(&index).copied().unwrap_or(false)
+    }
+}
@@ -6,6 +6,7 @@ use crate::eds_file::utils::*;
 use crate::structured_file::StructuredFileObject;
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct EDSFileInfo {
     pub file_name: String,
     pub file_version: u8,
@@ -1,7 +1,7 @@
 use std::fmt::Debug;
 
 use crate::{
-    ParseError,
+    EditError, ParseError,
     structured_file::{StructuredFile, StructuredFileObject},
 };
 
@@ -9,15 +9,17 @@ use super::{
     Address,
     access_mode::AccessMode,
     clone_eds_value,
-    data_type::{DataType, EDSValue},
+    data_type::{DataType, EDSValue, NodeIdOffset, eds_value_matches_type},
     object_type::ObjectType,
     utils::{
-        parse_required_bool, parse_required_double, parse_required_float, parse_required_hex_data,
-        parse_required_str, parse_required_uint,
+        parse_node_id_offset, parse_required_bool, parse_required_double, parse_required_float,
+        parse_required_hex_data, parse_required_int, parse_required_str, parse_required_uint,
     },
 };
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind"))]
 pub enum EDSObject {
     EDSNull {
         address: Address,
@@ -30,9 +32,12 @@ pub enum EDSObject {
         object_type: ObjectType,
         data_type: DataType,
         access_mode: AccessMode,
+        #[cfg_attr(feature = "serde", serde(with = "super::serde_value"))]
         default: Option<Box<dyn EDSValue>>,
         pdo_mappable: bool,
+        #[cfg_attr(feature = "serde", serde(with = "super::serde_value"))]
         low_limit: Option<Box<dyn EDSValue>>,
+        #[cfg_attr(feature = "serde", serde(with = "super::serde_value"))]
         high_limit: Option<Box<dyn EDSValue>>,
         refuse_write_on_download: bool,
         refuse_read_on_scan: bool,
@@ -52,9 +57,12 @@ pub enum EDSObject {
         object_type: ObjectType,
         data_type: DataType,
         access_mode: AccessMode,
+        #[cfg_attr(feature = "serde", serde(with = "super::serde_value"))]
         default: Option<Box<dyn EDSValue>>,
         pdo_mappable: bool,
+        #[cfg_attr(feature = "serde", serde(with = "super::serde_value"))]
         low_limit: Option<Box<dyn EDSValue>>,
+        #[cfg_attr(feature = "serde", serde(with = "super::serde_value"))]
         high_limit: Option<Box<dyn EDSValue>>,
         refuse_write_on_download: bool,
         refuse_read_on_scan: bool,
@@ -65,6 +73,7 @@ pub enum EDSObject {
         object_type: ObjectType,
         data_type: DataType,
         access_mode: AccessMode,
+        #[cfg_attr(feature = "serde", serde(with = "super::serde_value"))]
         default: Option<Box<dyn EDSValue>>,
         refuse_write_on_download: bool,
         refuse_read_on_scan: bool,
@@ -326,7 +335,849 @@ impl EDSObject {
         }
     }
 
-    fn make_addr_string(addr: &Address, with_subindex: bool) -> String {
+    pub fn get_name(&self) -> &String {
+        match &self {
+            EDSObject::CompactEDSArray {
+                address: _,
+                name,
+                object_type: _,
+                data_type: _,
+                access_mode: _,
+                default: _,
+                pdo_mappable: _,
+                low_limit: _,
+                high_limit: _,
+                refuse_write_on_download: _,
+                refuse_read_on_scan: _,
+            } => name,
+            EDSObject::EDSArray {
+                address: _,
+                name,
+                object_type: _,
+                sub_number: _,
+                entries: _,
+                refuse_write_on_download: _,
+                refuse_read_on_scan: _,
+            } => name,
+            EDSObject::EDSDomain {
+                address: _,
+                name,
+                object_type: _,
+                data_type: _,
+                access_mode: _,
+                default: _,
+                refuse_write_on_download: _,
+                refuse_read_on_scan: _,
+            } => name,
+            EDSObject::EDSNull {
+                address: _,
+                name,
+                object_type: _,
+            } => name,
+            EDSObject::EDSVariable {
+                address: _,
+                name,
+                object_type: _,
+                data_type: _,
+                access_mode: _,
+                default: _,
+                pdo_mappable: _,
+                low_limit: _,
+                high_limit: _,
+                refuse_write_on_download: _,
+                refuse_read_on_scan: _,
+            } => name,
+        }
+    }
+
+    pub fn get_access_mode(&self) -> Option<&AccessMode> {
+        match &self {
+            EDSObject::EDSVariable {
+                address: _,
+                name: _,
+                object_type: _,
+                data_type: _,
+                access_mode,
+                default: _,
+                pdo_mappable: _,
+                low_limit: _,
+                high_limit: _,
+                refuse_write_on_download: _,
+                refuse_read_on_scan: _,
+            } => Some(access_mode),
+            EDSObject::EDSDomain {
+                address: _,
+                name: _,
+                object_type: _,
+                data_type: _,
+                access_mode,
+                default: _,
+                refuse_write_on_download: _,
+                refuse_read_on_scan: _,
+            } => Some(access_mode),
+            EDSObject::CompactEDSArray {
+                address: _,
+                name: _,
+                object_type: _,
+                data_type: _,
+                access_mode,
+                default: _,
+                pdo_mappable: _,
+                low_limit: _,
+                high_limit: _,
+                refuse_write_on_download: _,
+                refuse_read_on_scan: _,
+            } => Some(access_mode),
+            _ => None,
+        }
+    }
+
+    pub fn get_default(&self) -> Option<&dyn EDSValue> {
+        match &self {
+            EDSObject::EDSVariable {
+                address: _,
+                name: _,
+                object_type: _,
+                data_type: _,
+                access_mode: _,
+                default,
+                pdo_mappable: _,
+                low_limit: _,
+                high_limit: _,
+                refuse_write_on_download: _,
+                refuse_read_on_scan: _,
+            } => default.as_deref(),
+            EDSObject::EDSDomain {
+                address: _,
+                name: _,
+                object_type: _,
+                data_type: _,
+                access_mode: _,
+                default,
+                refuse_write_on_download: _,
+                refuse_read_on_scan: _,
+            } => default.as_deref(),
+            EDSObject::CompactEDSArray {
+                address: _,
+                name: _,
+                object_type: _,
+                data_type: _,
+                access_mode: _,
+                default,
+                pdo_mappable: _,
+                low_limit: _,
+                high_limit: _,
+                refuse_write_on_download: _,
+                refuse_read_on_scan: _,
+            } => default.as_deref(),
+            _ => None,
+        }
+    }
+
+    pub fn get_limits(&self) -> (Option<&dyn EDSValue>, Option<&dyn EDSValue>) {
+        match &self {
+            EDSObject::EDSVariable {
+                address: _,
+                name: _,
+                object_type: _,
+                data_type: _,
+                access_mode: _,
+                default: _,
+                pdo_mappable: _,
+                low_limit,
+                high_limit,
+                refuse_write_on_download: _,
+                refuse_read_on_scan: _,
+            } => (low_limit.as_deref(), high_limit.as_deref()),
+            EDSObject::CompactEDSArray {
+                address: _,
+                name: _,
+                object_type: _,
+                data_type: _,
+                access_mode: _,
+                default: _,
+                pdo_mappable: _,
+                low_limit,
+                high_limit,
+                refuse_write_on_download: _,
+                refuse_read_on_scan: _,
+            } => (low_limit.as_deref(), high_limit.as_deref()),
+            _ => (None, None),
+        }
+    }
+
+    pub fn is_pdo_mappable(&self) -> bool {
+        match &self {
+            EDSObject::EDSVariable {
+                address: _,
+                name: _,
+                object_type: _,
+                data_type: _,
+                access_mode: _,
+                default: _,
+                pdo_mappable,
+                low_limit: _,
+                high_limit: _,
+                refuse_write_on_download: _,
+                refuse_read_on_scan: _,
+            } => *pdo_mappable,
+            EDSObject::CompactEDSArray {
+                address: _,
+                name: _,
+                object_type: _,
+                data_type: _,
+                access_mode: _,
+                default: _,
+                pdo_mappable,
+                low_limit: _,
+                high_limit: _,
+                refuse_write_on_download: _,
+                refuse_read_on_scan: _,
+            } => *pdo_mappable,
+            _ => false,
+        }
+    }
+
+    pub fn is_write_on_download_refused(&self) -> bool {
+        match &self {
+            EDSObject::EDSNull {
+                address: _,
+                name: _,
+                object_type: _,
+            } => false,
+            EDSObject::EDSVariable {
+                address: _,
+                name: _,
+                object_type: _,
+                data_type: _,
+                access_mode: _,
+                default: _,
+                pdo_mappable: _,
+                low_limit: _,
+                high_limit: _,
+                refuse_write_on_download,
+                refuse_read_on_scan: _,
+            } => *refuse_write_on_download,
+            EDSObject::EDSArray {
+                address: _,
+                name: _,
+                object_type: _,
+                sub_number: _,
+                entries: _,
+                refuse_write_on_download,
+                refuse_read_on_scan: _,
+            } => *refuse_write_on_download,
+            EDSObject::CompactEDSArray {
+                address: _,
+                name: _,
+                object_type: _,
+                data_type: _,
+                access_mode: _,
+                default: _,
+                pdo_mappable: _,
+                low_limit: _,
+                high_limit: _,
+                refuse_write_on_download,
+                refuse_read_on_scan: _,
+            } => *refuse_write_on_download,
+            EDSObject::EDSDomain {
+                address: _,
+                name: _,
+                object_type: _,
+                data_type: _,
+                access_mode: _,
+                default: _,
+                refuse_write_on_download,
+                refuse_read_on_scan: _,
+            } => *refuse_write_on_download,
+        }
+    }
+
+    pub fn is_read_on_scan_refused(&self) -> bool {
+        match &self {
+            EDSObject::EDSNull {
+                address: _,
+                name: _,
+                object_type: _,
+            } => false,
+            EDSObject::EDSVariable {
+                address: _,
+                name: _,
+                object_type: _,
+                data_type: _,
+                access_mode: _,
+                default: _,
+                pdo_mappable: _,
+                low_limit: _,
+                high_limit: _,
+                refuse_write_on_download: _,
+                refuse_read_on_scan,
+            } => *refuse_read_on_scan,
+            EDSObject::EDSArray {
+                address: _,
+                name: _,
+                object_type: _,
+                sub_number: _,
+                entries: _,
+                refuse_write_on_download: _,
+                refuse_read_on_scan,
+            } => *refuse_read_on_scan,
+            EDSObject::CompactEDSArray {
+                address: _,
+                name: _,
+                object_type: _,
+                data_type: _,
+                access_mode: _,
+                default: _,
+                pdo_mappable: _,
+                low_limit: _,
+                high_limit: _,
+                refuse_write_on_download: _,
+                refuse_read_on_scan,
+            } => *refuse_read_on_scan,
+            EDSObject::EDSDomain {
+                address: _,
+                name: _,
+                object_type: _,
+                data_type: _,
+                access_mode: _,
+                default: _,
+                refuse_write_on_download: _,
+                refuse_read_on_scan,
+            } => *refuse_read_on_scan,
+        }
+    }
+
+    pub fn get_entries(&self) -> Option<&Vec<EDSObject>> {
+        match &self {
+            EDSObject::EDSArray {
+                address: _,
+                name: _,
+                object_type: _,
+                sub_number: _,
+                entries,
+                refuse_write_on_download: _,
+                refuse_read_on_scan: _,
+            } => Some(entries),
+            _ => None,
+        }
+    }
+
+    //Finds the object describing the given subindex, compact arrays describe
+    //all of their subindices except the implicit number of entries
+    pub fn get_sub_object(&self, subindex: u8) -> Option<&EDSObject> {
+        match &self {
+            EDSObject::EDSArray {
+                address: _,
+                name: _,
+                object_type: _,
+                sub_number: _,
+                entries,
+                refuse_write_on_download: _,
+                refuse_read_on_scan: _,
+            } => entries
+                .iter()
+                .find(|e| e.get_address().subindex == subindex),
+            EDSObject::CompactEDSArray {
+                address: _,
+                name: _,
+                object_type: _,
+                data_type: _,
+                access_mode: _,
+                default: _,
+                pdo_mappable: _,
+                low_limit: _,
+                high_limit: _,
+                refuse_write_on_download: _,
+                refuse_read_on_scan: _,
+            } => {
+                if subindex == 0 {
+                    None
+                } else {
+                    Some(self)
+                }
+            }
+            _ => {
+                if subindex == 0 {
+                    Some(self)
+                } else {
+                    None
+                }
+            }
+        }
+    }
+
+    pub fn get_sub_object_mut(&mut self, subindex: u8) -> Option<&mut EDSObject> {
+        match self {
+            EDSObject::EDSArray {
+                address: _,
+                name: _,
+                object_type: _,
+                sub_number: _,
+                entries,
+                refuse_write_on_download: _,
+                refuse_read_on_scan: _,
+            } => entries
+                .iter_mut()
+                .find(|e| e.get_address().subindex == subindex),
+            EDSObject::CompactEDSArray {
+                address: _,
+                name: _,
+                object_type: _,
+                data_type: _,
+                access_mode: _,
+                default: _,
+                pdo_mappable: _,
+                low_limit: _,
+                high_limit: _,
+                refuse_write_on_download: _,
+                refuse_read_on_scan: _,
+            } => {
+                if subindex == 0 {
+                    None
+                } else {
+                    Some(self)
+                }
+            }
+            _ => {
+                if subindex == 0 {
+                    Some(self)
+                } else {
+                    None
+                }
+            }
+        }
+    }
+
+    //Returns false for objects without a value
+    pub fn set_default(&mut self, value: Option<Box<dyn EDSValue>>) -> bool {
+        match self {
+            EDSObject::EDSVariable {
+                address: _,
+                name: _,
+                object_type: _,
+                data_type: _,
+                access_mode: _,
+                default,
+                pdo_mappable: _,
+                low_limit: _,
+                high_limit: _,
+                refuse_write_on_download: _,
+                refuse_read_on_scan: _,
+            } => *default = value,
+            EDSObject::EDSDomain {
+                address: _,
+                name: _,
+                object_type: _,
+                data_type: _,
+                access_mode: _,
+                default,
+                refuse_write_on_download: _,
+                refuse_read_on_scan: _,
+            } => *default = value,
+            EDSObject::CompactEDSArray {
+                address: _,
+                name: _,
+                object_type: _,
+                data_type: _,
+                access_mode: _,
+                default,
+                pdo_mappable: _,
+                low_limit: _,
+                high_limit: _,
+                refuse_write_on_download: _,
+                refuse_read_on_scan: _,
+            } => *default = value,
+            _ => return false,
+        }
+        true
+    }
+
+    pub fn set_name(&mut self, value: String) {
+        match self {
+            EDSObject::EDSNull {
+                address: _,
+                name,
+                object_type: _,
+            } => *name = value,
+            EDSObject::EDSVariable {
+                address: _,
+                name,
+                object_type: _,
+                data_type: _,
+                access_mode: _,
+                default: _,
+                pdo_mappable: _,
+                low_limit: _,
+                high_limit: _,
+                refuse_write_on_download: _,
+                refuse_read_on_scan: _,
+            } => *name = value,
+            EDSObject::EDSArray {
+                address: _,
+                name,
+                object_type: _,
+                sub_number: _,
+                entries: _,
+                refuse_write_on_download: _,
+                refuse_read_on_scan: _,
+            } => *name = value,
+            EDSObject::CompactEDSArray {
+                address: _,
+                name,
+                object_type: _,
+                data_type: _,
+                access_mode: _,
+                default: _,
+                pdo_mappable: _,
+                low_limit: _,
+                high_limit: _,
+                refuse_write_on_download: _,
+                refuse_read_on_scan: _,
+            } => *name = value,
+            EDSObject::EDSDomain {
+                address: _,
+                name,
+                object_type: _,
+                data_type: _,
+                access_mode: _,
+                default: _,
+                refuse_write_on_download: _,
+                refuse_read_on_scan: _,
+            } => *name = value,
+        }
+    }
+
+    //Returns false for objects without an access type
+    pub fn set_access_mode(&mut self, value: AccessMode) -> bool {
+        match self {
+            EDSObject::EDSVariable {
+                address: _,
+                name: _,
+                object_type: _,
+                data_type: _,
+                access_mode,
+                default: _,
+                pdo_mappable: _,
+                low_limit: _,
+                high_limit: _,
+                refuse_write_on_download: _,
+                refuse_read_on_scan: _,
+            } => *access_mode = value,
+            EDSObject::CompactEDSArray {
+                address: _,
+                name: _,
+                object_type: _,
+                data_type: _,
+                access_mode,
+                default: _,
+                pdo_mappable: _,
+                low_limit: _,
+                high_limit: _,
+                refuse_write_on_download: _,
+                refuse_read_on_scan: _,
+            } => *access_mode = value,
+            EDSObject::EDSDomain {
+                address: _,
+                name: _,
+                object_type: _,
+                data_type: _,
+                access_mode,
+                default: _,
+                refuse_write_on_download: _,
+                refuse_read_on_scan: _,
+            } => *access_mode = value,
+            _ => return false,
+        }
+        true
+    }
+
+    //Returns false for objects without limits
+    pub fn set_limits(
+        &mut self,
+        low: Option<Box<dyn EDSValue>>,
+        high: Option<Box<dyn EDSValue>>,
+    ) -> bool {
+        match self {
+            EDSObject::EDSVariable {
+                address: _,
+                name: _,
+                object_type: _,
+                data_type: _,
+                access_mode: _,
+                default: _,
+                pdo_mappable: _,
+                low_limit,
+                high_limit,
+                refuse_write_on_download: _,
+                refuse_read_on_scan: _,
+            } => {
+                *low_limit = low;
+                *high_limit = high;
+            }
+            EDSObject::CompactEDSArray {
+                address: _,
+                name: _,
+                object_type: _,
+                data_type: _,
+                access_mode: _,
+                default: _,
+                pdo_mappable: _,
+                low_limit,
+                high_limit,
+                refuse_write_on_download: _,
+                refuse_read_on_scan: _,
+            } => {
+                *low_limit = low;
+                *high_limit = high;
+            }
+            _ => return false,
+        }
+        true
+    }
+
+    //Returns false for objects without ObjFlags
+    pub fn set_obj_flags(&mut self, write_on_download: bool, read_on_scan: bool) -> bool {
+        match self {
+            EDSObject::EDSVariable {
+                address: _,
+                name: _,
+                object_type: _,
+                data_type: _,
+                access_mode: _,
+                default: _,
+                pdo_mappable: _,
+                low_limit: _,
+                high_limit: _,
+                refuse_write_on_download,
+                refuse_read_on_scan,
+            } => {
+                *refuse_write_on_download = write_on_download;
+                *refuse_read_on_scan = read_on_scan;
+            }
+            EDSObject::EDSArray {
+                address: _,
+                name: _,
+                object_type: _,
+                sub_number: _,
+                entries: _,
+                refuse_write_on_download,
+                refuse_read_on_scan,
+            } => {
+                *refuse_write_on_download = write_on_download;
+                *refuse_read_on_scan = read_on_scan;
+            }
+            EDSObject::CompactEDSArray {
+                address: _,
+                name: _,
+                object_type: _,
+                data_type: _,
+                access_mode: _,
+                default: _,
+                pdo_mappable: _,
+                low_limit: _,
+                high_limit: _,
+                refuse_write_on_download,
+                refuse_read_on_scan,
+            } => {
+                *refuse_write_on_download = write_on_download;
+                *refuse_read_on_scan = read_on_scan;
+            }
+            EDSObject::EDSDomain {
+                address: _,
+                name: _,
+                object_type: _,
+                data_type: _,
+                access_mode: _,
+                default: _,
+                refuse_write_on_download,
+                refuse_read_on_scan,
+            } => {
+                *refuse_write_on_download = write_on_download;
+                *refuse_read_on_scan = read_on_scan;
+            }
+            _ => return false,
+        }
+        true
+    }
+
+    pub(crate) fn get_entries_mut(&mut self) -> Option<&mut Vec<EDSObject>> {
+        match self {
+            EDSObject::EDSArray {
+                address: _,
+                name: _,
+                object_type: _,
+                sub_number: _,
+                entries,
+                refuse_write_on_download: _,
+                refuse_read_on_scan: _,
+            } => Some(entries),
+            _ => None,
+        }
+    }
+
+    //Keeps SubNumber equal to the number of sub-entries
+    pub(crate) fn update_sub_number(&mut self) {
+        if let EDSObject::EDSArray {
+            address: _,
+            name: _,
+            object_type: _,
+            sub_number,
+            entries,
+            refuse_write_on_download: _,
+            refuse_read_on_scan: _,
+        } = self
+        {
+            *sub_number = entries.len() as u8;
+        }
+    }
+
+    //Moves the object and its sub-entries to another index
+    pub(crate) fn set_index(&mut self, index: u16) {
+        match self {
+            EDSObject::EDSNull {
+                address,
+                name: _,
+                object_type: _,
+            } => address.index = index,
+            EDSObject::EDSVariable {
+                address,
+                name: _,
+                object_type: _,
+                data_type: _,
+                access_mode: _,
+                default: _,
+                pdo_mappable: _,
+                low_limit: _,
+                high_limit: _,
+                refuse_write_on_download: _,
+                refuse_read_on_scan: _,
+            } => address.index = index,
+            EDSObject::CompactEDSArray {
+                address,
+                name: _,
+                object_type: _,
+                data_type: _,
+                access_mode: _,
+                default: _,
+                pdo_mappable: _,
+                low_limit: _,
+                high_limit: _,
+                refuse_write_on_download: _,
+                refuse_read_on_scan: _,
+            } => address.index = index,
+            EDSObject::EDSDomain {
+                address,
+                name: _,
+                object_type: _,
+                data_type: _,
+                access_mode: _,
+                default: _,
+                refuse_write_on_download: _,
+                refuse_read_on_scan: _,
+            } => address.index = index,
+            EDSObject::EDSArray {
+                address,
+                name: _,
+                object_type: _,
+                sub_number: _,
+                entries,
+                refuse_write_on_download: _,
+                refuse_read_on_scan: _,
+            } => {
+                address.index = index;
+                for e in entries {
+                    e.set_index(index);
+                }
+            }
+        }
+    }
+
+    //Checks the rules the parser enforces so that the object survives being
+    //written and parsed again
+    pub fn check(&self) -> Result<(), EditError> {
+        let addr = *self.get_address();
+        if self.get_name().is_empty() {
+            return Err(EditError::EmptyName { addr });
+        }
+        if let Some(data_type) = self.get_data_type() {
+            let (low, high) = self.get_limits();
+            let values = [self.get_default(), low, high];
+            for value in values.into_iter().flatten() {
+                let node_relative = data_type.is_integer() && value.as_any().is::<NodeIdOffset>();
+                if !node_relative && !eds_value_matches_type(value, data_type) {
+                    return Err(EditError::ValueTypeMismatch {
+                        addr,
+                        data_type: data_type.clone(),
+                    });
+                }
+            }
+            if (low.is_some() || high.is_some()) && data_type.bit_size().is_none_or(|s| s == 1) {
+                return Err(EditError::LimitsNotSupported {
+                    addr,
+                    data_type: data_type.clone(),
+                });
+            }
+        }
+        if let Some(access_mode) = self.get_access_mode()
+            && !matches!(self, EDSObject::EDSDomain { .. })
+            && !access_mode.is_valid(self.is_pdo_mappable())
+        {
+            return Err(EditError::PdoMappingNotAllowed {
+                addr,
+                access_mode: access_mode.clone(),
+            });
+        }
+        let EDSObject::EDSArray {
+            object_type,
+            sub_number,
+            entries,
+            ..
+        } = self
+        else {
+            return Ok(());
+        };
+        if *sub_number as usize != entries.len() {
+            return Err(EditError::SubNumberMismatch {
+                index: addr.index,
+                sub_number: *sub_number,
+                entries: entries.len(),
+            });
+        }
+        let mut last = None;
+        for e in entries {
+            let sub_addr = *e.get_address();
+            if sub_addr.index != addr.index || last.is_some_and(|l| l >= sub_addr.subindex) {
+                return Err(EditError::InvalidSubAddress { addr: sub_addr });
+            }
+            last = Some(sub_addr.subindex);
+            if matches!(
+                e.get_obj_type(),
+                ObjectType::Array | ObjectType::Defstruct | ObjectType::Record
+            ) {
+                return Err(EditError::NestedObject { addr: sub_addr });
+            }
+            e.check()?;
+        }
+        //Array entries after the number of entries share type and data type
+        if *object_type == ObjectType::Array {
+            let mut values = entries.iter().filter(|e| e.get_address().subindex != 0);
+            if let Some(first) = values.next() {
+                for e in values {
+                    if e.get_obj_type() != first.get_obj_type()
+                        || e.get_data_type() != first.get_data_type()
+                    {
+                        return Err(EditError::InconsistentArray {
+                            addr: *e.get_address(),
+                        });
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub(crate) fn make_addr_string(addr: &Address, with_subindex: bool) -> String {
         if !with_subindex {
             format!("{:x}", addr.index)
         } else {
@@ -366,7 +1217,10 @@ impl EDSObject {
         return Ok((refuse_write_on_download, refuse_read_on_scan));
     }
 
-    fn parse_sub_addr(sfile: &StructuredFile, addr: &Address) -> Result<EDSObject, ParseError> {
+    pub(crate) fn parse_sub_addr(
+        sfile: &StructuredFile,
+        addr: &Address,
+    ) -> Result<EDSObject, ParseError> {
         let section_name = Self::make_addr_string(addr, true);
         let obj = sfile
             .get_object(&section_name)
@@ -395,7 +1249,7 @@ impl EDSObject {
         }
     }
 
-    fn parse_data(
+    pub(crate) fn parse_data(
         obj: &StructuredFileObject,
         data_type: &DataType,
         name: &str,
@@ -403,6 +1257,11 @@ impl EDSObject {
         if obj.get_value(name).is_none() {
             return Ok(None);
         }
+        if data_type.is_integer()
+            && let Some(offset) = parse_node_id_offset(obj, name)?
+        {
+            return Ok(Some(Box::new(offset)));
+        }
         match data_type {
             DataType::Boolean => Ok(Some(Box::new(parse_required_bool(obj, name)?))),
             DataType::UInt8 => Ok(Some(Box::new(parse_required_uint::<u8>(obj, name)?))),
@@ -424,16 +1283,10 @@ impl EDSObject {
                     Ok(Some(Box::new(val)))
                 }
             }
-            DataType::Int8 => Ok(Some(Box::new(parse_required_uint::<u8>(obj, name)? as i8))),
-            DataType::Int16 => Ok(Some(
-                Box::new(parse_required_uint::<u16>(obj, name)? as i16),
-            )),
-            DataType::Int32 => Ok(Some(
-                Box::new(parse_required_uint::<u32>(obj, name)? as i32),
-            )),
-            DataType::Int64 => Ok(Some(
-                Box::new(parse_required_uint::<u64>(obj, name)? as i64),
-            )),
+            DataType::Int8 => Ok(Some(Box::new(parse_required_int::<i8>(obj, name)?))),
+            DataType::Int16 => Ok(Some(Box::new(parse_required_int::<i16>(obj, name)?))),
+            DataType::Int32 => Ok(Some(Box::new(parse_required_int::<i32>(obj, name)?))),
+            DataType::Int64 => Ok(Some(Box::new(parse_required_int::<i64>(obj, name)?))),
             DataType::Real32 => Ok(Some(Box::new(parse_required_float(obj, name)?))),
             DataType::Real64 => Ok(Some(Box::new(parse_required_double(obj, name)?))),
         }
@@ -448,7 +1301,7 @@ impl EDSObject {
         let access_mode = Self::parse_access_mode(obj, &addr)?;
         let (refuse_write_on_download, refuse_read_on_scan) = Self::parse_obj_flags(obj)?;
         let data_type = Self::parse_data_type(obj, &addr)?;
-        let default = Self::parse_data(obj, &data_type, "Default")?;
+        let default = Self::parse_data(obj, &data_type, "DefaultValue")?;
         let low_limit = Self::parse_data(obj, &data_type, "LowLimit")?;
         let high_limit = Self::parse_data(obj, &data_type, "HighLimit")?;
         let pdo_mappable = parse_required_bool(obj, "PDOMapping").or_else(|e| match &e {
@@ -514,7 +1367,7 @@ impl EDSObject {
             }
             _ => Err(e),
         })?;
-        let default = Self::parse_data(obj, &data_type, "Default")?;
+        let default = Self::parse_data(obj, &data_type, "DefaultValue")?;
         Ok(EDSObject::EDSDomain {
             address: addr,
             name: name.clone(),
@@ -537,7 +1390,11 @@ impl EDSObject {
         let (refuse_write_on_download, refuse_read_on_scan) = Self::parse_obj_flags(obj)?;
         let sub_number = parse_required_uint(obj, "SubNumber")?;
         let mut entries = Vec::new();
-        for i in 0..sub_number {
+        //SubNumber counts the defined sub-entries, their subindices may have gaps
+        for i in 0..=u8::MAX {
+            if entries.len() >= sub_number as usize {
+                break;
+            }
             let subaddr = Address::new(addr.index, i);
             let section_name = Self::make_addr_string(&subaddr, true);
             let sub = Self::parse_sub_addr(sfile, &subaddr);
@@ -607,7 +1464,7 @@ impl EDSObject {
         let access_mode = Self::parse_access_mode(obj, &addr)?;
         let (refuse_write_on_download, refuse_read_on_scan) = Self::parse_obj_flags(obj)?;
         let data_type = Self::parse_data_type(obj, &addr)?;
-        let default = Self::parse_data(obj, &data_type, "Default")?;
+        let default = Self::parse_data(obj, &data_type, "DefaultValue")?;
         let low_limit = Self::parse_data(obj, &data_type, "LowLimit")?;
         let high_limit = Self::parse_data(obj, &data_type, "HighLimit")?;
         let pdo_mappable = parse_required_bool(obj, "PDOMapping").or_else(|e| match &e {
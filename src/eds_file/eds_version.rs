@@ -1,9 +1,20 @@
+use std::fmt::Display;
+
 use crate::ParseError;
-use crate::structured_file::StructuredFileObject;
+use crate::eds_file::utils::parse_required_uint;
+use crate::structured_file::{StructuredFile, StructuredFileObject};
+
+//Keys and sections that were added with EDS 4.0, object links and dynamic
+//channels are not part of the object model and only checked for
+const V4_0_KEYS: [&str; 3] = ["CompactSubObj", "ObjFlags", "DynamicChannelsSupported"];
+const V4_0_SECTIONS: [&str; 2] = ["objectlinks", "dynamicchannels"];
 
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum EDSVersion {
+    #[cfg_attr(feature = "serde", serde(rename = "3.0"))]
     V3_0,
+    #[cfg_attr(feature = "serde", serde(rename = "4.0"))]
     V4_0,
     Unsupported,
 }
@@ -29,4 +40,46 @@ impl EDSVersion {
         }
         return Ok(EDSVersion::Unsupported);
     }
+
+    //Files that declare EDS 3.0 may not use constructs of 4.0, unsupported
+    //versions are parsed by the rules of 4.0. Keys of 4.0 that are empty or
+    //zero leave the object as 3.0 describes it and are accepted.
+    pub(crate) fn check_file(&self, sfile: &StructuredFile) -> Result<(), ParseError> {
+        if *self != EDSVersion::V3_0 {
+            return Ok(());
+        }
+        for obj in sfile.get_objects() {
+            let section = obj.get_name().to_lowercase();
+            if V4_0_SECTIONS.iter().any(|s| section.ends_with(s)) {
+                return Err(ParseError::UnsupportedInVersion {
+                    section: obj.get_name().clone(),
+                    key: None,
+                    version: self.clone(),
+                });
+            }
+            let used = V4_0_KEYS.iter().find(|key| {
+                obj.get_value(key).is_some_and(|v| {
+                    !v.is_empty() && parse_required_uint::<u64>(obj, key).ok() != Some(0)
+                })
+            });
+            if let Some(key) = used {
+                return Err(ParseError::UnsupportedInVersion {
+                    section: obj.get_name().clone(),
+                    key: Some(key.to_string()),
+                    version: self.clone(),
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Display for EDSVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self {
+            EDSVersion::V3_0 => write!(f, "3.0"),
+            EDSVersion::V4_0 => write!(f, "4.0"),
+            EDSVersion::Unsupported => write!(f, "unsupported"),
+        }
+    }
 }
@@ -0,0 +1,251 @@
+use std::collections::HashMap;
+use std::io::Write;
+
+use chrono::{DateTime, Timelike, Utc};
+
+use super::{
+    AccessMode, Address, EDSDeviceInfo, EDSDummyUsage, EDSFile, EDSFileInfo, EDSObject, EDSValue,
+    EDSVersion, ObjectList, data_type::DataType, eds_value_to_string,
+};
+
+pub(crate) type Keys = Vec<(&'static str, String)>;
+
+pub(crate) fn write_section<K: AsRef<str>>(out: &mut String, name: &str, keys: &[(K, String)]) {
+    out.push_str(&format!("[{}]\n", name));
+    for (key, value) in keys {
+        out.push_str(&format!("{}={}\n", key.as_ref(), value));
+    }
+    out.push('\n');
+}
+
+pub(crate) fn bool_str(value: bool) -> String {
+    (value as u8).to_string()
+}
+
+//EDS dates are MM-DD-YYYY and times hh:mm with AM or PM
+fn date_time_strs(value: &DateTime<Utc>) -> (String, String) {
+    let (pm, hour) = value.hour12();
+    let time = format!(
+        "{:02}:{:02}{}",
+        hour,
+        value.minute(),
+        if pm { "PM" } else { "AM" }
+    );
+    (value.format("%m-%d-%Y").to_string(), time)
+}
+
+pub(crate) fn file_info_keys(info: &EDSFileInfo) -> Keys {
+    let (creation_date, creation_time) = date_time_strs(&info.creation);
+    let (modification_date, modification_time) = date_time_strs(&info.modification);
+    let mut keys = vec![
+        ("FileName", info.file_name.clone()),
+        ("FileVersion", info.file_version.to_string()),
+        ("FileRevision", info.file_revision.to_string()),
+    ];
+    //Unsupported versions are not written as they can not be told apart
+    match info.eds_version {
+        EDSVersion::V3_0 => keys.push(("EDSVersion", "3.0".to_string())),
+        EDSVersion::V4_0 => keys.push(("EDSVersion", "4.0".to_string())),
+        EDSVersion::Unsupported => {}
+    }
+    keys.extend([
+        ("Description", info.description.clone()),
+        ("CreationTime", creation_time),
+        ("CreationDate", creation_date),
+        ("CreatedBy", info.created_by.clone()),
+        ("ModificationTime", modification_time),
+        ("ModificationDate", modification_date),
+        ("ModifiedBy", info.modified_by.clone()),
+    ]);
+    keys
+}
+
+pub(crate) fn device_info_keys(info: &EDSDeviceInfo, version: &EDSVersion) -> Keys {
+    let mut keys = vec![
+        ("VendorName", info.vendor_name.clone()),
+        ("VendorNumber", format!("0x{:08X}", info.vendor_number)),
+        ("ProductName", info.product_name.clone()),
+        ("ProductNumber", format!("0x{:08X}", info.product_number)),
+        ("RevisionNumber", format!("0x{:08X}", info.revision_number)),
+        ("OrderCode", info.order_code.clone()),
+        ("BaudRate_10", bool_str(info.baudrate_10khz)),
+        ("BaudRate_20", bool_str(info.baudrate_20khz)),
+        ("BaudRate_50", bool_str(info.baudrate_50khz)),
+        ("BaudRate_125", bool_str(info.baudrate_125khz)),
+        ("BaudRate_250", bool_str(info.baudrate_250khz)),
+        ("BaudRate_500", bool_str(info.baudrate_500khz)),
+        ("BaudRate_800", bool_str(info.baudrate_800khz)),
+        ("BaudRate_1000", bool_str(info.baudrate_1000khz)),
+        ("SimpleBootUpMaster", bool_str(info.simple_bootup_master)),
+        ("SimpleBootUpSlave", bool_str(info.simple_bootup_slave)),
+        ("Granularity", info.granularity.to_string()),
+        (
+            "DynamicChannelsSupported",
+            bool_str(info.dynamic_channel_supported),
+        ),
+        ("GroupMessaging", bool_str(info.group_messaging)),
+        ("NrOfRXPDO", info.nr_rpdo.to_string()),
+        ("NrOfTXPDO", info.nr_tpdo.to_string()),
+        ("LSS_Supported", bool_str(info.lss_supported)),
+    ];
+    if *version == EDSVersion::V3_0 {
+        keys.retain(|(k, _)| *k != "DynamicChannelsSupported");
+    }
+    keys
+}
+
+pub(crate) fn dummy_usage_keys(dummy_usage: &EDSDummyUsage) -> Vec<(String, String)> {
+    let mut indices: Vec<&u16> = dummy_usage.dummies.keys().collect();
+    indices.sort();
+    indices
+        .into_iter()
+        .map(|index| {
+            let supported = bool_str(dummy_usage.is_supported(*index));
+            (format!("Dummy{:04X}", index), supported)
+        })
+        .collect()
+}
+
+fn value_str(value: Option<&dyn EDSValue>, data_type: &DataType) -> Option<String> {
+    value.and_then(|v| eds_value_to_string(v, data_type))
+}
+
+fn obj_flags(refuse_write_on_download: bool, refuse_read_on_scan: bool) -> Option<String> {
+    let flags = refuse_write_on_download as u32 | (refuse_read_on_scan as u32) << 1;
+    (flags != 0).then(|| format!("0x{:X}", flags))
+}
+
+pub(crate) fn section_name(addr: &Address, is_sub: bool) -> String {
+    if is_sub {
+        format!("{:X}sub{:X}", addr.index, addr.subindex)
+    } else {
+        format!("{:X}", addr.index)
+    }
+}
+
+fn value_keys(
+    keys: &mut Keys,
+    data_type: &DataType,
+    access_mode: &AccessMode,
+    default: Option<&dyn EDSValue>,
+    pdo_mappable: Option<bool>,
+    limits: (Option<&dyn EDSValue>, Option<&dyn EDSValue>),
+) {
+    keys.push(("DataType", format!("0x{:04X}", data_type.encode())));
+    if let Some(low) = value_str(limits.0, data_type) {
+        keys.push(("LowLimit", low));
+    }
+    if let Some(high) = value_str(limits.1, data_type) {
+        keys.push(("HighLimit", high));
+    }
+    keys.push(("AccessType", access_mode.to_string()));
+    if let Some(default) = value_str(default, data_type) {
+        keys.push(("DefaultValue", default));
+    }
+    if let Some(pdo_mappable) = pdo_mappable {
+        keys.push(("PDOMapping", bool_str(pdo_mappable)));
+    }
+}
+
+//Keys of the section of an object, sub-objects have sections of their own
+pub(crate) fn object_keys(obj: &EDSObject) -> Keys {
+    let mut keys: Keys = Vec::new();
+    if let EDSObject::EDSArray { sub_number, .. } = obj {
+        keys.push(("SubNumber", format!("0x{:X}", sub_number)));
+    }
+    keys.push(("ParameterName", obj.get_name().clone()));
+    keys.push(("ObjectType", format!("0x{:X}", obj.get_obj_type().encode())));
+    if let EDSObject::CompactEDSArray { .. } = obj {
+        keys.push(("CompactSubObj", "1".to_string()));
+    }
+    if let (Some(data_type), Some(access_mode)) = (obj.get_data_type(), obj.get_access_mode()) {
+        let pdo_mappable = match obj {
+            EDSObject::EDSDomain { .. } => None,
+            _ => Some(obj.is_pdo_mappable()),
+        };
+        value_keys(
+            &mut keys,
+            data_type,
+            access_mode,
+            obj.get_default(),
+            pdo_mappable,
+            obj.get_limits(),
+        );
+    }
+    if let Some(flags) = obj_flags(
+        obj.is_write_on_download_refused(),
+        obj.is_read_on_scan_refused(),
+    ) {
+        keys.push(("ObjFlags", flags));
+    }
+    keys
+}
+
+pub(crate) type ParameterValues = HashMap<Address, Box<dyn EDSValue>>;
+
+//Configured values of a DCF are only held by objects without sub-objects
+fn write_object(out: &mut String, obj: &EDSObject, is_sub: bool, values: &ParameterValues) {
+    let name = section_name(obj.get_address(), is_sub);
+    let mut keys = object_keys(obj);
+    match obj.get_entries() {
+        Some(entries) => {
+            write_section(out, &name, &keys);
+            for entry in entries {
+                write_object(out, entry, true, values);
+            }
+        }
+        None => {
+            let value = values.get(obj.get_address()).map(|v| v.as_ref());
+            if let Some(value) = obj.get_data_type().and_then(|t| value_str(value, t)) {
+                keys.push(("ParameterValue", value));
+            }
+            write_section(out, &name, &keys);
+        }
+    }
+}
+
+pub(crate) fn write_info_sections(out: &mut String, file: &EDSFile) {
+    write_section(out, "FileInfo", &file_info_keys(&file.file_info));
+    write_section(
+        out,
+        "DeviceInfo",
+        &device_info_keys(&file.device_info, &file.file_info.eds_version),
+    );
+    if !file.dummy_usage.dummies.is_empty() {
+        let keys = dummy_usage_keys(&file.dummy_usage);
+        write_section(out, "DummyUsage", &keys);
+    }
+}
+
+//The object list sections are generated from the objects of each list and
+//all objects are written by ascending index
+pub(crate) fn write_object_sections(out: &mut String, file: &EDSFile, values: &ParameterValues) {
+    for list in ObjectList::ALL {
+        let mut indices: Vec<u16> = file.get_list(list).keys().map(|a| a.index).collect();
+        indices.sort();
+        out.push_str(&format!("[{}]\n", list.get_section_name()));
+        out.push_str(&format!("SupportedObjects={}\n", indices.len()));
+        for (i, index) in indices.iter().enumerate() {
+            out.push_str(&format!("{}=0x{:04X}\n", i + 1, index));
+        }
+        out.push('\n');
+    }
+    for obj in file.get_objects() {
+        write_object(out, obj, false, values);
+    }
+}
+
+impl EDSFile {
+    //Writes the file in EDS format
+    pub fn to_eds_string(&self) -> String {
+        let mut out = String::new();
+        write_info_sections(&mut out, self);
+        write_object_sections(&mut out, self, &HashMap::new());
+        out
+    }
+
+    pub fn save(&self, filename: &str) -> Result<(), std::io::Error> {
+        let mut file = std::fs::File::create(filename)?;
+        file.write_all(self.to_eds_string().as_bytes())
+    }
+}
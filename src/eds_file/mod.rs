@@ -1,4 +1,5 @@
 mod eds_device_info;
+mod eds_dummy_usage;
 mod eds_file_info;
 
 mod access_mode;
@@ -10,8 +11,12 @@ mod eds_date;
 mod eds_object;
 mod eds_time;
 mod eds_version;
+mod eds_writer;
+mod object_list;
+#[cfg(feature = "serde")]
+mod serde_value;
 
-mod utils;
+pub(crate) mod utils;
 
 #[cfg(test)]
 mod tests;
@@ -24,24 +29,99 @@ pub use data_type::*;
 pub use object_type::ObjectType;
 
 pub use eds_device_info::EDSDeviceInfo;
+pub use eds_dummy_usage::EDSDummyUsage;
 pub use eds_file_info::EDSFileInfo;
 pub use eds_object::*;
 pub use eds_version::EDSVersion;
+pub use object_list::ObjectList;
+
+pub(crate) use eds_writer::{
+    bool_str, device_info_keys, dummy_usage_keys, file_info_keys, object_keys, section_name,
+    write_info_sections, write_object_sections, write_section,
+};
 use utils::parse_required_uint;
 
 use super::structured_file::StructuredFile;
 use crate::ParseError;
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct EDSFile {
     pub file_info: EDSFileInfo,
     pub device_info: EDSDeviceInfo,
+    pub dummy_usage: EDSDummyUsage,
     pub mandatory_object: HashMap<Address, EDSObject>,
     pub optional_object: HashMap<Address, EDSObject>,
     pub manufacturer_object: HashMap<Address, EDSObject>,
 }
 
 impl EDSFile {
+    pub fn get_index_object(&self, index: u16) -> Option<&EDSObject> {
+        let index_addr = Address::new(index, 0);
+        self.mandatory_object
+            .get(&index_addr)
+            .or_else(|| self.optional_object.get(&index_addr))
+            .or_else(|| self.manufacturer_object.get(&index_addr))
+    }
+
+    pub fn get_list(&self, list: ObjectList) -> &HashMap<Address, EDSObject> {
+        match list {
+            ObjectList::Mandatory => &self.mandatory_object,
+            ObjectList::Optional => &self.optional_object,
+            ObjectList::Manufacturer => &self.manufacturer_object,
+        }
+    }
+
+    pub fn get_list_mut(&mut self, list: ObjectList) -> &mut HashMap<Address, EDSObject> {
+        match list {
+            ObjectList::Mandatory => &mut self.mandatory_object,
+            ObjectList::Optional => &mut self.optional_object,
+            ObjectList::Manufacturer => &mut self.manufacturer_object,
+        }
+    }
+
+    //The object list that contains an index
+    pub fn get_object_list(&self, index: u16) -> Option<ObjectList> {
+        let index_addr = Address::new(index, 0);
+        ObjectList::ALL
+            .into_iter()
+            .find(|list| self.get_list(*list).contains_key(&index_addr))
+    }
+
+    pub fn get_object(&self, addr: &Address) -> Option<&EDSObject> {
+        self.get_index_object(addr.index)
+            .and_then(|obj| obj.get_sub_object(addr.subindex))
+    }
+
+    pub fn get_object_mut(&mut self, addr: &Address) -> Option<&mut EDSObject> {
+        let index_addr = Address::new(addr.index, 0);
+        let obj = if self.mandatory_object.contains_key(&index_addr) {
+            self.mandatory_object.get_mut(&index_addr)
+        } else if self.optional_object.contains_key(&index_addr) {
+            self.optional_object.get_mut(&index_addr)
+        } else {
+            self.manufacturer_object.get_mut(&index_addr)
+        };
+        obj.and_then(|obj| obj.get_sub_object_mut(addr.subindex))
+    }
+
+    pub fn get_objects(&self) -> Vec<&EDSObject> {
+        let mut out: Vec<&EDSObject> = self
+            .mandatory_object
+            .values()
+            .chain(self.optional_object.values())
+            .chain(self.manufacturer_object.values())
+            .collect();
+        out.sort_by(|a, b| a.get_address().partial_cmp(b.get_address()).unwrap());
+        out
+    }
+
+    pub fn get_default_u64(&self, addr: &Address, node_id: u8) -> Option<u64> {
+        self.get_object(addr)
+            .and_then(|obj| obj.get_default())
+            .and_then(|v| eds_value_to_u64(v, Some(node_id)))
+    }
+
     fn parse_object_list(
         sfile: &StructuredFile,
         name: &str,
@@ -62,7 +142,7 @@ impl EDSFile {
         Ok(map)
     }
 
-    pub(crate) fn parse(sfile: StructuredFile) -> Result<EDSFile, ParseError> {
+    pub(crate) fn parse(sfile: &StructuredFile) -> Result<EDSFile, ParseError> {
         let file_info_obj =
             sfile
                 .get_object("FileInfo")
@@ -70,20 +150,26 @@ impl EDSFile {
                     section: "FileInfo".to_string(),
                 })?;
         let file_info = EDSFileInfo::parse(file_info_obj)?;
+        file_info.eds_version.check_file(sfile)?;
         let device_info_obj =
             sfile
                 .get_object("DeviceInfo")
                 .ok_or(ParseError::MissingRequiredSection {
                     section: "DeviceInfo".to_string(),
                 })?;
-        let device_info = EDSDeviceInfo::parse(device_info_obj)?;
-        let mandatory_objects = Self::parse_object_list(&sfile, "MandatoryObjects")?;
-        let optional_objects = Self::parse_object_list(&sfile, "OptionalObjects")?;
-        let manufacturer_objects = Self::parse_object_list(&sfile, "ManufacturerObjects")?;
+        let device_info = EDSDeviceInfo::parse(device_info_obj, &file_info.eds_version)?;
+        let dummy_usage = match sfile.get_object("DummyUsage") {
+            Some(obj) => EDSDummyUsage::parse(obj)?,
+            None => EDSDummyUsage::default(),
+        };
+        let mandatory_objects = Self::parse_object_list(sfile, "MandatoryObjects")?;
+        let optional_objects = Self::parse_object_list(sfile, "OptionalObjects")?;
+        let manufacturer_objects = Self::parse_object_list(sfile, "ManufacturerObjects")?;
 
         Ok(EDSFile {
             file_info: file_info,
             device_info: device_info,
+            dummy_usage,
             mandatory_object: mandatory_objects,
             optional_object: optional_objects,
             manufacturer_object: manufacturer_objects,
@@ -0,0 +1,45 @@
+use std::fmt::Display;
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum ObjectList {
+    Mandatory,
+    Optional,
+    Manufacturer,
+}
+
+impl ObjectList {
+    pub const ALL: [ObjectList; 3] = [
+        ObjectList::Mandatory,
+        ObjectList::Optional,
+        ObjectList::Manufacturer,
+    ];
+
+    //Section name of the list in an EDS file
+    pub fn get_section_name(&self) -> &'static str {
+        match self {
+            ObjectList::Mandatory => "MandatoryObjects",
+            ObjectList::Optional => "OptionalObjects",
+            ObjectList::Manufacturer => "ManufacturerObjects",
+        }
+    }
+
+    //The list an object usually belongs to, CiA 301 only requires device type,
+    //error register and identity
+    pub fn for_index(index: u16) -> ObjectList {
+        match index {
+            0x1000 | 0x1001 | 0x1018 => ObjectList::Mandatory,
+            0x2000..=0x5FFF => ObjectList::Manufacturer,
+            _ => ObjectList::Optional,
+        }
+    }
+}
+
+impl Display for ObjectList {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self {
+            ObjectList::Mandatory => write!(f, "Mandatory"),
+            ObjectList::Optional => write!(f, "Optional"),
+            ObjectList::Manufacturer => write!(f, "Manufacturer"),
+        }
+    }
+}
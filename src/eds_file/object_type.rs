@@ -1,6 +1,7 @@
 use std::fmt::Display;
 
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ObjectType {
     Variable,
     Array,
@@ -24,6 +25,18 @@ impl ObjectType {
             _ => None,
         }
     }
+
+    pub fn encode(&self) -> u8 {
+        match self {
+            ObjectType::Null => 0x00,
+            ObjectType::Domain => 0x02,
+            ObjectType::Deftype => 0x05,
+            ObjectType::Defstruct => 0x06,
+            ObjectType::Variable => 0x07,
+            ObjectType::Array => 0x08,
+            ObjectType::Record => 0x09,
+        }
+    }
 }
 
 impl Display for ObjectType {
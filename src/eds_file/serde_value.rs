@@ -0,0 +1,128 @@
+use std::str::FromStr;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use super::{EDSValue, NodeIdOffset};
+
+//Values are written with the Rust type they are stored as, e.g.
+//{"type": "UInt16", "value": 500} or {"type": "NodeIdOffset", "value": 384}.
+//Strings of both string types share a tag, the data type of the object
+//tells them apart. Floats JSON can not hold are written as "NaN", "inf" or
+//"-inf".
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "type", content = "value")]
+enum TaggedValue {
+    Boolean(bool),
+    UInt8(u8),
+    UInt16(u16),
+    UInt32(u32),
+    UInt64(u64),
+    Int8(i8),
+    Int16(i16),
+    Int32(i32),
+    Int64(i64),
+    Real32(Real<f32>),
+    Real64(Real<f64>),
+    String(String),
+    Bytes(Vec<u8>),
+    NodeIdOffset(u64),
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum Real<T> {
+    Finite(T),
+    NonFinite(String),
+}
+
+impl<T: Copy + Into<f64> + ToString + FromStr> Real<T> {
+    fn new(value: T) -> Real<T> {
+        if value.into().is_finite() {
+            Real::Finite(value)
+        } else {
+            Real::NonFinite(value.to_string())
+        }
+    }
+
+    fn get(self) -> Result<T, String> {
+        match self {
+            Real::Finite(value) => Ok(value),
+            Real::NonFinite(s) => s.parse().map_err(|_| format!("Invalid float {}", s)),
+        }
+    }
+}
+
+fn tag_value(value: &dyn EDSValue) -> Option<TaggedValue> {
+    let any = value.as_any();
+    let tagged = if let Some(v) = any.downcast_ref::<bool>() {
+        TaggedValue::Boolean(*v)
+    } else if let Some(v) = any.downcast_ref::<u8>() {
+        TaggedValue::UInt8(*v)
+    } else if let Some(v) = any.downcast_ref::<u16>() {
+        TaggedValue::UInt16(*v)
+    } else if let Some(v) = any.downcast_ref::<u32>() {
+        TaggedValue::UInt32(*v)
+    } else if let Some(v) = any.downcast_ref::<u64>() {
+        TaggedValue::UInt64(*v)
+    } else if let Some(v) = any.downcast_ref::<i8>() {
+        TaggedValue::Int8(*v)
+    } else if let Some(v) = any.downcast_ref::<i16>() {
+        TaggedValue::Int16(*v)
+    } else if let Some(v) = any.downcast_ref::<i32>() {
+        TaggedValue::Int32(*v)
+    } else if let Some(v) = any.downcast_ref::<i64>() {
+        TaggedValue::Int64(*v)
+    } else if let Some(v) = any.downcast_ref::<f32>() {
+        TaggedValue::Real32(Real::new(*v))
+    } else if let Some(v) = any.downcast_ref::<f64>() {
+        TaggedValue::Real64(Real::new(*v))
+    } else if let Some(v) = any.downcast_ref::<String>() {
+        TaggedValue::String(v.clone())
+    } else if let Some(v) = any.downcast_ref::<Vec<u8>>() {
+        TaggedValue::Bytes(v.clone())
+    } else {
+        TaggedValue::NodeIdOffset(any.downcast_ref::<NodeIdOffset>()?.offset)
+    };
+    Some(tagged)
+}
+
+fn untag_value(value: TaggedValue) -> Result<Box<dyn EDSValue>, String> {
+    let untagged: Box<dyn EDSValue> = match value {
+        TaggedValue::Boolean(v) => Box::new(v),
+        TaggedValue::UInt8(v) => Box::new(v),
+        TaggedValue::UInt16(v) => Box::new(v),
+        TaggedValue::UInt32(v) => Box::new(v),
+        TaggedValue::UInt64(v) => Box::new(v),
+        TaggedValue::Int8(v) => Box::new(v),
+        TaggedValue::Int16(v) => Box::new(v),
+        TaggedValue::Int32(v) => Box::new(v),
+        TaggedValue::Int64(v) => Box::new(v),
+        TaggedValue::Real32(v) => Box::new(v.get()?),
+        TaggedValue::Real64(v) => Box::new(v.get()?),
+        TaggedValue::String(v) => Box::new(v),
+        TaggedValue::Bytes(v) => Box::new(v),
+        TaggedValue::NodeIdOffset(offset) => Box::new(NodeIdOffset { offset }),
+    };
+    Ok(untagged)
+}
+
+pub(crate) fn serialize<S: Serializer>(
+    value: &Option<Box<dyn EDSValue>>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    let tagged = value
+        .as_ref()
+        .map(|v| tag_value(v.as_ref()).ok_or(v))
+        .transpose()
+        .map_err(|v| serde::ser::Error::custom(format!("Unsupported value {:?}", v)))?;
+    tagged.serialize(serializer)
+}
+
+pub(crate) fn deserialize<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<Box<dyn EDSValue>>, D::Error> {
+    Option::<TaggedValue>::deserialize(deserializer)?
+        .map(untag_value)
+        .transpose()
+        .map_err(serde::de::Error::custom)
+}
@@ -1,8 +1,8 @@
-use crate::ParseError;
-use crate::eds_file::EDSFile;
+use crate::eds_file::{Address, EDSFile};
 use crate::raw_file::RawFile;
 use crate::structured_file::StructuredFile;
 use crate::tests::utils::*;
+use crate::{ParseError, load_file, load_string};
 
 #[test]
 fn test_phase_3_success() {
@@ -54,7 +54,7 @@ fn test_phase_3_success() {
     let mut tmp = make_tmp_file(raw);
     let rfile = RawFile::new_from_file(&mut tmp).expect("Failed to read back lines from file!");
     let sfile = StructuredFile::parse(rfile).expect("Failed to parse correct string!");
-    let _efile = EDSFile::parse(sfile).expect("Failed to parse eds file!");
+    let _efile = EDSFile::parse(&sfile).expect("Failed to parse eds file!");
 }
 
 #[test]
@@ -107,7 +107,7 @@ fn test_phase_3_invalid_granularity() {
     let mut tmp = make_tmp_file(raw);
     let rfile = RawFile::new_from_file(&mut tmp).expect("Failed to read back lines from file!");
     let sfile = StructuredFile::parse(rfile).expect("Failed to parse correct string!");
-    let efile = EDSFile::parse(sfile);
+    let efile = EDSFile::parse(&sfile);
     assert!(efile.is_err());
     let err = efile.unwrap_err();
     let ParseError::InvalidValueFormat { object, section } = err else {
@@ -166,7 +166,7 @@ fn test_phase_3_incomplete_file_info() {
     let mut tmp = make_tmp_file(raw);
     let rfile = RawFile::new_from_file(&mut tmp).expect("Failed to read back lines from file!");
     let sfile = StructuredFile::parse(rfile).expect("Failed to parse correct string!");
-    let efile = EDSFile::parse(sfile);
+    let efile = EDSFile::parse(&sfile);
     match efile {
         Err(ParseError::MissingRequiredObject { object, section }) => {
             assert_eq!(section, "FileInfo");
@@ -213,7 +213,7 @@ fn test_phase_3_no_file_info() {
     let mut tmp = make_tmp_file(raw);
     let rfile = RawFile::new_from_file(&mut tmp).expect("Failed to read back lines from file!");
     let sfile = StructuredFile::parse(rfile).expect("Failed to parse correct string!");
-    let efile = EDSFile::parse(sfile);
+    let efile = EDSFile::parse(&sfile);
     match efile {
         Err(ParseError::MissingRequiredSection { section }) => assert_eq!(section, "FileInfo"),
         _ => panic!(),
@@ -247,7 +247,7 @@ fn test_phase_3_no_device_info() {
     let mut tmp = make_tmp_file(raw);
     let rfile = RawFile::new_from_file(&mut tmp).expect("Failed to read back lines from file!");
     let sfile = StructuredFile::parse(rfile).expect("Failed to parse correct string!");
-    let efile = EDSFile::parse(sfile);
+    let efile = EDSFile::parse(&sfile);
     println!("Res: {:?}", efile);
     match efile {
         Err(ParseError::MissingRequiredSection { section }) => assert_eq!(section, "DeviceInfo"),
@@ -304,7 +304,7 @@ fn test_phase_3_partial_device_info() {
     let mut tmp = make_tmp_file(raw);
     let rfile = RawFile::new_from_file(&mut tmp).expect("Failed to read back lines from file!");
     let sfile = StructuredFile::parse(rfile).expect("Failed to parse correct string!");
-    let efile = EDSFile::parse(sfile);
+    let efile = EDSFile::parse(&sfile);
     match efile {
         Err(ParseError::MissingRequiredObject { object, section }) => {
             assert_eq!(section, "DeviceInfo");
@@ -313,3 +313,101 @@ fn test_phase_3_partial_device_info() {
         _ => panic!(),
     }
 }
+
+#[test]
+fn test_phase_3_dummy_usage() {
+    let efile = make_eds_file(&vec![
+        "[DummyUsage]",
+        "Dummy0001=0",
+        "Dummy0007=1",
+        "[MandatoryObjects]",
+        "SupportedObjects=0",
+        "[OptionalObjects]",
+        "SupportedObjects=0",
+        "[ManufacturerObjects]",
+        "SupportedObjects=0",
+    ])
+    .expect("Failed to parse eds file!");
+    assert!(!efile.dummy_usage.is_supported(0x1));
+    assert!(efile.dummy_usage.is_supported(0x7));
+    assert!(!efile.dummy_usage.is_supported(0x5));
+
+    let efile = make_eds_file(&vec![
+        "[DummyUsage]",
+        "DummyXYZ=1",
+        "[MandatoryObjects]",
+        "SupportedObjects=0",
+        "[OptionalObjects]",
+        "SupportedObjects=0",
+        "[ManufacturerObjects]",
+        "SupportedObjects=0",
+    ]);
+    match efile {
+        Err(ParseError::InvalidValueFormat { object, section }) => {
+            assert_eq!(object, "DummyXYZ");
+            assert_eq!(section, "DummyUsage");
+        }
+        _ => panic!(),
+    }
+}
+
+#[test]
+fn test_write_round_trip() {
+    let efile = load_file("res/micro-motor.eds").expect("Failed to parse eds file!");
+    let written = efile.to_eds_string();
+    let parsed = load_string(&written).expect("Failed to parse written file!");
+
+    assert_eq!(parsed.file_info.modification, efile.file_info.modification);
+    assert_eq!(
+        parsed.device_info.vendor_number,
+        efile.device_info.vendor_number
+    );
+    assert_eq!(parsed.get_objects().len(), efile.get_objects().len());
+    for obj in efile.get_objects() {
+        obj.check().expect("Failed to check parsed object!");
+        let index = obj.get_address().index;
+        assert_eq!(parsed.get_object_list(index), efile.get_object_list(index));
+        let other = parsed
+            .get_index_object(index)
+            .expect("Missing written object!");
+        assert_eq!(other.get_name(), obj.get_name());
+        assert_eq!(other.get_obj_type(), obj.get_obj_type());
+        assert_eq!(
+            other.get_entries().map(|e| e.len()),
+            obj.get_entries().map(|e| e.len())
+        );
+    }
+    //Writing is stable
+    assert_eq!(parsed.to_eds_string(), written);
+}
+
+#[test]
+fn test_default_value_key() {
+    //CiA 306 names the key DefaultValue, Default is not read
+    let efile = make_eds_file(&vec![
+        "[MandatoryObjects]",
+        "SupportedObjects=0",
+        "[OptionalObjects]",
+        "SupportedObjects=2",
+        "1=0x2000",
+        "2=0x2001",
+        "[ManufacturerObjects]",
+        "SupportedObjects=0",
+        "[2000]",
+        "ParameterName=Speed",
+        "ObjectType=0x7",
+        "DataType=0x0006",
+        "AccessType=rw",
+        "DefaultValue=0x10",
+        "[2001]",
+        "ParameterName=Torque",
+        "ObjectType=0x7",
+        "DataType=0x0006",
+        "AccessType=rw",
+        "Default=0x10",
+    ])
+    .expect("Failed to parse eds file!");
+    assert_eq!(efile.get_default_u64(&Address::new(0x2000, 0), 0), Some(16));
+    assert_eq!(efile.get_default_u64(&Address::new(0x2001, 0), 0), None);
+    assert!(efile.to_eds_string().contains("DefaultValue=0x0010\n"));
+}
@@ -1,4 +1,7 @@
-use crate::{eds_file::EDSVersion, structured_file::StructuredFileObject};
+use crate::eds_file::{EDSFile, EDSObject, EDSVersion};
+use crate::structured_file::StructuredFileObject;
+use crate::tests::utils::{EDS_HEADER, make_string};
+use crate::{ParseError, load_string};
 
 #[test]
 pub fn test_parse_3() {
@@ -39,3 +42,86 @@ pub fn test_parse_invalid() {
     let v = EDSVersion::parse(&obj);
     assert!(v.is_err());
 }
+
+const COMPACT_OBJECTS: [&str; 13] = [
+    "[MandatoryObjects]",
+    "SupportedObjects=0",
+    "[OptionalObjects]",
+    "SupportedObjects=0",
+    "[ManufacturerObjects]",
+    "SupportedObjects=1",
+    "1=0x2000",
+    "[2000]",
+    "ParameterName=Gains",
+    "ObjectType=0x8",
+    "DataType=0x0007",
+    "AccessType=rw",
+    "CompactSubObj=1",
+];
+
+fn make_versioned_file(version: &str, lines: &[&str]) -> Result<EDSFile, ParseError> {
+    let version_line = format!("EDSVersion={}", version);
+    let mut data: Vec<&str> = EDS_HEADER
+        .iter()
+        .filter(|l| !l.starts_with("DynamicChannelsSupported") || version == "4.0")
+        .map(|l| {
+            if l.starts_with("EDSVersion") {
+                version_line.as_str()
+            } else {
+                l
+            }
+        })
+        .collect();
+    data.extend(lines);
+    load_string(&make_string(&data))
+}
+
+#[test]
+pub fn test_version_rules() {
+    let file = make_versioned_file("4.0", &COMPACT_OBJECTS).expect("Failed to parse v4.0 file!");
+    assert!(matches!(
+        file.get_index_object(0x2000),
+        Some(EDSObject::CompactEDSArray { .. })
+    ));
+
+    //DynamicChannelsSupported is not required by 3.0
+    let empty = [&COMPACT_OBJECTS[..5], &["SupportedObjects=0"]].concat();
+    let file = make_versioned_file("3.0", &empty).expect("Failed to parse v3.0 file!");
+    assert_eq!(file.file_info.eds_version, EDSVersion::V3_0);
+    assert!(!file.device_info.dynamic_channel_supported);
+    assert!(!file.to_eds_string().contains("DynamicChannelsSupported"));
+
+    //Keys of 4.0 that are zero describe a 3.0 object
+    let mut unused = empty.clone();
+    unused.extend([
+        "[1000]",
+        "ParameterName=Device type",
+        "ObjectType=0x7",
+        "DataType=0x0007",
+        "AccessType=ro",
+        "CompactSubObj=0",
+        "ObjFlags=0x0",
+    ]);
+    unused[1] = "SupportedObjects=1";
+    unused.insert(2, "1=0x1000");
+    let file = make_versioned_file("3.0", &unused).expect("Failed to parse v3.0 file!");
+    assert!(file.get_index_object(0x1000).is_some());
+    let mut data = EDS_HEADER.to_vec();
+    data[4] = "EDSVersion=3.0";
+    data.extend(&empty);
+    let file = load_string(&make_string(&data)).expect("Failed to parse v3.0 file!");
+    assert!(!file.device_info.dynamic_channel_supported);
+
+    let result = make_versioned_file("3.0", &COMPACT_OBJECTS);
+    assert!(matches!(
+        result,
+        Err(ParseError::UnsupportedInVersion { key: Some(key), version: EDSVersion::V3_0, .. })
+            if key == "CompactSubObj"
+    ));
+    let mut links = empty.clone();
+    links.extend(["[2000ObjectLinks]", "ObjectLinks=1", "1=0x2001"]);
+    assert!(matches!(
+        make_versioned_file("3.0", &links),
+        Err(ParseError::UnsupportedInVersion { key: None, .. })
+    ));
+}
@@ -4,4 +4,6 @@ mod eds_date;
 mod eds_file;
 mod eds_time;
 mod eds_version;
+#[cfg(feature = "serde")]
+mod serialization;
 mod util_tests;
@@ -0,0 +1,94 @@
+use serde_json::json;
+
+use crate::eds_diff::diff;
+use crate::eds_file::{Address, EDSFile, ObjectList};
+use crate::load_file;
+
+fn list_key(list: ObjectList) -> &'static str {
+    match list {
+        ObjectList::Mandatory => "mandatory_object",
+        ObjectList::Optional => "optional_object",
+        ObjectList::Manufacturer => "manufacturer_object",
+    }
+}
+
+#[test]
+fn test_json_round_trip() {
+    let mut file = load_file("res/micro-motor.eds").expect("Failed to parse eds file!");
+    file.set_default(&Address::new(0x2011, 0), Some(Box::new(0.1f32)))
+        .expect("Failed to set default!");
+    let data = serde_json::to_string(&file).expect("Failed to serialize file!");
+    let parsed: EDSFile = serde_json::from_str(&data).expect("Failed to deserialize file!");
+    assert!(diff(&file, &parsed).is_empty());
+    assert_eq!(parsed.to_eds_string(), file.to_eds_string());
+    let value = parsed
+        .get_object(&Address::new(0x2011, 0))
+        .and_then(|o| o.get_default())
+        .and_then(|v| v.as_any().downcast_ref::<f32>());
+    assert_eq!(value, Some(&0.1f32));
+}
+
+#[test]
+fn test_json_non_finite() {
+    let mut file = load_file("res/micro-motor.eds").expect("Failed to parse eds file!");
+    let addr = Address::new(0x2011, 0);
+    for value in [f32::NAN, f32::INFINITY, f32::NEG_INFINITY] {
+        file.set_default(&addr, Some(Box::new(value)))
+            .expect("Failed to set default!");
+        let data = serde_json::to_string(&file).expect("Failed to serialize file!");
+        let parsed: EDSFile = serde_json::from_str(&data).expect("Failed to deserialize file!");
+        let parsed_value = parsed
+            .get_object(&addr)
+            .and_then(|o| o.get_default())
+            .and_then(|v| v.as_any().downcast_ref::<f32>())
+            .expect("Missing default!");
+        assert_eq!(parsed_value.to_string(), value.to_string());
+    }
+
+    let value = serde_json::to_value(&file).expect("Failed to serialize file!");
+    let list = list_key(
+        file.get_object_list(0x2011)
+            .expect("Failed to find object!"),
+    );
+    let default = &value[list]["0x2011sub0"]["default"];
+    assert_eq!(*default, json!({ "type": "Real32", "value": "-inf" }));
+    let mut invalid = value.clone();
+    invalid[list]["0x2011sub0"]["default"]["value"] = json!("fast");
+    assert!(serde_json::from_value::<EDSFile>(invalid).is_err());
+}
+
+#[test]
+fn test_json_format() {
+    let file = load_file("res/micro-motor.eds").expect("Failed to parse eds file!");
+    let value = serde_json::to_value(&file).expect("Failed to serialize file!");
+    assert_eq!(
+        value["file_info"]["creation"],
+        json!("2022-10-16T12:01:00Z")
+    );
+    assert_eq!(value["file_info"]["eds_version"], json!("4.0"));
+
+    let object = |index: u16| {
+        let list = file.get_object_list(index).expect("Failed to find object!");
+        &value[list_key(list)][format!("0x{:04X}sub0", index)]
+    };
+    let identity = object(0x1018);
+    assert_eq!(identity["kind"], json!("EDSArray"));
+    assert_eq!(identity["address"], json!("0x1018sub0"));
+    assert_eq!(identity["entries"][2]["address"], json!("0x1018sub2"));
+    assert_eq!(
+        identity["entries"][1]["default"],
+        json!({ "type": "UInt32", "value": 0x1A3 })
+    );
+    assert_eq!(
+        object(0x1014)["default"],
+        json!({ "type": "NodeIdOffset", "value": 0x80 })
+    );
+    let pwm = object(0x2002);
+    assert_eq!(pwm["access_mode"], json!("rww"));
+    assert_eq!(pwm["data_type"], json!("Int16"));
+    assert_eq!(pwm["low_limit"], json!(null));
+
+    let mut invalid = value.clone();
+    invalid["mandatory_object"]["0x1000sub0"]["address"] = json!("1000");
+    assert!(serde_json::from_value::<EDSFile>(invalid).is_err());
+}
@@ -1,4 +1,4 @@
-use crate::{ParseError, eds_file::utils::*, structured_file::StructuredFileObject};
+use crate::{NodeIdOffset, ParseError, eds_file::utils::*, structured_file::StructuredFileObject};
 
 #[test]
 fn test_hex_parse() {
@@ -225,3 +225,68 @@ fn test_parse_date_time_combo() {
     assert!(!parse_date_time_combo(&obj, "test_2_date", "test_2_time").is_ok());
     assert!(!parse_date_time_combo(&obj, "test_3_date", "test_3_time").is_ok());
 }
+
+#[test]
+fn test_parse_node_id_offset() {
+    let mut obj = StructuredFileObject::new("test_sec".to_string());
+    obj.get_values_mut()
+        .insert("test_plain".to_string(), "0x180".to_string());
+    obj.get_values_mut()
+        .insert("test_prefix".to_string(), "$NODEID+0x180".to_string());
+    obj.get_values_mut().insert(
+        "test_suffix".to_string(),
+        "0x80000380 + $nodeid".to_string(),
+    );
+    obj.get_values_mut()
+        .insert("test_only".to_string(), "$NODEID".to_string());
+    obj.get_values_mut()
+        .insert("test_inv".to_string(), "$NODEID+0x80+0x100".to_string());
+    assert_eq!(parse_node_id_offset(&obj, "test_plain").ok(), Some(None));
+    assert_eq!(
+        parse_node_id_offset(&obj, "test_prefix").ok(),
+        Some(Some(NodeIdOffset { offset: 0x180 }))
+    );
+    assert_eq!(
+        parse_node_id_offset(&obj, "test_suffix").ok(),
+        Some(Some(NodeIdOffset { offset: 0x80000380 }))
+    );
+    assert_eq!(
+        parse_node_id_offset(&obj, "test_only").ok(),
+        Some(Some(NodeIdOffset { offset: 0 }))
+    );
+    assert!(parse_node_id_offset(&obj, "test_inv").is_err());
+    assert_eq!(NodeIdOffset { offset: 0x180 }.resolve(5), 0x185);
+}
+
+#[test]
+fn test_parse_int() {
+    let mut obj = StructuredFileObject::new("test_sec".to_string());
+    obj.get_values_mut()
+        .insert("test_n2".to_string(), "-2".to_string());
+    obj.get_values_mut()
+        .insert("test_hex".to_string(), "0xFE".to_string());
+    obj.get_values_mut()
+        .insert("test_200".to_string(), "200".to_string());
+    obj.get_values_mut()
+        .insert("test_hex_large".to_string(), "0x1FE".to_string());
+    assert_eq!(parse_required_int::<i8>(&obj, "test_n2").ok(), Some(-2));
+    assert_eq!(parse_required_int::<i8>(&obj, "test_hex").ok(), Some(-2));
+    assert_eq!(parse_required_int::<i16>(&obj, "test_hex").ok(), Some(254));
+    assert_eq!(parse_required_int::<i8>(&obj, "test_200").ok(), None);
+    assert_eq!(parse_required_int::<i16>(&obj, "test_200").ok(), Some(200));
+    assert_eq!(parse_required_int::<i8>(&obj, "test_hex_large").ok(), None);
+}
+
+#[test]
+fn test_parse_hex_data() {
+    let mut obj = StructuredFileObject::new("test_sec".to_string());
+    obj.get_values_mut()
+        .insert("test_bytes".to_string(), "01FFa0".to_string());
+    obj.get_values_mut()
+        .insert("test_odd".to_string(), "01F".to_string());
+    assert_eq!(
+        parse_required_hex_data(&obj, "test_bytes").ok(),
+        Some(vec![0x01, 0xFF, 0xA0])
+    );
+    assert!(parse_required_hex_data(&obj, "test_odd").is_err());
+}
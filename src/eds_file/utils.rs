@@ -1,8 +1,9 @@
 use std::num::ParseIntError;
 use std::str::FromStr;
 
-use chrono::{DateTime, TimeZone, Utc};
+use chrono::{DateTime, TimeZone, Timelike, Utc};
 
+use super::data_type::NodeIdOffset;
 use super::eds_date::EDSDate;
 use super::eds_time::EDSTime;
 use crate::ParseError;
@@ -46,7 +47,7 @@ pub fn parse_required_hex_data(
             object: name.to_string(),
             section: obj.get_name().clone(),
         })?;
-    if !val.is_ascii() {
+    if !val.is_ascii() || val.len() % 2 != 0 {
         return Err(ParseError::ParseHexError {
             object: name.to_string(),
             section: obj.get_name().clone(),
@@ -55,7 +56,8 @@ pub fn parse_required_hex_data(
     }
 
     let bytes: Result<Vec<u8>, _> = (0..val.len())
-        .map(|i| u8::from_str_radix(&val[i..i + 1], 16))
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&val[i..i + 2], 16))
         .collect();
     bytes.or_else(|e| {
         Err(ParseError::ParseIntError {
@@ -107,8 +109,43 @@ pub fn parse_required_uint<T: FromStr<Err = ParseIntError> + TryFrom<u64>>(
             object: name.to_string(),
             section: obj.get_name().clone(),
         })?;
+    parse_uint_value(obj, name, val)
+}
+
+//Signed values are either given as decimals or as the raw bits in hex or octal
+pub fn parse_required_int<T: FromStr<Err = ParseIntError> + TryFrom<i64>>(
+    obj: &StructuredFileObject,
+    name: &str,
+) -> Result<T, ParseError> {
+    let val = parse_required_str(obj, name)?;
+    let is_raw =
+        val.starts_with("0x") || val.starts_with("0X") || (val.starts_with("0") && val != "0");
+    if !is_raw {
+        return val.parse::<T>().map_err(|e| ParseError::ParseIntError {
+            section: obj.get_name().to_string(),
+            object: name.to_string(),
+            err: e,
+        });
+    }
+    let bits = (std::mem::size_of::<T>() * 8) as u32;
+    let raw = parse_uint_value::<u64>(obj, name, val)?;
+    let invalid = ParseError::InvalidValueFormat {
+        object: name.to_string(),
+        section: obj.get_name().clone(),
+    };
+    if bits < 64 && raw >> bits != 0 {
+        return Err(invalid);
+    }
+    let signed = ((raw << (64 - bits)) as i64) >> (64 - bits);
+    T::try_from(signed).map_err(|_| invalid)
+}
 
-    if val.starts_with("0x") {
+fn parse_uint_value<T: FromStr<Err = ParseIntError> + TryFrom<u64>>(
+    obj: &StructuredFileObject,
+    name: &str,
+    val: &str,
+) -> Result<T, ParseError> {
+    if val.starts_with("0x") || val.starts_with("0X") {
         parse_hex_str::<T, 4>(&val[2..]).ok_or(ParseError::ParseHexError {
             object: name.to_string(),
             section: obj.get_name().clone(),
@@ -121,16 +158,41 @@ pub fn parse_required_uint<T: FromStr<Err = ParseIntError> + TryFrom<u64>>(
             value: val[1..].to_string(),
         })
     } else {
-        val.parse::<T>().or_else(|e| {
-            Err(ParseError::ParseIntError {
-                section: obj.get_name().to_string(),
-                object: name.to_string(),
-                err: e,
-            })
+        val.parse::<T>().map_err(|e| ParseError::ParseIntError {
+            section: obj.get_name().to_string(),
+            object: name.to_string(),
+            err: e,
         })
     }
 }
 
+pub fn parse_node_id_offset(
+    obj: &StructuredFileObject,
+    name: &str,
+) -> Result<Option<NodeIdOffset>, ParseError> {
+    let val = parse_required_str(obj, name)?;
+    let upper = val.to_uppercase();
+    if !upper.contains("$NODEID") {
+        return Ok(None);
+    }
+    let mut offset = None;
+    for term in upper.split('+').map(|t| t.trim()) {
+        if term == "$NODEID" {
+            continue;
+        }
+        if offset.is_some() || term.is_empty() {
+            return Err(ParseError::InvalidValueFormat {
+                object: name.to_string(),
+                section: obj.get_name().clone(),
+            });
+        }
+        offset = Some(parse_uint_value::<u64>(obj, name, term)?);
+    }
+    Ok(Some(NodeIdOffset {
+        offset: offset.unwrap_or(0),
+    }))
+}
+
 pub fn parse_required_bool(obj: &StructuredFileObject, name: &str) -> Result<bool, ParseError> {
     obj.get_value(name)
         .ok_or(ParseError::MissingRequiredObject {
@@ -183,3 +245,11 @@ pub fn parse_date_time_combo(
         }),
     }
 }
+
+//The current time at the minute resolution of EDS timestamps
+pub fn eds_now() -> DateTime<Utc> {
+    let now = Utc::now();
+    now.with_second(0)
+        .and_then(|t| t.with_nanosecond(0))
+        .unwrap_or(now)
+}
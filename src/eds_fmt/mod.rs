@@ -0,0 +1,163 @@
+#[cfg(test)]
+mod tests;
+
+use std::collections::HashMap;
+
+use crate::ParseError;
+use crate::eds_file::{
+    Address, EDSFile, ObjectList, utils::parse_required_uint, write_info_sections,
+    write_object_sections, write_section,
+};
+use crate::raw_file::RawFile;
+use crate::structured_file::{StructuredFile, StructuredFileObject};
+
+const INFO_SECTIONS: [&str; 4] = ["fileinfo", "deviceinfo", "dummyusage", "comments"];
+
+//Object sections are written from the parsed objects, sections of objects
+//that are not listed are kept like unknown sections
+fn is_object_section(file: &EDSFile, name: &str) -> bool {
+    let (index, subindex) = match name.split_once("sub") {
+        Some((index, subindex)) => (index, Some(subindex)),
+        None => (name, None),
+    };
+    let Ok(index) = u16::from_str_radix(index, 16) else {
+        return false;
+    };
+    match subindex {
+        None => file.get_index_object(index).is_some(),
+        Some(subindex) => u8::from_str_radix(subindex, 16)
+            .is_ok_and(|subindex| file.get_object(&Address::new(index, subindex)).is_some()),
+    }
+}
+
+fn is_known_section(file: &EDSFile, name: &str) -> bool {
+    let lower = name.to_lowercase();
+    INFO_SECTIONS.contains(&lower.as_str())
+        || ObjectList::ALL
+            .iter()
+            .any(|l| l.get_section_name().eq_ignore_ascii_case(&lower))
+        || is_object_section(file, &lower)
+}
+
+//Empty lines can not be written and are left out
+fn comment_keys(obj: &StructuredFileObject) -> Result<Vec<(String, String)>, ParseError> {
+    let count: u16 = parse_required_uint(obj, "Lines")?;
+    let lines: Vec<&String> = (1..=count)
+        .filter_map(|i| obj.get_value(&format!("Line{}", i)))
+        .collect();
+    let mut keys = vec![("Lines".to_string(), lines.len().to_string())];
+    for (i, line) in lines.into_iter().enumerate() {
+        keys.push((format!("Line{}", i + 1), line.clone()));
+    }
+    Ok(keys)
+}
+
+//Sections the file model does not hold, like object links or dynamic
+//channels, are kept with named keys before numbered keys
+fn other_keys(obj: &StructuredFileObject) -> Vec<(String, String)> {
+    let mut keys: Vec<(String, String)> = obj
+        .get_values()
+        .iter()
+        .map(|(k, v)| (k.clone(), v.clone()))
+        .collect();
+    keys.sort_by_key(|(k, _)| (k.parse::<u32>().ok(), k.to_lowercase()));
+    keys
+}
+
+//Keys the file model does not hold are kept after the keys of their section,
+//the lists and comments are written completely from the file
+fn extra_keys(sfile: &StructuredFile, section: &str, written: &[&str]) -> Vec<(String, String)> {
+    let regenerated = section.eq_ignore_ascii_case("comments")
+        || ObjectList::ALL
+            .iter()
+            .any(|l| l.get_section_name().eq_ignore_ascii_case(section));
+    let Some(obj) = sfile.get_object(section).filter(|_| !regenerated) else {
+        return Vec::new();
+    };
+    other_keys(obj)
+        .into_iter()
+        .filter(|(k, _)| !written.iter().any(|w| w.eq_ignore_ascii_case(k)))
+        .collect()
+}
+
+fn format_sfile(sfile: &StructuredFile, file: &EDSFile) -> Result<String, ParseError> {
+    let mut written = String::new();
+    write_info_sections(&mut written, file);
+    if let Some(comments) = sfile.get_object("Comments") {
+        write_section(&mut written, "Comments", &comment_keys(comments)?);
+    }
+    write_object_sections(&mut written, file, &HashMap::new());
+
+    let mut out = String::new();
+    let mut section = "";
+    let mut keys = Vec::new();
+    for line in written.lines() {
+        if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            section = name;
+            keys.clear();
+        } else if line.is_empty() {
+            for (key, value) in extra_keys(sfile, section, &keys) {
+                out.push_str(&format!("{}={}\n", key, value));
+            }
+        } else if let Some((key, _)) = line.split_once('=') {
+            keys.push(key);
+        }
+        out.push_str(line);
+        out.push('\n');
+    }
+
+    let mut others: Vec<&StructuredFileObject> = sfile
+        .get_objects()
+        .into_iter()
+        .filter(|o| !is_known_section(file, o.get_name()))
+        .collect();
+    others.sort_by_key(|o| o.get_name().to_lowercase());
+    for obj in others {
+        write_section(&mut out, obj.get_name(), &other_keys(obj));
+    }
+    Ok(out)
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct FormatCheck {
+    pub formatted: String,
+    //Line number of the first line that differs from the formatted file
+    pub first_difference: Option<usize>,
+}
+
+impl FormatCheck {
+    pub fn is_formatted(&self) -> bool {
+        self.first_difference.is_none()
+    }
+}
+
+//Rewrites an EDS in the canonical layout: the info sections and comments,
+//the object lists and the objects by ascending address. Keys are written in
+//the order of CiA 306, numbers in hex with the width of their data type and
+//dates and times as MM-DD-YYYY and hh:mmAM.
+pub fn format_eds_string(data: &str) -> Result<String, ParseError> {
+    let sfile = StructuredFile::parse(RawFile::new_from_string(data))?;
+    let file = EDSFile::parse(&sfile)?;
+    format_sfile(&sfile, &file)
+}
+
+pub fn check_eds_format(data: &str) -> Result<FormatCheck, ParseError> {
+    let formatted = format_eds_string(data)?;
+    let mut lines = data.split('\n');
+    let mut formatted_lines = formatted.split('\n');
+    let mut first_difference = None;
+    for i in 1.. {
+        match (lines.next(), formatted_lines.next()) {
+            (None, None) => break,
+            (a, b) if a == b => continue,
+            _ => {
+                first_difference = Some(i);
+                break;
+            }
+        }
+    }
+    Ok(FormatCheck {
+        formatted,
+        first_difference,
+    })
+}
@@ -0,0 +1,156 @@
+use crate::eds_diff::diff;
+use crate::eds_file::Address;
+use crate::eds_fmt::{check_eds_format, format_eds_string};
+use crate::tests::utils::*;
+use crate::{load_file, load_string};
+
+const UNFORMATTED: [&str; 37] = [
+    "[2000]",
+    "ParameterName=Speed",
+    "PDOMapping=1",
+    "DefaultValue=0x1f",
+    "AccessType=rww",
+    "DataType=0x0006",
+    "ObjectType=7",
+    "Denotation=Left wheel",
+    "[ObjectLinks]",
+    "2=0x2000",
+    "ObjectLinks=2",
+    "1=0x1000",
+    "[1000]",
+    "ParameterName=Device type",
+    "ObjectType=0x7",
+    "DataType=0x0007",
+    "AccessType=ro",
+    "DefaultValue=0",
+    "PDOMapping=0",
+    "[ManufacturerObjects]",
+    "SupportedObjects=1",
+    "1=0x2000",
+    "[OptionalObjects]",
+    "SupportedObjects=0",
+    "[Comments]",
+    "Lines=3",
+    "Line1=Speed of both wheels",
+    "Line3=in rpm",
+    "[MandatoryObjects]",
+    "SupportedObjects=2",
+    "1=0x1000",
+    "2=0x1001",
+    "[1001]",
+    "ParameterName=Error register",
+    "ObjectType=0x7",
+    "DataType=0x0005",
+    "AccessType=ro",
+];
+
+#[test]
+fn test_format() {
+    let data = std::fs::read_to_string("res/micro-motor.eds").expect("Failed to read eds file!");
+    let check = check_eds_format(&data).expect("Failed to check eds file!");
+    assert!(!check.is_formatted());
+    assert_eq!(check.first_difference, Some(2));
+
+    //Formatting keeps the file and is stable
+    let formatted = check.formatted;
+    let original = load_file("res/micro-motor.eds").expect("Failed to parse eds file!");
+    let reparsed = load_string(&formatted).expect("Failed to parse formatted file!");
+    assert!(diff(&original, &reparsed).is_empty());
+    let check = check_eds_format(&formatted).expect("Failed to check formatted file!");
+    assert!(check.is_formatted());
+    assert_eq!(check.formatted, formatted);
+
+    let lines: Vec<&str> = formatted.lines().collect();
+    let position = |line: &str| {
+        lines
+            .iter()
+            .position(|l| *l == line)
+            .unwrap_or_else(|| panic!("Missing line {}!", line))
+    };
+    assert!(position("[FileInfo]") < position("[DeviceInfo]"));
+    assert!(position("[DeviceInfo]") < position("[DummyUsage]"));
+    assert!(position("[DummyUsage]") < position("[Comments]"));
+    assert!(position("[Comments]") < position("[MandatoryObjects]"));
+    assert!(position("[ManufacturerObjects]") < position("[1000]"));
+    assert!(position("[1018]") < position("[1018sub0]"));
+    assert!(position("[1018sub4]") < position("[2001]"));
+    assert_eq!(lines[1], "FileName=velocity-control-test.eds");
+    position("VendorNumber=0xDEADBEEF");
+    position("EDSVersion=4.0");
+    position("CreationTime=12:01PM");
+    position("CreationDate=10-16-2022");
+    //Keys the file model does not hold are kept
+    assert_eq!(lines[position("LSS_Supported=0") + 1], "CompactPDO=0");
+}
+
+#[test]
+fn test_format_sections() {
+    let mut lines = EDS_HEADER.to_vec();
+    lines.extend(UNFORMATTED);
+    let formatted = format_eds_string(&make_string(&lines)).expect("Failed to format eds file!");
+    let file = load_string(&formatted).expect("Failed to parse formatted file!");
+    assert!(file.get_object(&Address::new(0x2000, 0)).is_some());
+
+    let sections: Vec<&str> = formatted.lines().filter(|l| l.starts_with('[')).collect();
+    assert_eq!(
+        sections,
+        [
+            "[FileInfo]",
+            "[DeviceInfo]",
+            "[Comments]",
+            "[MandatoryObjects]",
+            "[OptionalObjects]",
+            "[ManufacturerObjects]",
+            "[1000]",
+            "[1001]",
+            "[2000]",
+            "[ObjectLinks]",
+        ]
+        .as_slice()
+    );
+    assert!(
+        formatted.contains("[Comments]\nLines=2\nLine1=Speed of both wheels\nLine2=in rpm\n\n")
+    );
+    assert!(formatted.contains(concat!(
+        "[2000]\n",
+        "ParameterName=Speed\n",
+        "ObjectType=0x7\n",
+        "DataType=0x0006\n",
+        "AccessType=rww\n",
+        "DefaultValue=0x001F\n",
+        "PDOMapping=1\n",
+        "Denotation=Left wheel\n\n",
+    )));
+    assert!(formatted.ends_with("[ObjectLinks]\nObjectLinks=2\n1=0x1000\n2=0x2000\n\n"));
+}
+
+#[test]
+fn test_format_unlisted_objects() {
+    //Sections of objects that no list references are kept, not dropped
+    let mut lines = EDS_HEADER.to_vec();
+    lines.extend(&UNFORMATTED[..UNFORMATTED.len() - 5]);
+    lines.extend([
+        "[2001]",
+        "ParameterName=Unlisted",
+        "ObjectType=0x9",
+        "SubNumber=1",
+        "[2001sub0]",
+        "ParameterName=Number of entries",
+        "ObjectType=0x7",
+        "DataType=0x0005",
+        "AccessType=ro",
+        "DefaultValue=0",
+    ]);
+    lines.extend(&UNFORMATTED[UNFORMATTED.len() - 5..]);
+    let formatted = format_eds_string(&make_string(&lines)).expect("Failed to format eds file!");
+    let sections: Vec<&str> = formatted.lines().filter(|l| l.starts_with('[')).collect();
+    assert_eq!(
+        &sections[sections.len() - 3..],
+        ["[2001]", "[2001sub0]", "[ObjectLinks]"].as_slice()
+    );
+    assert!(formatted.contains("[2001]\nObjectType=0x9\nParameterName=Unlisted\nSubNumber=1\n\n"));
+    assert!(formatted.contains("[2001sub0]\nAccessType=ro\nDataType=0x0005\n"));
+
+    let check = check_eds_format(&formatted).expect("Failed to check formatted file!");
+    assert!(check.is_formatted());
+}
@@ -0,0 +1,79 @@
+use std::fmt::Display;
+
+use crate::eds_file::Address;
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum MergeTarget {
+    FileInfo,
+    DeviceInfo,
+    DummyUsage,
+    Object {
+        address: Address,
+        is_sub_object: bool,
+    },
+}
+
+impl Display for MergeTarget {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self {
+            MergeTarget::FileInfo => write!(f, "FileInfo"),
+            MergeTarget::DeviceInfo => write!(f, "DeviceInfo"),
+            MergeTarget::DummyUsage => write!(f, "DummyUsage"),
+            MergeTarget::Object {
+                address,
+                is_sub_object: true,
+            } => write!(f, "0x{:04X}.{}", address.index, address.subindex),
+            MergeTarget::Object { address, .. } => write!(f, "0x{:04X}", address.index),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Resolution {
+    Base,
+    Ours,
+    Theirs,
+}
+
+fn format_value(value: &Option<String>) -> &str {
+    value.as_deref().unwrap_or("-")
+}
+
+//Both sides changed the same key in different ways. Without a key the
+//presence of a whole object is in conflict, e.g. one side removed it while
+//the other one changed it, and the values are the parameter names.
+//Conflicts on the ObjectType are resolved by taking the whole object.
+#[derive(Debug, PartialEq, Clone)]
+pub struct MergeConflict {
+    pub target: MergeTarget,
+    pub key: Option<String>,
+    pub base: Option<String>,
+    pub ours: Option<String>,
+    pub theirs: Option<String>,
+}
+
+impl MergeConflict {
+    pub fn get_value(&self, resolution: Resolution) -> Option<&String> {
+        match resolution {
+            Resolution::Base => self.base.as_ref(),
+            Resolution::Ours => self.ours.as_ref(),
+            Resolution::Theirs => self.theirs.as_ref(),
+        }
+    }
+}
+
+impl Display for MergeConflict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.target)?;
+        if let Some(key) = &self.key {
+            write!(f, ".{}", key)?;
+        }
+        write!(
+            f,
+            ": base {}, ours {}, theirs {}",
+            format_value(&self.base),
+            format_value(&self.ours),
+            format_value(&self.theirs)
+        )
+    }
+}
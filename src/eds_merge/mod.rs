@@ -0,0 +1,414 @@
+mod merge_conflict;
+
+#[cfg(test)]
+mod tests;
+
+pub use merge_conflict::*;
+
+use std::collections::BTreeMap;
+
+use crate::eds_builder::update_highest_subindex;
+use crate::eds_file::{
+    Address, EDSFile, ObjectList, device_info_keys, dummy_usage_keys, file_info_keys, object_keys,
+    section_name, utils::eds_now, write_section,
+};
+use crate::{EditError, load_string};
+
+type Keys = Vec<(String, String)>;
+
+//Changed on both sides by every edit, the merge sets them itself
+const REVISION_KEYS: [&str; 3] = ["FileRevision", "ModificationTime", "ModificationDate"];
+//Values are written in the format of the data type and can only be taken
+//together with it
+const VALUE_KEYS: [&str; 4] = ["DataType", "LowLimit", "HighLimit", "DefaultValue"];
+const STRUCTURE_KEYS: [&str; 2] = ["ObjectType", "CompactSubObj"];
+
+fn to_keys(keys: Vec<(&'static str, String)>) -> Keys {
+    keys.into_iter().map(|(k, v)| (k.to_string(), v)).collect()
+}
+
+fn get_key<'a>(keys: Option<&'a Keys>, key: &str) -> Option<&'a String> {
+    keys?.iter().find(|(k, _)| k == key).map(|(_, v)| v)
+}
+
+//An object as the sections it is written to, the list of the object is kept
+//as an extra key and the SubNumber is counted when writing
+#[derive(Debug, PartialEq, Clone)]
+struct ObjectSections {
+    keys: Keys,
+    subs: BTreeMap<u8, Keys>,
+}
+
+impl ObjectSections {
+    fn get_key(&self, key: &str) -> Option<String> {
+        get_key(Some(&self.keys), key).cloned()
+    }
+}
+
+fn file_objects(file: &EDSFile) -> BTreeMap<u16, ObjectSections> {
+    file.get_objects()
+        .into_iter()
+        .map(|obj| {
+            let index = obj.get_address().index;
+            let mut keys = to_keys(object_keys(obj));
+            keys.retain(|(k, _)| k != "SubNumber");
+            if let Some(list) = file.get_object_list(index) {
+                keys.push(("ObjectList".to_string(), list.to_string()));
+            }
+            let subs = obj
+                .get_entries()
+                .map(|entries| {
+                    entries
+                        .iter()
+                        .map(|e| (e.get_address().subindex, to_keys(object_keys(e))))
+                        .collect()
+                })
+                .unwrap_or_default();
+            (index, ObjectSections { keys, subs })
+        })
+        .collect()
+}
+
+struct MergePass<'a> {
+    resolutions: &'a [(MergeTarget, Option<String>, Resolution)],
+    conflicts: Vec<MergeConflict>,
+}
+
+impl MergePass<'_> {
+    //Three-way merge of a single value, a conflict keeps our value until it
+    //is resolved
+    fn pick<T: PartialEq + Clone>(
+        &mut self,
+        target: MergeTarget,
+        key: Option<&str>,
+        [base, ours, theirs]: [Option<&T>; 3],
+        show: impl Fn(&T) -> Option<String>,
+    ) -> Option<T> {
+        if ours == theirs || theirs == base {
+            return ours.cloned();
+        }
+        if ours == base {
+            return theirs.cloned();
+        }
+        let resolution = self
+            .resolutions
+            .iter()
+            .find(|(t, k, _)| *t == target && k.as_deref() == key)
+            .map(|(_, _, r)| *r);
+        match resolution {
+            Some(Resolution::Base) => base.cloned(),
+            Some(Resolution::Ours) => ours.cloned(),
+            Some(Resolution::Theirs) => theirs.cloned(),
+            None => {
+                self.conflicts.push(MergeConflict {
+                    target,
+                    key: key.map(|k| k.to_string()),
+                    base: base.and_then(&show),
+                    ours: ours.and_then(&show),
+                    theirs: theirs.and_then(&show),
+                });
+                ours.cloned()
+            }
+        }
+    }
+
+    //Merges a section key by key, returns true if one of the automatic keys
+    //was changed on both sides
+    fn merge_keys(
+        &mut self,
+        target: MergeTarget,
+        sections: [Option<&Keys>; 3],
+        automatic: &[&str],
+    ) -> (Keys, bool) {
+        let mut names: Vec<&String> = Vec::new();
+        for (key, _) in [sections[1], sections[2], sections[0]]
+            .into_iter()
+            .flatten()
+            .flatten()
+        {
+            if !names.contains(&key) {
+                names.push(key);
+            }
+        }
+        //A changed data type takes the values with it
+        let grouped = get_key(sections[1], "DataType") != get_key(sections[2], "DataType");
+        let group = if grouped {
+            let values = sections.map(|s| s.map(|s| VALUE_KEYS.map(|k| get_key(Some(s), k))));
+            self.pick(
+                target,
+                Some("DataType"),
+                values.each_ref().map(|v| v.as_ref()),
+                |v| v[0].cloned(),
+            )
+        } else {
+            None
+        };
+
+        let mut out = Keys::new();
+        let mut automatic_changed = false;
+        for name in names {
+            let value = if let Some(i) = VALUE_KEYS.iter().position(|k| grouped && k == name) {
+                group.and_then(|g| g[i]).cloned()
+            } else {
+                let [base, ours, theirs] = sections.map(|s| get_key(s, name));
+                if automatic.contains(&name.as_str())
+                    && ours != theirs
+                    && base != ours
+                    && base != theirs
+                {
+                    automatic_changed = true;
+                    ours.cloned()
+                } else {
+                    self.pick(target, Some(name), [base, ours, theirs], |v| {
+                        Some(v.to_string())
+                    })
+                }
+            };
+            if let Some(value) = value {
+                out.push((name.clone(), value));
+            }
+        }
+        (out, automatic_changed)
+    }
+
+    fn merge_object(
+        &mut self,
+        index: u16,
+        objects: [Option<&ObjectSections>; 3],
+        recompute: &mut Vec<u16>,
+    ) -> Option<ObjectSections> {
+        let target = MergeTarget::Object {
+            address: Address::new(index, 0),
+            is_sub_object: false,
+        };
+        let [base, ours, theirs] = objects;
+        let (Some(o), Some(t)) = (ours, theirs) else {
+            return self.pick(target, None, objects, |s| s.get_key("ParameterName"));
+        };
+        if ours == theirs || theirs == base {
+            return ours.cloned();
+        }
+        if ours == base {
+            return theirs.cloned();
+        }
+        let structure = |s: &ObjectSections| STRUCTURE_KEYS.map(|k| s.get_key(k));
+        if structure(o) != structure(t) {
+            return self.pick(target, Some("ObjectType"), objects, |s| {
+                s.get_key("ObjectType")
+            });
+        }
+
+        let (keys, _) = self.merge_keys(target, objects.map(|s| s.map(|s| &s.keys)), &[]);
+        let mut subindices: Vec<u8> = objects
+            .into_iter()
+            .flatten()
+            .flat_map(|s| s.subs.keys())
+            .copied()
+            .collect();
+        subindices.sort();
+        subindices.dedup();
+        let mut subs = BTreeMap::new();
+        for subindex in subindices {
+            let sub_target = MergeTarget::Object {
+                address: Address::new(index, subindex),
+                is_sub_object: true,
+            };
+            let sections = objects.map(|s| s.and_then(|s| s.subs.get(&subindex)));
+            let merged = if let [_, Some(_), Some(_)] = sections {
+                //Sub 0 is recounted if both sides changed the entries
+                let automatic: &[&str] = if subindex == 0 {
+                    &["DefaultValue"]
+                } else {
+                    &[]
+                };
+                let (keys, changed) = self.merge_keys(sub_target, sections, automatic);
+                if changed {
+                    recompute.push(index);
+                }
+                Some(keys)
+            } else {
+                self.pick(sub_target, None, sections, |k| {
+                    get_key(Some(k), "ParameterName").cloned()
+                })
+            };
+            if let Some(keys) = merged {
+                subs.insert(subindex, keys);
+            }
+        }
+        Some(ObjectSections { keys, subs })
+    }
+}
+
+struct MergeOutput {
+    text: String,
+    conflicts: Vec<MergeConflict>,
+    bump_revision: bool,
+    recompute: Vec<u16>,
+}
+
+fn write_objects(out: &mut String, objects: &BTreeMap<u16, ObjectSections>) {
+    for list in ObjectList::ALL {
+        let name = list.to_string();
+        let indices: Vec<&u16> = objects
+            .iter()
+            .filter(|(_, o)| o.get_key("ObjectList").as_ref() == Some(&name))
+            .map(|(index, _)| index)
+            .collect();
+        let mut keys = vec![("SupportedObjects".to_string(), indices.len().to_string())];
+        for (i, index) in indices.iter().enumerate() {
+            keys.push(((i + 1).to_string(), format!("0x{:04X}", index)));
+        }
+        write_section(out, list.get_section_name(), &keys);
+    }
+    for (index, obj) in objects {
+        let mut keys: Keys = obj
+            .keys
+            .iter()
+            .filter(|(k, _)| k != "ObjectList")
+            .cloned()
+            .collect();
+        if !obj.subs.is_empty() {
+            keys.insert(
+                0,
+                ("SubNumber".to_string(), format!("0x{:X}", obj.subs.len())),
+            );
+        }
+        write_section(out, &section_name(&Address::new(*index, 0), false), &keys);
+        for (subindex, sub) in &obj.subs {
+            let name = section_name(&Address::new(*index, *subindex), true);
+            write_section(out, &name, sub);
+        }
+    }
+}
+
+//Three-way merge of two files that were changed from the same base. Objects
+//and the info sections are merged key by key, conflicts are collected and
+//can be resolved one by one before the merged file is built.
+pub struct EDSMerge {
+    base: EDSFile,
+    ours: EDSFile,
+    theirs: EDSFile,
+    resolutions: Vec<(MergeTarget, Option<String>, Resolution)>,
+}
+
+impl EDSMerge {
+    pub fn new(base: &EDSFile, ours: &EDSFile, theirs: &EDSFile) -> EDSMerge {
+        EDSMerge {
+            base: base.clone(),
+            ours: ours.clone(),
+            theirs: theirs.clone(),
+            resolutions: Vec::new(),
+        }
+    }
+
+    fn run(&self) -> MergeOutput {
+        let mut pass = MergePass {
+            resolutions: &self.resolutions,
+            conflicts: Vec::new(),
+        };
+        let files = [&self.base, &self.ours, &self.theirs];
+        let file_info = files.map(|f| to_keys(file_info_keys(&f.file_info)));
+        let (file_info, bump_revision) = pass.merge_keys(
+            MergeTarget::FileInfo,
+            file_info.each_ref().map(Some),
+            &REVISION_KEYS,
+        );
+        let device_info =
+            files.map(|f| to_keys(device_info_keys(&f.device_info, &f.file_info.eds_version)));
+        let (device_info, _) = pass.merge_keys(
+            MergeTarget::DeviceInfo,
+            device_info.each_ref().map(Some),
+            &[],
+        );
+        let dummy_usage = files.map(|f| dummy_usage_keys(&f.dummy_usage));
+        let (dummy_usage, _) = pass.merge_keys(
+            MergeTarget::DummyUsage,
+            dummy_usage.each_ref().map(Some),
+            &[],
+        );
+
+        let objects = files.map(file_objects);
+        let mut indices: Vec<u16> = objects.iter().flat_map(|o| o.keys()).copied().collect();
+        indices.sort();
+        indices.dedup();
+        let mut recompute = Vec::new();
+        let mut merged = BTreeMap::new();
+        for index in indices {
+            let sections = objects.each_ref().map(|o| o.get(&index));
+            if let Some(obj) = pass.merge_object(index, sections, &mut recompute) {
+                merged.insert(index, obj);
+            }
+        }
+
+        let mut text = String::new();
+        write_section(&mut text, "FileInfo", &file_info);
+        write_section(&mut text, "DeviceInfo", &device_info);
+        if !dummy_usage.is_empty() {
+            write_section(&mut text, "DummyUsage", &dummy_usage);
+        }
+        write_objects(&mut text, &merged);
+        MergeOutput {
+            text,
+            conflicts: pass.conflicts,
+            bump_revision,
+            recompute,
+        }
+    }
+
+    //Conflicts that have not been resolved yet
+    pub fn get_conflicts(&self) -> Vec<MergeConflict> {
+        self.run().conflicts
+    }
+
+    pub fn resolve(&mut self, conflict: &MergeConflict, resolution: Resolution) {
+        self.resolutions
+            .retain(|(t, k, _)| *t != conflict.target || *k != conflict.key);
+        self.resolutions
+            .push((conflict.target, conflict.key.clone(), resolution));
+    }
+
+    pub fn resolve_all(&mut self, resolution: Resolution) {
+        for conflict in self.get_conflicts() {
+            self.resolve(&conflict, resolution);
+        }
+    }
+
+    //Builds the merged file, fails while there are unresolved conflicts or
+    //if the merged objects are not valid
+    pub fn merge(&self) -> Result<EDSFile, EditError> {
+        let output = self.run();
+        if !output.conflicts.is_empty() {
+            return Err(EditError::MergeConflicts {
+                conflicts: output.conflicts,
+            });
+        }
+        let mut file = load_string(&output.text).map_err(|e| EditError::InvalidMerge {
+            message: e.to_string(),
+        })?;
+        if output.bump_revision {
+            let revision = self
+                .ours
+                .file_info
+                .file_revision
+                .max(self.theirs.file_info.file_revision);
+            file.file_info.file_revision = revision.saturating_add(1);
+            file.file_info.modification = eds_now();
+        }
+        for index in output.recompute {
+            let addr = Address::new(index, 0);
+            if let Some(list) = file.get_object_list(index)
+                && let Some(obj) = file.get_list_mut(list).get_mut(&addr)
+            {
+                update_highest_subindex(obj);
+            }
+        }
+        for obj in file.get_objects() {
+            obj.check()?;
+        }
+        Ok(file)
+    }
+}
+
+pub fn merge(base: &EDSFile, ours: &EDSFile, theirs: &EDSFile) -> Result<EDSFile, EditError> {
+    EDSMerge::new(base, ours, theirs).merge()
+}
@@ -0,0 +1,170 @@
+use crate::eds_builder::VariableBuilder;
+use crate::eds_file::{AccessMode, Address, DataType, EDSFile, ObjectList};
+use crate::eds_merge::{EDSMerge, MergeConflict, MergeTarget, Resolution, merge};
+use crate::{EditError, load_file, load_string};
+
+fn target(index: u16, subindex: u8, is_sub_object: bool) -> MergeTarget {
+    MergeTarget::Object {
+        address: Address::new(index, subindex),
+        is_sub_object,
+    }
+}
+
+fn add_sub(file: &mut EDSFile, name: &str) {
+    file.add_sub_object(
+        VariableBuilder::new(name, DataType::Int32, AccessMode::ReadOnly)
+            .build(Address::new(0x2004, 2)),
+    )
+    .expect("Failed to add sub-object!");
+}
+
+#[test]
+fn test_merge() {
+    let base = load_file("res/micro-motor.eds").expect("Failed to parse eds file!");
+    let mut ours = base.clone();
+    ours.set_default(&Address::new(0x1017, 0), Some(Box::new(1000u16)))
+        .expect("Failed to set default!");
+    ours.set_object_name(0x2001, "Update period")
+        .expect("Failed to set name!");
+    add_sub(&mut ours, "Position Error");
+
+    let mut theirs = base.clone();
+    theirs
+        .set_default(&Address::new(0x1017, 0), Some(Box::new(500u16)))
+        .expect("Failed to set default!");
+    theirs
+        .set_limits(
+            &Address::new(0x2002, 0),
+            Some(Box::new(-100i16)),
+            Some(Box::new(100i16)),
+        )
+        .expect("Failed to set limits!");
+    theirs
+        .remove_object(0x2003)
+        .expect("Failed to remove object!");
+    theirs
+        .add_object(
+            ObjectList::Manufacturer,
+            VariableBuilder::new("Temperature", DataType::Int16, AccessMode::ReadOnly)
+                .build(Address::new(0x2100, 0)),
+        )
+        .expect("Failed to add object!");
+    add_sub(&mut theirs, "Speed Error");
+
+    //Changes of one side are taken as they are
+    let merged = merge(&base, &ours, &base).expect("Failed to merge!");
+    assert_eq!(merged.to_eds_string(), ours.to_eds_string());
+
+    let mut merger = EDSMerge::new(&base, &ours, &theirs);
+    let conflicts = merger.get_conflicts();
+    assert_eq!(
+        conflicts,
+        vec![
+            MergeConflict {
+                target: target(0x1017, 0, false),
+                key: Some("DefaultValue".to_string()),
+                base: Some("0x0000".to_string()),
+                ours: Some("0x03E8".to_string()),
+                theirs: Some("0x01F4".to_string()),
+            },
+            MergeConflict {
+                target: target(0x2004, 2, true),
+                key: Some("ParameterName".to_string()),
+                base: None,
+                ours: Some("Position Error".to_string()),
+                theirs: Some("Speed Error".to_string()),
+            },
+        ]
+    );
+    assert_eq!(
+        conflicts[0].to_string(),
+        "0x1017.DefaultValue: base 0x0000, ours 0x03E8, theirs 0x01F4"
+    );
+    assert!(matches!(
+        merger.merge(),
+        Err(EditError::MergeConflicts { conflicts }) if conflicts.len() == 2
+    ));
+
+    merger.resolve(&conflicts[0], Resolution::Theirs);
+    merger.resolve(&conflicts[1], Resolution::Ours);
+    assert!(merger.get_conflicts().is_empty());
+    let merged = merger.merge().expect("Failed to merge!");
+    assert_eq!(
+        merged.get_default_u64(&Address::new(0x1017, 0), 0),
+        Some(500)
+    );
+    assert_eq!(
+        merged
+            .get_object(&Address::new(0x2001, 0))
+            .map(|o| o.get_name().as_str()),
+        Some("Update period")
+    );
+    let (low, high) = merged
+        .get_object(&Address::new(0x2002, 0))
+        .expect("Missing pwm command!")
+        .get_limits();
+    assert!(low.is_some() && high.is_some());
+    assert!(merged.get_index_object(0x2003).is_none());
+    assert_eq!(
+        merged.get_object_list(0x2100),
+        Some(ObjectList::Manufacturer)
+    );
+    assert_eq!(
+        merged
+            .get_object(&Address::new(0x2004, 2))
+            .map(|o| o.get_name().as_str()),
+        Some("Position Error")
+    );
+    assert_eq!(
+        merged.file_info.file_revision,
+        theirs.file_info.file_revision + 1
+    );
+
+    //The merged file goes through the EDS writer
+    let text = merged.to_eds_string();
+    assert!(text.contains("[2004]\nSubNumber=0x2\n"));
+    let reparsed = load_string(&text).expect("Failed to parse merged file!");
+    assert_eq!(reparsed.to_eds_string(), text);
+}
+
+#[test]
+fn test_merge_removed() {
+    let base = load_file("res/micro-motor.eds").expect("Failed to parse eds file!");
+    let mut ours = base.clone();
+    ours.set_object_name(0x2003, "Output duty cycle")
+        .expect("Failed to set name!");
+    let mut theirs = base.clone();
+    theirs
+        .remove_object(0x2003)
+        .expect("Failed to remove object!");
+
+    let mut merger = EDSMerge::new(&base, &ours, &theirs);
+    let conflicts = merger.get_conflicts();
+    assert_eq!(conflicts.len(), 1);
+    assert_eq!(conflicts[0].key, None);
+    assert_eq!(
+        conflicts[0].get_value(Resolution::Ours).map(|s| s.as_str()),
+        Some("Output duty cycle")
+    );
+    assert_eq!(conflicts[0].get_value(Resolution::Theirs), None);
+
+    merger.resolve_all(Resolution::Theirs);
+    let merged = merger.merge().expect("Failed to merge!");
+    assert!(merged.get_index_object(0x2003).is_none());
+    merger.resolve_all(Resolution::Ours);
+    merger.resolve(&conflicts[0], Resolution::Base);
+    let merged = merger.merge().expect("Failed to merge!");
+    assert_eq!(
+        merged
+            .get_object(&Address::new(0x2003, 0))
+            .map(|o| o.get_name().as_str()),
+        Some("Output PWM")
+    );
+
+    //The revision stops at its highest value
+    theirs.file_info.file_revision = u8::MAX;
+    let mut merger = EDSMerge::new(&base, &ours, &theirs);
+    merger.resolve_all(Resolution::Theirs);
+    let merged = merger.merge().expect("Failed to merge!");
+    assert_eq!(merged.file_info.file_revision, u8::MAX);
+}
@@ -0,0 +1,256 @@
+mod patch_operation;
+
+#[cfg(test)]
+mod tests;
+
+pub use patch_operation::*;
+
+use crate::eds_file::{
+    AccessMode, Address, EDSFile, EDSObject, EDSValue, ObjectList,
+    utils::{parse_required_str, parse_required_uint},
+};
+use crate::structured_file::{StructuredFile, StructuredFileObject};
+use crate::{EditError, ParseError};
+
+const SET_KEYS: [&str; 5] = [
+    "ParameterName",
+    "AccessType",
+    "DefaultValue",
+    "LowLimit",
+    "HighLimit",
+];
+
+//Objects are given as 0x1018 and sub-objects as 0x1018sub2
+fn parse_target(value: &str) -> Option<(Address, bool)> {
+    let lower = value.to_lowercase();
+    let value = lower.strip_prefix("0x").unwrap_or(&lower);
+    match value.split_once("sub") {
+        Some((index, subindex)) => {
+            let index = u16::from_str_radix(index, 16).ok()?;
+            let subindex = u8::from_str_radix(subindex, 16).ok()?;
+            Some((Address::new(index, subindex), true))
+        }
+        None => Some((Address::new(u16::from_str_radix(value, 16).ok()?, 0), false)),
+    }
+}
+
+//Lists are written like the object lists of an EDS and may be left out
+fn parse_list(sfile: &StructuredFile, name: &str) -> Result<Vec<(Address, bool)>, ParseError> {
+    let Some(obj) = sfile.get_object(name) else {
+        return Ok(Vec::new());
+    };
+    let count: u16 = parse_required_uint(obj, "SupportedObjects")?;
+    (1..=count)
+        .map(|i| {
+            let key = i.to_string();
+            parse_target(parse_required_str(obj, &key)?).ok_or(ParseError::InvalidValueFormat {
+                object: key,
+                section: obj.get_name().clone(),
+            })
+        })
+        .collect()
+}
+
+fn parse_set(
+    sfile: &StructuredFile,
+    addr: Address,
+    is_sub_object: bool,
+) -> Result<PatchOperation, ParseError> {
+    let section = EDSObject::make_addr_string(&addr, is_sub_object);
+    let obj = sfile
+        .get_object(&section)
+        .ok_or(ParseError::MissingRequiredSection { section })?;
+    if let Some(key) = obj
+        .get_values()
+        .keys()
+        .find(|k| !SET_KEYS.iter().any(|s| s.eq_ignore_ascii_case(k)))
+    {
+        return Err(ParseError::UnsupportedKey {
+            key: key.clone(),
+            section: obj.get_name().clone(),
+        });
+    }
+    let mut values = Vec::new();
+    if let Some(name) = obj.get_value("ParameterName") {
+        values.push(PatchValue::Name(name.clone()));
+    }
+    if let Some(access_mode) = obj.get_value("AccessType") {
+        let mode = AccessMode::parse(access_mode).ok_or(ParseError::InvalidAccessMode {
+            address: addr,
+            access_mode: access_mode.clone(),
+        })?;
+        values.push(PatchValue::AccessMode(mode));
+    }
+    if let Some(default) = obj.get_value("DefaultValue") {
+        values.push(PatchValue::Default(default.clone()));
+    }
+    if let Some(low) = obj.get_value("LowLimit") {
+        values.push(PatchValue::LowLimit(low.clone()));
+    }
+    if let Some(high) = obj.get_value("HighLimit") {
+        values.push(PatchValue::HighLimit(high.clone()));
+    }
+    Ok(PatchOperation::Set {
+        addr,
+        is_sub_object,
+        values,
+    })
+}
+
+fn exists(file: &EDSFile, addr: &Address, is_sub_object: bool) -> bool {
+    let obj = file.get_index_object(addr.index);
+    if !is_sub_object {
+        return obj.is_some();
+    }
+    obj.and_then(|o| o.get_entries())
+        .is_some_and(|e| e.iter().any(|s| s.get_address().subindex == addr.subindex))
+}
+
+fn parse_value(
+    obj: &EDSObject,
+    key: &str,
+    value: &str,
+) -> Result<Option<Box<dyn EDSValue>>, EditError> {
+    let addr = *obj.get_address();
+    let data_type = obj.get_data_type().ok_or(EditError::NoValue { addr })?;
+    let mut sobj = StructuredFileObject::new(EDSObject::make_addr_string(&addr, true));
+    sobj.get_values_mut()
+        .insert(key.to_string(), value.to_string());
+    EDSObject::parse_data(&sobj, data_type, key).map_err(|_| EditError::ValueTypeMismatch {
+        addr,
+        data_type: data_type.clone(),
+    })
+}
+
+fn set_values(
+    file: &mut EDSFile,
+    addr: &Address,
+    is_sub_object: bool,
+    values: &[PatchValue],
+) -> Result<(), EditError> {
+    for value in values {
+        let obj = if is_sub_object {
+            file.get_object(addr)
+        } else {
+            file.get_index_object(addr.index)
+        }
+        .ok_or(EditError::ObjectDoesNotExist { addr: *addr })?;
+        //Values of arrays and records are set on their sub-objects
+        if !is_sub_object && obj.get_entries().is_some() && !matches!(value, PatchValue::Name(_)) {
+            return Err(EditError::NoValue { addr: *addr });
+        }
+        let (low, high) = obj.get_limits();
+        let (low, high) = (low.map(|v| v.clone_box()), high.map(|v| v.clone_box()));
+        match value {
+            PatchValue::Name(name) if is_sub_object => file.set_name(addr, name)?,
+            PatchValue::Name(name) => file.set_object_name(addr.index, name)?,
+            PatchValue::AccessMode(mode) => file.set_access_mode(addr, mode.clone())?,
+            PatchValue::Default(val) => {
+                let default = parse_value(obj, "DefaultValue", val)?;
+                file.set_default(addr, default)?
+            }
+            PatchValue::LowLimit(val) => {
+                let low = parse_value(obj, "LowLimit", val)?;
+                file.set_limits(addr, low, high)?
+            }
+            PatchValue::HighLimit(val) => {
+                let high = parse_value(obj, "HighLimit", val)?;
+                file.set_limits(addr, low, high)?
+            }
+        }
+    }
+    Ok(())
+}
+
+#[derive(Debug, Clone)]
+pub struct PatchResult {
+    pub file: EDSFile,
+    pub stale: Vec<StaleTarget>,
+}
+
+//Changes to apply on top of a vendor EDS. A patch is written like an EDS
+//fragment: the [RemoveObjects], [AddObjects] and [SetObjects] sections list
+//their targets like an object list, added objects are complete object
+//sections and set objects only hold the keys to change.
+#[derive(Debug, Clone, Default)]
+pub struct EDSPatch {
+    pub operations: Vec<PatchOperation>,
+}
+
+impl EDSPatch {
+    pub(crate) fn parse(sfile: &StructuredFile) -> Result<EDSPatch, ParseError> {
+        let mut operations = Vec::new();
+        for (addr, is_sub_object) in parse_list(sfile, "RemoveObjects")? {
+            operations.push(PatchOperation::Remove {
+                addr,
+                is_sub_object,
+            });
+        }
+        for (addr, is_sub_object) in parse_list(sfile, "AddObjects")? {
+            let object = if is_sub_object {
+                EDSObject::parse_sub_addr(sfile, &addr)?
+            } else {
+                EDSObject::parse(sfile, &addr)?
+            };
+            operations.push(PatchOperation::Add {
+                object,
+                is_sub_object,
+            });
+        }
+        for (addr, is_sub_object) in parse_list(sfile, "SetObjects")? {
+            operations.push(parse_set(sfile, addr, is_sub_object)?);
+        }
+        Ok(EDSPatch { operations })
+    }
+
+    //Applies the operations in order to a copy of the file. Targets that do
+    //not fit the file are reported and skipped, invalid values fail the
+    //whole patch.
+    pub fn apply(&self, file: &EDSFile) -> Result<PatchResult, EditError> {
+        let mut out = file.clone();
+        let mut stale = Vec::new();
+        let mut applied = false;
+        for op in &self.operations {
+            let (addr, is_sub_object) = (*op.get_address(), op.is_sub_object());
+            let exists = exists(&out, &addr, is_sub_object);
+            match op {
+                PatchOperation::Add { .. } if exists => {
+                    stale.push(StaleTarget::Exists {
+                        addr,
+                        is_sub_object,
+                    });
+                    continue;
+                }
+                PatchOperation::Remove { .. } | PatchOperation::Set { .. } if !exists => {
+                    stale.push(StaleTarget::Missing {
+                        addr,
+                        is_sub_object,
+                    });
+                    continue;
+                }
+                PatchOperation::Remove { .. } if is_sub_object => {
+                    out.remove_sub_object(&addr)?;
+                }
+                PatchOperation::Remove { .. } => {
+                    out.remove_object(addr.index)?;
+                }
+                PatchOperation::Add { object, .. } if is_sub_object => {
+                    out.add_sub_object(object.clone())?;
+                }
+                PatchOperation::Add { object, .. } => {
+                    out.add_object(ObjectList::for_index(addr.index), object.clone())?;
+                }
+                PatchOperation::Set { values, .. } => {
+                    set_values(&mut out, &addr, is_sub_object, values)?;
+                }
+            }
+            applied = true;
+        }
+        //A patched file is one revision after the file it was applied to
+        out.file_info = file.file_info.clone();
+        if applied {
+            out.touch();
+        }
+        Ok(PatchResult { file: out, stale })
+    }
+}
@@ -0,0 +1,81 @@
+use std::fmt::Display;
+
+use crate::eds_file::{AccessMode, Address, EDSObject};
+
+//Values of numbers are kept as written and parsed with the data type of the
+//object the patch is applied to
+#[derive(Debug, PartialEq, Clone)]
+pub enum PatchValue {
+    Name(String),
+    AccessMode(AccessMode),
+    Default(String),
+    LowLimit(String),
+    HighLimit(String),
+}
+
+#[derive(Debug, Clone)]
+pub enum PatchOperation {
+    Remove {
+        addr: Address,
+        is_sub_object: bool,
+    },
+    Add {
+        object: EDSObject,
+        is_sub_object: bool,
+    },
+    Set {
+        addr: Address,
+        is_sub_object: bool,
+        values: Vec<PatchValue>,
+    },
+}
+
+impl PatchOperation {
+    pub fn get_address(&self) -> &Address {
+        match &self {
+            PatchOperation::Remove { addr, .. } => addr,
+            PatchOperation::Add { object, .. } => object.get_address(),
+            PatchOperation::Set { addr, .. } => addr,
+        }
+    }
+
+    pub fn is_sub_object(&self) -> bool {
+        match &self {
+            PatchOperation::Remove { is_sub_object, .. } => *is_sub_object,
+            PatchOperation::Add { is_sub_object, .. } => *is_sub_object,
+            PatchOperation::Set { is_sub_object, .. } => *is_sub_object,
+        }
+    }
+}
+
+//Operations that did not fit the file they were applied to, they are skipped
+#[derive(Debug, PartialEq, Clone)]
+pub enum StaleTarget {
+    //The object to remove or change does not exist
+    Missing { addr: Address, is_sub_object: bool },
+    //The object to add already exists
+    Exists { addr: Address, is_sub_object: bool },
+}
+
+fn addr_str(addr: &Address, is_sub_object: bool) -> String {
+    if is_sub_object {
+        format!("0x{:04X}sub{:X}", addr.index, addr.subindex)
+    } else {
+        format!("0x{:04X}", addr.index)
+    }
+}
+
+impl Display for StaleTarget {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self {
+            StaleTarget::Missing {
+                addr,
+                is_sub_object,
+            } => write!(f, "{} does not exist", addr_str(addr, *is_sub_object)),
+            StaleTarget::Exists {
+                addr,
+                is_sub_object,
+            } => write!(f, "{} already exists", addr_str(addr, *is_sub_object)),
+        }
+    }
+}
@@ -0,0 +1,141 @@
+use crate::eds_file::{AccessMode, Address, DataType, ObjectList};
+use crate::eds_patch::{PatchOperation, PatchValue, StaleTarget};
+use crate::{EditError, ParseError, load_file, load_patch_string};
+
+const PATCH: &str = "[RemoveObjects]
+SupportedObjects=1
+1=0x2003
+
+[AddObjects]
+SupportedObjects=2
+1=0x2100
+2=0x2004sub2
+
+[2100]
+ParameterName=Temperature
+ObjectType=0x7
+DataType=0x0003
+AccessType=ro
+
+[2004sub2]
+ParameterName=Position Error
+ObjectType=0x7
+DataType=0x0004
+AccessType=ro
+
+[SetObjects]
+SupportedObjects=2
+1=0x1017
+2=0x2002
+
+[1017]
+DefaultValue=1000
+
+[2002]
+AccessType=ro
+LowLimit=-100
+HighLimit=100
+";
+
+#[test]
+fn test_parse_patch() {
+    let patch = load_patch_string(PATCH).expect("Failed to parse patch!");
+    assert_eq!(patch.operations.len(), 5);
+    assert!(matches!(
+        patch.operations[0],
+        PatchOperation::Remove {
+            addr: Address {
+                index: 0x2003,
+                subindex: 0
+            },
+            is_sub_object: false
+        }
+    ));
+    assert!(patch.operations[2].is_sub_object());
+    assert_eq!(*patch.operations[2].get_address(), Address::new(0x2004, 2));
+    let PatchOperation::Set { values, .. } = &patch.operations[4] else {
+        panic!("Expected a set operation!");
+    };
+    assert_eq!(
+        *values,
+        vec![
+            PatchValue::AccessMode(AccessMode::ReadOnly),
+            PatchValue::LowLimit("-100".to_string()),
+            PatchValue::HighLimit("100".to_string()),
+        ]
+    );
+
+    let unknown = PATCH.replace("DefaultValue=1000", "ObjectType=0x9");
+    assert!(matches!(
+        load_patch_string(&unknown),
+        Err(ParseError::UnsupportedKey { key, .. }) if key == "ObjectType"
+    ));
+}
+
+#[test]
+fn test_apply_patch() {
+    let file = load_file("res/micro-motor.eds").expect("Failed to parse eds file!");
+    let patch = load_patch_string(PATCH).expect("Failed to parse patch!");
+    let result = patch.apply(&file).expect("Failed to apply patch!");
+    assert!(result.stale.is_empty());
+    let patched = result.file;
+    assert_eq!(
+        patched.get_default_u64(&Address::new(0x1017, 0), 0),
+        Some(1000)
+    );
+    assert!(patched.get_index_object(0x2003).is_none());
+    assert_eq!(
+        patched.get_object_list(0x2100),
+        Some(ObjectList::Manufacturer)
+    );
+    let sub = patched
+        .get_object(&Address::new(0x2004, 2))
+        .expect("Missing added sub-object!");
+    assert_eq!(sub.get_data_type(), Some(&DataType::Int32));
+    let pwm = patched
+        .get_object(&Address::new(0x2002, 0))
+        .expect("Missing pwm command!");
+    assert_eq!(pwm.get_access_mode(), Some(&AccessMode::ReadOnly));
+    let (low, high) = pwm.get_limits();
+    assert!(low.is_some() && high.is_some());
+    assert_eq!(
+        patched.file_info.file_revision,
+        file.file_info.file_revision + 1
+    );
+    //The vendor file is left as it is
+    assert!(file.get_index_object(0x2003).is_some());
+
+    //A newer vendor revision that already has some of the changes
+    let mut newer = file.clone();
+    newer
+        .remove_object(0x2003)
+        .expect("Failed to remove object!");
+    let result = patch.apply(&patched).expect("Failed to apply patch!");
+    assert_eq!(
+        result.stale,
+        vec![
+            StaleTarget::Missing {
+                addr: Address::new(0x2003, 0),
+                is_sub_object: false
+            },
+            StaleTarget::Exists {
+                addr: Address::new(0x2100, 0),
+                is_sub_object: false
+            },
+            StaleTarget::Exists {
+                addr: Address::new(0x2004, 2),
+                is_sub_object: true
+            },
+        ]
+    );
+    assert_eq!(result.stale[2].to_string(), "0x2004sub2 already exists");
+    let result = patch.apply(&newer).expect("Failed to apply patch!");
+    assert_eq!(result.stale.len(), 1);
+
+    let invalid = PATCH.replace("DefaultValue=1000", "DefaultValue=fast");
+    let patch = load_patch_string(&invalid).expect("Failed to parse patch!");
+    assert!(matches!(
+        patch.apply(&file),
+        Err(EditError::ValueTypeMismatch { .. })
+    ));
+}
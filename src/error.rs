@@ -1,6 +1,10 @@
 use std::fmt::Display;
 
-use crate::eds_file::{AccessMode, Address, ObjectType};
+use crate::communication_profile::StandardObjectIssue;
+use crate::eds_file::{AccessMode, Address, DataType, EDSVersion, ObjectType};
+use crate::eds_merge::MergeConflict;
+use crate::pdo::PdoDirection;
+use crate::sdo::SdoAbortCode;
 
 #[derive(Debug)]
 pub enum ParseError {
@@ -80,6 +84,15 @@ pub enum ParseError {
     NestedListsUnsupported {
         addr: Address,
     },
+    UnsupportedKey {
+        key: String,
+        section: String,
+    },
+    UnsupportedInVersion {
+        section: String,
+        key: Option<String>,
+        version: EDSVersion,
+    },
 }
 
 impl From<std::io::Error> for ParseError {
@@ -216,6 +229,444 @@ impl Display for ParseError {
                     date_obj, time_obj, section
                 )
             }
+            Self::UnsupportedKey { key, section } => {
+                write!(f, "Unsupported key \"{}\" in section \"{}\"", key, section)
+            }
+            Self::UnsupportedInVersion {
+                section,
+                key: Some(key),
+                version,
+            } => write!(
+                f,
+                "Key \"{}\" in section \"{}\" is not supported by EDS {}",
+                key, section, version
+            ),
+            Self::UnsupportedInVersion {
+                section,
+                key: None,
+                version,
+            } => write!(
+                f,
+                "Section \"{}\" is not supported by EDS {}",
+                section, version
+            ),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum PdoMappingError {
+    MissingMappingObject {
+        addr: Address,
+    },
+    InvalidMappingValue {
+        addr: Address,
+    },
+    MappedObjectNotFound {
+        mapping: Address,
+        mapped: Address,
+    },
+    ObjectNotMappable {
+        addr: Address,
+    },
+    AccessModeNotMappable {
+        addr: Address,
+        access_mode: AccessMode,
+        direction: PdoDirection,
+    },
+    InvalidBitLength {
+        addr: Address,
+        bit_length: u8,
+    },
+    TooManyBits {
+        mapping: Address,
+        bits: u32,
+    },
+    GranularityViolation {
+        addr: Address,
+        granularity: u8,
+    },
+    UnfillableGap {
+        bits: u32,
+    },
+    MappingNotSupported,
+    InvalidPdoNumber {
+        direction: PdoDirection,
+        number: u16,
+    },
+}
+
+impl Display for PdoMappingError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self {
+            Self::MissingMappingObject { addr } => {
+                write!(f, "Missing PDO mapping object {}", addr)
+            }
+            Self::InvalidMappingValue { addr } => {
+                write!(f, "Invalid or missing PDO mapping value in {}", addr)
+            }
+            Self::MappedObjectNotFound { mapping, mapped } => {
+                write!(f, "Object {} mapped in {} does not exist", mapped, mapping)
+            }
+            Self::ObjectNotMappable { addr } => {
+                write!(f, "Object {} is not PDO mappable", addr)
+            }
+            Self::AccessModeNotMappable {
+                addr,
+                access_mode,
+                direction,
+            } => write!(
+                f,
+                "Object {} with access type \"{}\" can not be mapped into a {}",
+                addr, access_mode, direction
+            ),
+            Self::InvalidBitLength { addr, bit_length } => {
+                write!(
+                    f,
+                    "Invalid bit length {} for mapped object {}",
+                    bit_length, addr
+                )
+            }
+            Self::TooManyBits { mapping, bits } => {
+                write!(
+                    f,
+                    "PDO mapping {} exceeds 64 bits with {} bits",
+                    mapping, bits
+                )
+            }
+            Self::GranularityViolation { addr, granularity } => write!(
+                f,
+                "Object {} can not be mapped with a granularity of {} bits",
+                addr, granularity
+            ),
+            Self::UnfillableGap { bits } => write!(
+                f,
+                "Gap of {} bits can not be filled with the supported dummy objects",
+                bits
+            ),
+            Self::MappingNotSupported => write!(f, "The device does not support PDO mapping"),
+            Self::InvalidPdoNumber { direction, number } => {
+                write!(f, "Invalid {} number {}", direction, number)
+            }
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum PdoFrameError {
+    MissingValue { addr: Address },
+    InvalidValue { addr: Address },
+    InvalidPayloadLength { expected: usize, actual: usize },
+    GranularityViolation { addr: Address, granularity: u8 },
+    MappingNotSupported,
+}
+
+impl Display for PdoFrameError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self {
+            Self::MissingValue { addr } => write!(f, "Missing value for mapped object {}", addr),
+            Self::InvalidValue { addr } => {
+                write!(f, "Value for mapped object {} does not fit its type", addr)
+            }
+            Self::InvalidPayloadLength { expected, actual } => write!(
+                f,
+                "Invalid PDO payload length {}, expected {} bytes",
+                actual, expected
+            ),
+            Self::GranularityViolation { addr, granularity } => write!(
+                f,
+                "Mapped object {} is not aligned to the device granularity of {} bits",
+                addr, granularity
+            ),
+            Self::MappingNotSupported => write!(f, "The device does not support PDO mapping"),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum DcfError {
+    InvalidValue { addr: Address },
+    TruncatedConciseDcf { length: usize },
+    DownloadFailed { addr: Address, code: SdoAbortCode },
+}
+
+impl Display for DcfError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self {
+            Self::InvalidValue { addr } => {
+                write!(f, "Value for {} can not be encoded", addr)
+            }
+            Self::TruncatedConciseDcf { length } => {
+                write!(f, "Concise DCF of {} bytes is truncated", length)
+            }
+            Self::DownloadFailed { addr, code } => {
+                write!(f, "Download to {} failed with {}", addr, code)
+            }
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum CommParamError {
+    MissingObject {
+        addr: Address,
+    },
+    InvalidValue {
+        addr: Address,
+    },
+    InvalidPdoNumber {
+        direction: PdoDirection,
+        number: u16,
+    },
+    InvalidSdoServerNumber {
+        number: u16,
+    },
+}
+
+impl Display for CommParamError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self {
+            Self::MissingObject { addr } => {
+                write!(f, "Missing communication parameter object {}", addr)
+            }
+            Self::InvalidValue { addr } => {
+                write!(f, "Invalid communication parameter value in {}", addr)
+            }
+            Self::InvalidPdoNumber { direction, number } => {
+                write!(f, "Invalid {} number {}", direction, number)
+            }
+            Self::InvalidSdoServerNumber { number } => {
+                write!(f, "Invalid SDO server number {}", number)
+            }
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum CanBusError {
+    InvalidFrame { cob_id: u32, length: usize },
+    Disconnected,
+}
+
+impl Display for CanBusError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self {
+            Self::InvalidFrame { cob_id, length } => {
+                write!(f, "Invalid CAN frame 0x{:X} with {} bytes", cob_id, length)
+            }
+            Self::Disconnected => write!(f, "CAN bus is disconnected"),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum VirtualSlaveError {
+    BusError(CanBusError),
+    CommParamError(CommParamError),
+}
+
+impl From<CanBusError> for VirtualSlaveError {
+    fn from(value: CanBusError) -> Self {
+        Self::BusError(value)
+    }
+}
+
+impl From<CommParamError> for VirtualSlaveError {
+    fn from(value: CommParamError) -> Self {
+        Self::CommParamError(value)
+    }
+}
+
+impl Display for VirtualSlaveError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self {
+            Self::BusError(e) => write!(f, "{}", e),
+            Self::CommParamError(e) => {
+                write!(f, "Failed to load communication parameters: {}", e)
+            }
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum ObjectDictionaryError {
+    ObjectDoesNotExist { addr: Address },
+    NotReadable { addr: Address },
+    NotWritable { addr: Address },
+    TypeMismatch { addr: Address, data_type: DataType },
+    ValueTooLow { addr: Address },
+    ValueTooHigh { addr: Address },
+    NoValue { addr: Address },
+}
+
+impl ObjectDictionaryError {
+    pub fn get_abort_code(&self) -> SdoAbortCode {
+        match &self {
+            Self::ObjectDoesNotExist { .. } => SdoAbortCode::ObjectDoesNotExist,
+            Self::NotReadable { .. } => SdoAbortCode::ReadWriteOnly,
+            Self::NotWritable { .. } => SdoAbortCode::WriteReadOnly,
+            Self::TypeMismatch { .. } => SdoAbortCode::LengthMismatch,
+            Self::ValueTooLow { .. } => SdoAbortCode::ValueTooLow,
+            Self::ValueTooHigh { .. } => SdoAbortCode::ValueTooHigh,
+            Self::NoValue { .. } => SdoAbortCode::NoDataAvailable,
+        }
+    }
+}
+
+impl Display for ObjectDictionaryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self {
+            Self::ObjectDoesNotExist { addr } => write!(f, "Object {} does not exist", addr),
+            Self::NotReadable { addr } => write!(f, "Object {} is not readable", addr),
+            Self::NotWritable { addr } => write!(f, "Object {} is not writable", addr),
+            Self::TypeMismatch { addr, data_type } => {
+                write!(f, "Value for {} is not of type {:?}", addr, data_type)
+            }
+            Self::ValueTooLow { addr } => write!(f, "Value for {} is below its limit", addr),
+            Self::ValueTooHigh { addr } => write!(f, "Value for {} is above its limit", addr),
+            Self::NoValue { addr } => write!(f, "Object {} has no value", addr),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum EditError {
+    UnknownStandardObject {
+        index: u16,
+    },
+    ObjectExists {
+        index: u16,
+    },
+    ObjectDoesNotExist {
+        addr: Address,
+    },
+    SubObjectExists {
+        addr: Address,
+    },
+    NoSubObjects {
+        index: u16,
+    },
+    NumberOfEntries {
+        addr: Address,
+    },
+    NoValue {
+        addr: Address,
+    },
+    EmptyName {
+        addr: Address,
+    },
+    MissingInfo {
+        key: String,
+    },
+    InvalidSubAddress {
+        addr: Address,
+    },
+    SubNumberMismatch {
+        index: u16,
+        sub_number: u8,
+        entries: usize,
+    },
+    NestedObject {
+        addr: Address,
+    },
+    InconsistentArray {
+        addr: Address,
+    },
+    PdoMappingNotAllowed {
+        addr: Address,
+        access_mode: AccessMode,
+    },
+    LimitsNotSupported {
+        addr: Address,
+        data_type: DataType,
+    },
+    ValueTypeMismatch {
+        addr: Address,
+        data_type: DataType,
+    },
+    NonconformingObjects {
+        issues: Vec<StandardObjectIssue>,
+    },
+    MergeConflicts {
+        conflicts: Vec<MergeConflict>,
+    },
+    InvalidMerge {
+        message: String,
+    },
+    UnsupportedVersion {
+        version: EDSVersion,
+    },
+}
+
+impl Display for EditError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self {
+            Self::UnknownStandardObject { index } => {
+                write!(f, "0x{:04X} is not a standard object", index)
+            }
+            Self::ObjectExists { index } => write!(f, "Object 0x{:04X} already exists", index),
+            Self::ObjectDoesNotExist { addr } => write!(f, "Object {} does not exist", addr),
+            Self::SubObjectExists { addr } => write!(f, "Sub-object {} already exists", addr),
+            Self::NoSubObjects { index } => {
+                write!(f, "Object 0x{:04X} can not have sub-objects", index)
+            }
+            Self::NumberOfEntries { addr } => write!(
+                f,
+                "Sub-object {} holds the number of entries and can not be removed",
+                addr
+            ),
+            Self::NoValue { addr } => write!(f, "Object {} has no value", addr),
+            Self::EmptyName { addr } => write!(f, "Object {} has no name", addr),
+            Self::MissingInfo { key } => write!(f, "No value given for {}", key),
+            Self::InvalidSubAddress { addr } => {
+                write!(f, "Sub-object {} is duplicated or out of place", addr)
+            }
+            Self::SubNumberMismatch {
+                index,
+                sub_number,
+                entries,
+            } => write!(
+                f,
+                "Object 0x{:04X} has SubNumber {} but {} sub-objects",
+                index, sub_number, entries
+            ),
+            Self::NestedObject { addr } => {
+                write!(f, "Sub-object {} can not be an array or record", addr)
+            }
+            Self::InconsistentArray { addr } => {
+                write!(f, "Array entry {} differs from the other entries", addr)
+            }
+            Self::PdoMappingNotAllowed { addr, access_mode } => write!(
+                f,
+                "Object {} can not be PDO mappable with access type {}",
+                addr, access_mode
+            ),
+            Self::LimitsNotSupported { addr, data_type } => write!(
+                f,
+                "Object {} of type {:?} can not have limits",
+                addr, data_type
+            ),
+            Self::ValueTypeMismatch { addr, data_type } => {
+                write!(f, "Value for {} is not of type {:?}", addr, data_type)
+            }
+            Self::NonconformingObjects { issues } => {
+                write!(f, "Objects do not conform to CiA 301:")?;
+                for issue in issues {
+                    write!(f, "\n  {}", issue)?;
+                }
+                Ok(())
+            }
+            Self::MergeConflicts { conflicts } => {
+                write!(f, "Unresolved merge conflicts:")?;
+                for conflict in conflicts {
+                    write!(f, "\n  {}", conflict)?;
+                }
+                Ok(())
+            }
+            Self::InvalidMerge { message } => write!(f, "Merged file is invalid: {}", message),
+            Self::UnsupportedVersion { version } => {
+                write!(f, "EDS version {} can not be converted", version)
+            }
         }
     }
 }
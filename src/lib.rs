@@ -3,17 +3,78 @@ pub use error::*;
 #[cfg(test)]
 mod tests;
 
+mod can_bus;
+mod can_log;
+mod comm;
+mod communication_profile;
+mod dcf;
+mod device_profile;
+mod device_scan;
+mod eds_builder;
+mod eds_diff;
 mod eds_file;
+mod eds_fmt;
+mod eds_merge;
+mod eds_patch;
 mod error;
+mod network;
+mod object_dictionary;
+mod pdo;
 mod raw_file;
+mod sdo;
 mod structured_file;
+mod virtual_slave;
 
+pub use can_bus::*;
+pub use can_log::*;
+pub use comm::*;
+pub use communication_profile::*;
+pub use dcf::*;
+pub use device_profile::*;
+pub use device_scan::*;
+pub use eds_builder::*;
+pub use eds_diff::*;
 pub use eds_file::*;
+pub use eds_fmt::*;
+pub use eds_merge::*;
+pub use eds_patch::*;
+pub use network::*;
+pub use object_dictionary::*;
+pub use pdo::*;
 use raw_file::RawFile;
+pub use sdo::*;
 use structured_file::StructuredFile;
+pub use virtual_slave::*;
 
 pub fn load_file(filename: &str) -> Result<EDSFile, ParseError> {
     let rfile = RawFile::new(filename)?;
     let sfile = StructuredFile::parse(rfile)?;
-    EDSFile::parse(sfile)
+    EDSFile::parse(&sfile)
+}
+
+pub fn load_string(data: &str) -> Result<EDSFile, ParseError> {
+    let sfile = StructuredFile::parse(RawFile::new_from_string(data))?;
+    EDSFile::parse(&sfile)
+}
+
+pub fn load_dcf_file(filename: &str) -> Result<DCFFile, ParseError> {
+    let rfile = RawFile::new(filename)?;
+    let sfile = StructuredFile::parse(rfile)?;
+    DCFFile::parse(&sfile)
+}
+
+pub fn load_dcf_string(data: &str) -> Result<DCFFile, ParseError> {
+    let sfile = StructuredFile::parse(RawFile::new_from_string(data))?;
+    DCFFile::parse(&sfile)
+}
+
+pub fn load_patch_file(filename: &str) -> Result<EDSPatch, ParseError> {
+    let rfile = RawFile::new(filename)?;
+    let sfile = StructuredFile::parse(rfile)?;
+    EDSPatch::parse(&sfile)
+}
+
+pub fn load_patch_string(data: &str) -> Result<EDSPatch, ParseError> {
+    let sfile = StructuredFile::parse(RawFile::new_from_string(data))?;
+    EDSPatch::parse(&sfile)
 }
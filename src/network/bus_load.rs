@@ -0,0 +1,260 @@
+use super::{CobIdKind, HEARTBEAT_COB_ID_BASE, Network, NetworkNode};
+use crate::CommParamError;
+use crate::comm::{SyncParams, read_default};
+use crate::eds_file::Address;
+use crate::pdo::{MAX_PDO_BYTES, PdoCommParams, PdoDirection, PdoMapping};
+
+pub const HEARTBEAT_TIME_INDEX: u16 = 0x1017;
+
+//Bits of a data frame covered by bit stuffing, SOF up to the end of the CRC
+fn stuffed_region_bits(payload_bytes: u8, extended: bool) -> u32 {
+    let header = if extended { 54 } else { 34 };
+    header + 8 * payload_bytes as u32
+}
+
+//Frame length without stuff bits including the 3 bit interframe space
+pub fn can_frame_bits(payload_bytes: u8, extended: bool) -> u32 {
+    //CRC delimiter, ACK slot and delimiter, EOF and interframe space
+    stuffed_region_bits(payload_bytes, extended) + 13
+}
+
+pub fn can_max_stuff_bits(payload_bytes: u8, extended: bool) -> u32 {
+    (stuffed_region_bits(payload_bytes, extended) - 1) / 4
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct FrameLoad {
+    pub node_id: u8,
+    pub kind: CobIdKind,
+    pub cob_id: u32,
+    pub extended: bool,
+    pub payload_bytes: u8,
+    pub nominal_fps: f64,
+    pub worst_fps: f64,
+    //Event driven frames without an inhibit time have no upper bound
+    pub unbounded: bool,
+}
+
+impl FrameLoad {
+    pub fn get_nominal_frame_bits(&self) -> u32 {
+        can_frame_bits(self.payload_bytes, self.extended)
+    }
+
+    pub fn get_worst_frame_bits(&self) -> u32 {
+        can_frame_bits(self.payload_bytes, self.extended)
+            + can_max_stuff_bits(self.payload_bytes, self.extended)
+    }
+
+    pub fn get_nominal_bps(&self) -> f64 {
+        self.nominal_fps * self.get_nominal_frame_bits() as f64
+    }
+
+    pub fn get_worst_bps(&self) -> f64 {
+        self.worst_fps * self.get_worst_frame_bits() as f64
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct BusUtilization {
+    pub baudrate: u16,
+    pub nominal_percent: f64,
+    pub worst_percent: f64,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct BusLoad {
+    pub sync_period_us: u32,
+    pub frames: Vec<FrameLoad>,
+    pub utilization: Vec<BusUtilization>,
+}
+
+impl BusLoad {
+    pub fn get_node_frames(&self, node_id: u8) -> Vec<&FrameLoad> {
+        self.frames
+            .iter()
+            .filter(|f| f.node_id == node_id)
+            .collect()
+    }
+
+    pub fn get_nominal_fps(&self) -> f64 {
+        self.frames.iter().map(|f| f.nominal_fps).sum()
+    }
+
+    pub fn get_worst_fps(&self) -> f64 {
+        self.frames.iter().map(|f| f.worst_fps).sum()
+    }
+
+    pub fn get_nominal_bps(&self) -> f64 {
+        self.frames.iter().map(|f| f.get_nominal_bps()).sum()
+    }
+
+    pub fn get_worst_bps(&self) -> f64 {
+        self.frames.iter().map(|f| f.get_worst_bps()).sum()
+    }
+
+    pub fn get_node_nominal_bps(&self, node_id: u8) -> f64 {
+        self.get_node_frames(node_id)
+            .iter()
+            .map(|f| f.get_nominal_bps())
+            .sum()
+    }
+
+    pub fn get_node_worst_bps(&self, node_id: u8) -> f64 {
+        self.get_node_frames(node_id)
+            .iter()
+            .map(|f| f.get_worst_bps())
+            .sum()
+    }
+
+    pub fn is_unbounded(&self) -> bool {
+        self.frames.iter().any(|f| f.unbounded)
+    }
+
+    pub fn get_utilization(&self, baudrate: u16) -> BusUtilization {
+        let bps = baudrate as f64 * 1000.0;
+        BusUtilization {
+            baudrate,
+            nominal_percent: self.get_nominal_bps() / bps * 100.0,
+            worst_percent: self.get_worst_bps() / bps * 100.0,
+        }
+    }
+}
+
+fn pdo_frame_load(node: &NetworkNode, pdo: &PdoCommParams, sync_period_us: u32) -> FrameLoad {
+    //Mappings that can not be decoded are assumed to fill the whole frame
+    let payload_bytes = PdoMapping::decode(&node.file, pdo.direction, pdo.number)
+        .map(|m| m.get_payload_length() as u8)
+        .unwrap_or(MAX_PDO_BYTES as u8);
+    let sync_fps = if sync_period_us == 0 {
+        0.0
+    } else {
+        1_000_000.0 / sync_period_us as f64
+    };
+    let event_fps = if pdo.event_timer_ms == 0 {
+        0.0
+    } else {
+        1000.0 / pdo.event_timer_ms as f64
+    };
+    let (nominal_fps, worst_fps, unbounded) = match pdo.transmission_type {
+        0 => (0.0, sync_fps, false),
+        1..=240 => {
+            let fps = sync_fps / pdo.transmission_type as f64;
+            (fps, fps, false)
+        }
+        252 => (0.0, sync_fps, false),
+        253 => (0.0, 0.0, false),
+        254 | 255 => {
+            if pdo.inhibit_100us == 0 {
+                (event_fps, event_fps, true)
+            } else {
+                let inhibit_fps = 10_000.0 / pdo.inhibit_100us as f64;
+                (event_fps, inhibit_fps.max(event_fps), false)
+            }
+        }
+        _ => (0.0, 0.0, false),
+    };
+    FrameLoad {
+        node_id: node.node_id,
+        kind: CobIdKind::Tpdo { number: pdo.number },
+        cob_id: pdo.cob_id,
+        extended: pdo.extended,
+        payload_bytes,
+        nominal_fps,
+        worst_fps,
+        unbounded,
+    }
+}
+
+impl Network {
+    //Configured bit rates of the nodes, or the ones every node supports
+    pub fn get_baudrates(&self) -> Vec<u16> {
+        let mut configured: Vec<u16> = self
+            .nodes
+            .iter()
+            .map(|n| n.baudrate)
+            .filter(|b| *b != 0)
+            .collect();
+        configured.sort();
+        configured.dedup();
+        if !configured.is_empty() {
+            return configured;
+        }
+        let mut nodes = self.nodes.iter();
+        let Some(first) = nodes.next() else {
+            return Vec::new();
+        };
+        let mut common = first.file.device_info.get_baudrates();
+        for node in nodes {
+            let supported = node.file.device_info.get_baudrates();
+            common.retain(|b| supported.contains(b));
+        }
+        common
+    }
+
+    fn get_sync_params(&self) -> Result<Option<(u8, SyncParams)>, CommParamError> {
+        for node in &self.nodes {
+            if node.file.get_index_object(0x1005).is_none() {
+                continue;
+            }
+            let sync = SyncParams::read(&node.file, node.node_id)?;
+            if sync.generator {
+                return Ok(Some((node.node_id, sync)));
+            }
+        }
+        Ok(None)
+    }
+
+    pub fn estimate_bus_load(&self) -> Result<BusLoad, CommParamError> {
+        let sync = self.get_sync_params()?;
+        let sync_period_us = sync.as_ref().map(|(_, s)| s.cycle_period_us).unwrap_or(0);
+        let mut frames = Vec::new();
+        if let Some((node_id, sync)) = &sync
+            && sync.cycle_period_us != 0
+        {
+            let fps = 1_000_000.0 / sync.cycle_period_us as f64;
+            frames.push(FrameLoad {
+                node_id: *node_id,
+                kind: CobIdKind::Sync,
+                cob_id: sync.cob_id,
+                extended: sync.extended,
+                payload_bytes: if sync.counter_overflow == 0 { 0 } else { 1 },
+                nominal_fps: fps,
+                worst_fps: fps,
+                unbounded: false,
+            });
+        }
+        for node in &self.nodes {
+            let heartbeat = Address::new(HEARTBEAT_TIME_INDEX, 0);
+            let heartbeat_ms = read_default(&node.file, &heartbeat, node.node_id)?.unwrap_or(0);
+            if heartbeat_ms != 0 {
+                let fps = 1000.0 / heartbeat_ms as f64;
+                frames.push(FrameLoad {
+                    node_id: node.node_id,
+                    kind: CobIdKind::Heartbeat,
+                    cob_id: HEARTBEAT_COB_ID_BASE + node.node_id as u32,
+                    extended: false,
+                    payload_bytes: 1,
+                    nominal_fps: fps,
+                    worst_fps: fps,
+                    unbounded: false,
+                });
+            }
+            for pdo in PdoCommParams::read_all(&node.file, node.node_id)? {
+                if pdo.valid && pdo.direction == PdoDirection::Transmit {
+                    frames.push(pdo_frame_load(node, &pdo, sync_period_us));
+                }
+            }
+        }
+        let mut out = BusLoad {
+            sync_period_us,
+            frames,
+            utilization: Vec::new(),
+        };
+        out.utilization = self
+            .get_baudrates()
+            .iter()
+            .map(|b| out.get_utilization(*b))
+            .collect();
+        Ok(out)
+    }
+}
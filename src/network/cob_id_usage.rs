@@ -0,0 +1,77 @@
+use std::fmt::Display;
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum CobIdKind {
+    Sync,
+    Time,
+    Emcy,
+    SdoRx { number: u16 },
+    SdoTx { number: u16 },
+    Rpdo { number: u16 },
+    Tpdo { number: u16 },
+    Heartbeat,
+}
+
+impl Display for CobIdKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self {
+            CobIdKind::Sync => write!(f, "SYNC"),
+            CobIdKind::Time => write!(f, "TIME"),
+            CobIdKind::Emcy => write!(f, "EMCY"),
+            CobIdKind::SdoRx { number } => write!(f, "SDO{} rx", number),
+            CobIdKind::SdoTx { number } => write!(f, "SDO{} tx", number),
+            CobIdKind::Rpdo { number } => write!(f, "RPDO{}", number),
+            CobIdKind::Tpdo { number } => write!(f, "TPDO{}", number),
+            CobIdKind::Heartbeat => write!(f, "Heartbeat"),
+        }
+    }
+}
+
+impl CobIdKind {
+    //Only configurable COB-IDs have to stay out of the restricted ranges
+    pub fn is_configurable(&self) -> bool {
+        !matches!(
+            self,
+            CobIdKind::Heartbeat | CobIdKind::SdoRx { number: 1 } | CobIdKind::SdoTx { number: 1 }
+        )
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct CobIdUsage {
+    pub node_id: u8,
+    pub kind: CobIdKind,
+    pub cob_id: u32,
+    pub extended: bool,
+    //Consumers may share a COB-ID with its producer, everything else must be unique
+    pub consumer: bool,
+}
+
+impl Display for CobIdUsage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} of node {} on COB-ID 0x{:X}",
+            self.kind, self.node_id, self.cob_id
+        )
+    }
+}
+
+//Restricted CAN identifiers from CiA 301, only relevant for 11 bit identifiers
+pub const RESTRICTED_COB_IDS: [(u32, u32); 8] = [
+    (0x000, 0x000),
+    (0x001, 0x07F),
+    (0x101, 0x180),
+    (0x581, 0x5FF),
+    (0x601, 0x67F),
+    (0x6E0, 0x6FF),
+    (0x701, 0x77F),
+    (0x780, 0x7FF),
+];
+
+pub fn is_restricted_cob_id(cob_id: u32, extended: bool) -> bool {
+    !extended
+        && RESTRICTED_COB_IDS
+            .iter()
+            .any(|(start, end)| (*start..=*end).contains(&cob_id))
+}
@@ -0,0 +1,99 @@
+mod bus_load;
+mod cob_id_usage;
+mod network_issue;
+mod network_node;
+
+#[cfg(test)]
+mod tests;
+
+pub use bus_load::*;
+pub use cob_id_usage::*;
+pub use network_issue::NetworkIssue;
+pub use network_node::*;
+
+use crate::CommParamError;
+use crate::dcf::DCFFile;
+use crate::eds_file::EDSFile;
+
+#[derive(Debug, Clone, Default)]
+pub struct Network {
+    pub nodes: Vec<NetworkNode>,
+}
+
+impl Network {
+    pub fn new() -> Network {
+        Network { nodes: Vec::new() }
+    }
+
+    pub fn add_eds(&mut self, eds: &EDSFile, node_id: u8) {
+        self.add_dcf(&DCFFile::from_eds(eds.clone(), node_id));
+    }
+
+    pub fn add_dcf(&mut self, dcf: &DCFFile) {
+        let name = if dcf.commissioning.node_name.is_empty() {
+            dcf.eds.device_info.product_name.clone()
+        } else {
+            dcf.commissioning.node_name.clone()
+        };
+        self.nodes.push(NetworkNode {
+            node_id: dcf.get_node_id(),
+            name,
+            baudrate: dcf.commissioning.baudrate,
+            file: dcf.get_configured_eds(),
+        });
+    }
+
+    pub fn get_node(&self, node_id: u8) -> Option<&NetworkNode> {
+        self.nodes.iter().find(|n| n.node_id == node_id)
+    }
+
+    pub fn get_cob_id_usages(&self) -> Result<Vec<CobIdUsage>, CommParamError> {
+        let mut out = Vec::new();
+        for node in &self.nodes {
+            out.extend(node.get_cob_id_usages()?);
+        }
+        Ok(out)
+    }
+
+    pub fn check(&self) -> Result<Vec<NetworkIssue>, CommParamError> {
+        let mut issues = Vec::new();
+        for (i, node) in self.nodes.iter().enumerate() {
+            if !(1..=127).contains(&node.node_id) {
+                issues.push(NetworkIssue::InvalidNodeId {
+                    node_id: node.node_id,
+                });
+            }
+            let previous = self.nodes[..i].iter().filter(|n| n.node_id == node.node_id);
+            if previous.count() == 1 {
+                issues.push(NetworkIssue::DuplicateNodeId {
+                    node_id: node.node_id,
+                });
+            }
+        }
+
+        let usages = self.get_cob_id_usages()?;
+        for usage in &usages {
+            if usage.kind.is_configurable() && is_restricted_cob_id(usage.cob_id, usage.extended) {
+                issues.push(NetworkIssue::RestrictedCobId {
+                    usage: usage.clone(),
+                });
+            }
+        }
+        let producers: Vec<&CobIdUsage> = usages.iter().filter(|u| !u.consumer).collect();
+        for (i, usage) in producers.iter().enumerate() {
+            let same = |o: &&&CobIdUsage| o.cob_id == usage.cob_id && o.extended == usage.extended;
+            if producers[..i].iter().any(|o| same(&o)) {
+                continue;
+            }
+            let colliding: Vec<CobIdUsage> = producers[i..]
+                .iter()
+                .filter(|o| same(o))
+                .map(|o| (*o).clone())
+                .collect();
+            if colliding.len() > 1 {
+                issues.push(NetworkIssue::CobIdCollision { usages: colliding });
+            }
+        }
+        Ok(issues)
+    }
+}
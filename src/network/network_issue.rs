@@ -0,0 +1,29 @@
+use std::fmt::Display;
+
+use super::CobIdUsage;
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum NetworkIssue {
+    InvalidNodeId { node_id: u8 },
+    DuplicateNodeId { node_id: u8 },
+    CobIdCollision { usages: Vec<CobIdUsage> },
+    RestrictedCobId { usage: CobIdUsage },
+}
+
+impl Display for NetworkIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self {
+            Self::InvalidNodeId { node_id } => write!(f, "Invalid node id {}", node_id),
+            Self::DuplicateNodeId { node_id } => {
+                write!(f, "Node id {} is used by multiple nodes", node_id)
+            }
+            Self::CobIdCollision { usages } => {
+                let list: Vec<String> = usages.iter().map(|u| u.to_string()).collect();
+                write!(f, "COB-ID collision between {}", list.join(", "))
+            }
+            Self::RestrictedCobId { usage } => {
+                write!(f, "Restricted COB-ID used by {}", usage)
+            }
+        }
+    }
+}
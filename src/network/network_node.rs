@@ -0,0 +1,78 @@
+use super::{CobIdKind, CobIdUsage};
+use crate::CommParamError;
+use crate::comm::{EmcyParams, SdoServerParams, SyncParams, TimeParams};
+use crate::eds_file::EDSFile;
+use crate::pdo::{PdoCommParams, PdoDirection};
+
+pub const HEARTBEAT_COB_ID_BASE: u32 = 0x700;
+
+#[derive(Debug, Clone)]
+pub struct NetworkNode {
+    pub node_id: u8,
+    pub name: String,
+    //Configured bit rate in kbit/s, 0 if not commissioned
+    pub baudrate: u16,
+    //EDS with all configured values applied and node ids resolved
+    pub file: EDSFile,
+}
+
+impl NetworkNode {
+    fn usage(&self, kind: CobIdKind, cob_id: u32, extended: bool, consumer: bool) -> CobIdUsage {
+        CobIdUsage {
+            node_id: self.node_id,
+            kind,
+            cob_id,
+            extended,
+            consumer,
+        }
+    }
+
+    pub fn get_cob_id_usages(&self) -> Result<Vec<CobIdUsage>, CommParamError> {
+        let file = &self.file;
+        let node_id = self.node_id;
+        let mut out = vec![self.usage(
+            CobIdKind::Heartbeat,
+            HEARTBEAT_COB_ID_BASE + node_id as u32,
+            false,
+            false,
+        )];
+        for sdo in SdoServerParams::read_all(file, node_id)? {
+            let number = sdo.number;
+            if sdo.rx_valid {
+                let kind = CobIdKind::SdoRx { number };
+                out.push(self.usage(kind, sdo.rx_cob_id, sdo.rx_extended, false));
+            }
+            if sdo.tx_valid {
+                let kind = CobIdKind::SdoTx { number };
+                out.push(self.usage(kind, sdo.tx_cob_id, sdo.tx_extended, false));
+            }
+        }
+        if file.get_index_object(0x1014).is_some() {
+            let emcy = EmcyParams::read(file, node_id)?;
+            if emcy.valid {
+                out.push(self.usage(CobIdKind::Emcy, emcy.cob_id, emcy.extended, false));
+            }
+        }
+        if file.get_index_object(0x1005).is_some() {
+            let sync = SyncParams::read(file, node_id)?;
+            out.push(self.usage(CobIdKind::Sync, sync.cob_id, sync.extended, !sync.generator));
+        }
+        if file.get_index_object(0x1012).is_some() {
+            let time = TimeParams::read(file, node_id)?;
+            if time.producer || time.consumer {
+                out.push(self.usage(CobIdKind::Time, time.cob_id, time.extended, !time.producer));
+            }
+        }
+        for pdo in PdoCommParams::read_all(file, node_id)? {
+            if !pdo.valid {
+                continue;
+            }
+            let (kind, consumer) = match pdo.direction {
+                PdoDirection::Receive => (CobIdKind::Rpdo { number: pdo.number }, true),
+                PdoDirection::Transmit => (CobIdKind::Tpdo { number: pdo.number }, false),
+            };
+            out.push(self.usage(kind, pdo.cob_id, pdo.extended, consumer));
+        }
+        Ok(out)
+    }
+}
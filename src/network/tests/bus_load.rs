@@ -0,0 +1,56 @@
+use crate::load_file;
+use crate::network::{CobIdKind, Network, can_frame_bits, can_max_stuff_bits};
+use crate::tests::utils::*;
+
+#[test]
+fn test_frame_bits() {
+    assert_eq!(can_frame_bits(0, false), 47);
+    assert_eq!(can_frame_bits(8, false) + can_max_stuff_bits(8, false), 135);
+    assert_eq!(can_frame_bits(8, true) + can_max_stuff_bits(8, true), 160);
+}
+
+#[test]
+fn test_bus_load() {
+    let dcf = make_dcf_file(&PDO_DCF.to_vec()).expect("Failed to parse dcf file!");
+    let mut network = Network::new();
+    network.add_dcf(&dcf);
+    let load = network
+        .estimate_bus_load()
+        .expect("Failed to estimate bus load!");
+    assert_eq!(load.sync_period_us, 10000);
+    assert_eq!(load.frames.len(), 3);
+    let tpdo = load
+        .frames
+        .iter()
+        .find(|f| f.kind == CobIdKind::Tpdo { number: 1 })
+        .expect("Missing TPDO1!");
+    assert_eq!(tpdo.cob_id, 0x182);
+    assert_eq!(tpdo.payload_bytes, 2);
+    assert_eq!(tpdo.nominal_fps, 50.0);
+    assert_eq!(tpdo.get_worst_frame_bits(), 75);
+
+    assert_eq!(load.get_nominal_fps(), 160.0);
+    assert_eq!(load.get_nominal_bps(), 8400.0);
+    assert_eq!(load.get_worst_bps(), 9900.0);
+    assert_eq!(load.get_node_nominal_bps(2), 8400.0);
+    assert!(!load.is_unbounded());
+    assert_eq!(load.utilization.len(), 1);
+    assert_eq!(load.utilization[0].baudrate, 500);
+    assert!((load.utilization[0].worst_percent - 1.98).abs() < 1e-9);
+}
+
+#[test]
+fn test_sample_bus_load() {
+    let efile = load_file("res/micro-motor.eds").expect("Failed to load sample file!");
+    let mut network = Network::new();
+    network.add_eds(&efile, 5);
+    network.add_eds(&efile, 6);
+    let load = network
+        .estimate_bus_load()
+        .expect("Failed to estimate bus load!");
+    //Both enabled TPDOs are event driven without inhibit time
+    assert_eq!(load.get_node_frames(6).len(), 2);
+    assert!(load.is_unbounded());
+    assert_eq!(load.get_nominal_bps(), 0.0);
+    assert_eq!(network.get_baudrates(), efile.device_info.get_baudrates());
+}
@@ -0,0 +1,2 @@
+mod bus_load;
+mod network;
@@ -0,0 +1,146 @@
+use crate::eds_file::{Address, NodeIdOffset};
+use crate::load_file;
+use crate::network::{CobIdKind, Network, NetworkIssue, is_restricted_cob_id};
+use crate::tests::utils::*;
+
+const TPDO_DCF: [&str; 35] = [
+    "[DeviceComissioning]",
+    "NodeID=3",
+    "NodeName=Left Wheel",
+    "Baudrate=500",
+    "[MandatoryObjects]",
+    "SupportedObjects=0",
+    "[OptionalObjects]",
+    "SupportedObjects=1",
+    "1=0x1800",
+    "[ManufacturerObjects]",
+    "SupportedObjects=0",
+    "[1800]",
+    "ParameterName=TPDO1 communication parameter",
+    "ObjectType=0x9",
+    "SubNumber=3",
+    "[1800sub0]",
+    "ParameterName=Highest sub-index supported",
+    "ObjectType=0x7",
+    "DataType=0x0005",
+    "AccessType=const",
+    "DefaultValue=2",
+    "[1800sub1]",
+    "ParameterName=COB-ID used by TPDO",
+    "ObjectType=0x7",
+    "DataType=0x0007",
+    "AccessType=rw",
+    "DefaultValue=$NODEID+0x180",
+    "ParameterValue=0x185",
+    "[1800sub2]",
+    "ParameterName=Transmission type",
+    "ObjectType=0x7",
+    "DataType=0x0005",
+    "AccessType=rw",
+    "DefaultValue=0xFF",
+    "",
+];
+
+#[test]
+fn test_restricted_cob_ids() {
+    assert!(is_restricted_cob_id(0x000, false));
+    assert!(is_restricted_cob_id(0x581, false));
+    assert!(is_restricted_cob_id(0x7FF, false));
+    assert!(!is_restricted_cob_id(0x080, false));
+    assert!(!is_restricted_cob_id(0x185, false));
+    assert!(!is_restricted_cob_id(0x700, false));
+    assert!(!is_restricted_cob_id(0x000, true));
+}
+
+#[test]
+fn test_sample_usages() {
+    let efile = load_file("res/micro-motor.eds").expect("Failed to load sample file!");
+    let mut network = Network::new();
+    network.add_eds(&efile, 5);
+    let usages = network
+        .get_cob_id_usages()
+        .expect("Failed to resolve cob ids!");
+    let find = |kind: CobIdKind| usages.iter().find(|u| u.kind == kind);
+    assert_eq!(find(CobIdKind::Heartbeat).map(|u| u.cob_id), Some(0x705));
+    assert_eq!(
+        find(CobIdKind::SdoRx { number: 1 }).map(|u| u.cob_id),
+        Some(0x605)
+    );
+    assert_eq!(find(CobIdKind::Emcy).map(|u| u.cob_id), Some(0x85));
+    assert_eq!(
+        find(CobIdKind::Tpdo { number: 2 }).map(|u| u.cob_id),
+        Some(0x285)
+    );
+    assert!(find(CobIdKind::Rpdo { number: 1 }).is_some_and(|u| u.consumer));
+    //TPDO3 and TPDO4 are disabled in the sample
+    assert!(find(CobIdKind::Tpdo { number: 3 }).is_none());
+    assert!(find(CobIdKind::Time).is_none());
+
+    let issues = network.check().expect("Failed to check network!");
+    assert_eq!(issues.len(), 1);
+    assert!(matches!(
+        &issues[0],
+        NetworkIssue::RestrictedCobId { usage } if usage.kind == CobIdKind::Sync
+    ));
+}
+
+#[test]
+fn test_node_id_issues() {
+    let efile = load_file("res/micro-motor.eds").expect("Failed to load sample file!");
+    let mut network = Network::new();
+    network.add_eds(&efile, 5);
+    network.add_eds(&efile, 5);
+    network.add_eds(&efile, 5);
+    network.add_eds(&efile, 0);
+    let issues = network.check().expect("Failed to check network!");
+    let duplicates: Vec<&NetworkIssue> = issues
+        .iter()
+        .filter(|i| matches!(i, NetworkIssue::DuplicateNodeId { node_id: 5 }))
+        .collect();
+    assert_eq!(duplicates.len(), 1);
+    assert!(issues.contains(&NetworkIssue::InvalidNodeId { node_id: 0 }));
+    //Every producing COB-ID of node 5 collides three times
+    assert!(issues.iter().any(|i| matches!(
+        i,
+        NetworkIssue::CobIdCollision { usages } if usages.len() == 3 && usages[0].cob_id == 0x705
+    )));
+}
+
+#[test]
+fn test_dcf_collision() {
+    let dcf = make_dcf_file(&TPDO_DCF.to_vec()).expect("Failed to parse dcf file!");
+    assert_eq!(dcf.get_node_id(), 3);
+    assert_eq!(dcf.commissioning.node_name, "Left Wheel");
+    let addr = Address::new(0x1800, 1);
+    assert_eq!(
+        dcf.eds
+            .get_object(&addr)
+            .and_then(|o| o.get_default())
+            .and_then(|v| v.as_any().downcast_ref::<NodeIdOffset>()),
+        Some(&NodeIdOffset { offset: 0x180 })
+    );
+    assert_eq!(
+        dcf.get_value(&addr)
+            .and_then(|v| v.as_any().downcast_ref::<u32>()),
+        Some(&0x185)
+    );
+
+    let efile = load_file("res/micro-motor.eds").expect("Failed to load sample file!");
+    let mut network = Network::new();
+    network.add_eds(&efile, 5);
+    network.add_dcf(&dcf);
+    assert_eq!(
+        network.get_node(3).map(|n| n.name.as_str()),
+        Some("Left Wheel")
+    );
+    let issues = network.check().expect("Failed to check network!");
+    let collisions: Vec<&NetworkIssue> = issues
+        .iter()
+        .filter(|i| matches!(i, NetworkIssue::CobIdCollision { .. }))
+        .collect();
+    assert_eq!(collisions.len(), 1);
+    assert_eq!(
+        collisions[0].to_string(),
+        "COB-ID collision between TPDO1 of node 5 on COB-ID 0x185, TPDO1 of node 3 on COB-ID 0x185"
+    );
+}
@@ -0,0 +1,334 @@
+mod value_change;
+
+#[cfg(test)]
+mod tests;
+
+pub use value_change::*;
+
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::ops::RangeInclusive;
+
+use crate::ObjectDictionaryError;
+use crate::dcf::DCFFile;
+use crate::eds_file::{
+    Address, DataType, EDSFile, EDSObject, EDSValue, NodeIdOffset, eds_value_compare,
+    eds_value_from_bytes, eds_value_matches_type, eds_value_to_bytes,
+};
+use crate::sdo::{SdoAbortCode, SdoObjectStore};
+
+//Runtime values of a device built from its static EDS description, writes are
+//checked against the data type, access mode and limits of each object
+pub struct ObjectDictionary {
+    file: EDSFile,
+    node_id: u8,
+    values: HashMap<Address, Box<dyn EDSValue>>,
+    subscribers: Vec<Subscriber>,
+    next_id: SubscriptionId,
+}
+
+impl Debug for ObjectDictionary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ObjectDictionary")
+            .field("node_id", &self.node_id)
+            .field("values", &self.values)
+            .field("subscribers", &self.subscribers.len())
+            .finish()
+    }
+}
+
+//Resolves node id relative values for the given node
+fn resolve(value: &dyn EDSValue, data_type: &DataType, node_id: u8) -> Box<dyn EDSValue> {
+    match value.as_any().downcast_ref::<NodeIdOffset>() {
+        Some(offset) => offset
+            .resolve_value(node_id, data_type)
+            .unwrap_or_else(|| value.clone_box()),
+        None => value.clone_box(),
+    }
+}
+
+//Collects the value of every variable, compact arrays store a single value
+//under their own address
+fn collect_values(file: &EDSFile) -> HashMap<Address, Box<dyn EDSValue>> {
+    let mut out = HashMap::new();
+    for obj in file.get_objects() {
+        let subs: Vec<&EDSObject> = match obj.get_entries() {
+            Some(entries) => entries.iter().collect(),
+            None => vec![obj],
+        };
+        for sub in subs {
+            if let Some(value) = sub.get_default() {
+                out.insert(*sub.get_address(), value.clone_box());
+            }
+        }
+    }
+    out
+}
+
+impl ObjectDictionary {
+    pub fn new(file: &EDSFile, node_id: u8) -> ObjectDictionary {
+        Self::from_dcf(&DCFFile::from_eds(file.clone(), node_id))
+    }
+
+    //Starts from the configured ParameterValues of the DCF
+    pub fn from_dcf(dcf: &DCFFile) -> ObjectDictionary {
+        ObjectDictionary {
+            file: dcf.eds.clone(),
+            node_id: dcf.get_node_id(),
+            values: collect_values(&dcf.get_configured_eds()),
+            subscribers: Vec::new(),
+            next_id: 0,
+        }
+    }
+
+    pub fn get_file(&self) -> &EDSFile {
+        &self.file
+    }
+
+    pub fn get_node_id(&self) -> u8 {
+        self.node_id
+    }
+
+    //Returns the EDS with every default replaced by the current value
+    pub fn to_eds_file(&self) -> EDSFile {
+        let mut out = self.file.clone();
+        for (addr, value) in &self.values {
+            if let Some(obj) = out.get_object_mut(addr)
+                && obj.get_address() == addr
+            {
+                obj.set_default(Some(value.clone_box()));
+            }
+        }
+        out
+    }
+
+    pub fn get(&self, addr: &Address) -> Option<&dyn EDSValue> {
+        let obj = self.file.get_object(addr)?;
+        self.values
+            .get(addr)
+            .or_else(|| self.values.get(obj.get_address()))
+            .map(|v| v.as_ref())
+    }
+
+    pub fn get_as<T: Clone + 'static>(&self, addr: &Address) -> Option<T> {
+        self.get(addr)?.as_any().downcast_ref::<T>().cloned()
+    }
+
+    fn find_object(&self, addr: &Address) -> Result<&EDSObject, ObjectDictionaryError> {
+        self.file
+            .get_object(addr)
+            .ok_or(ObjectDictionaryError::ObjectDoesNotExist { addr: *addr })
+    }
+
+    //Reads a value the way a remote client would, honouring the access mode
+    pub fn read_value(&self, addr: &Address) -> Result<&dyn EDSValue, ObjectDictionaryError> {
+        let obj = self.find_object(addr)?;
+        if !obj.get_access_mode().is_some_and(|m| m.is_readable()) {
+            return Err(ObjectDictionaryError::NotReadable { addr: *addr });
+        }
+        self.get(addr)
+            .ok_or(ObjectDictionaryError::NoValue { addr: *addr })
+    }
+
+    //Writes a value the way a remote client would, honouring the access mode
+    pub fn write_value(
+        &mut self,
+        addr: &Address,
+        value: Box<dyn EDSValue>,
+    ) -> Result<(), ObjectDictionaryError> {
+        let obj = self.find_object(addr)?;
+        if !obj.get_access_mode().is_some_and(|m| m.is_writable()) {
+            return Err(ObjectDictionaryError::NotWritable { addr: *addr });
+        }
+        self.set(addr, value)
+    }
+
+    //Sets a value from the application side, read only objects may be changed
+    //but data type and limits are still enforced
+    pub fn set(
+        &mut self,
+        addr: &Address,
+        value: Box<dyn EDSValue>,
+    ) -> Result<(), ObjectDictionaryError> {
+        let value = self.check_value(addr, value)?;
+        let old_value = self.get(addr).map(|v| v.clone_box());
+        self.values.insert(*addr, value);
+        self.notify(addr, old_value);
+        Ok(())
+    }
+
+    fn check_value(
+        &self,
+        addr: &Address,
+        value: Box<dyn EDSValue>,
+    ) -> Result<Box<dyn EDSValue>, ObjectDictionaryError> {
+        let obj = self.find_object(addr)?;
+        let data_type = obj
+            .get_data_type()
+            .ok_or(ObjectDictionaryError::NotWritable { addr: *addr })?;
+        let value = resolve(value.as_ref(), data_type, self.node_id);
+        if !eds_value_matches_type(value.as_ref(), data_type) {
+            return Err(ObjectDictionaryError::TypeMismatch {
+                addr: *addr,
+                data_type: data_type.clone(),
+            });
+        }
+        let (low, high) = obj.get_limits();
+        if let Some(low) = low
+            && eds_value_compare(
+                value.as_ref(),
+                resolve(low, data_type, self.node_id).as_ref(),
+                data_type,
+            ) == Some(Ordering::Less)
+        {
+            return Err(ObjectDictionaryError::ValueTooLow { addr: *addr });
+        }
+        if let Some(high) = high
+            && eds_value_compare(
+                value.as_ref(),
+                resolve(high, data_type, self.node_id).as_ref(),
+                data_type,
+            ) == Some(Ordering::Greater)
+        {
+            return Err(ObjectDictionaryError::ValueTooHigh { addr: *addr });
+        }
+        Ok(value)
+    }
+
+    fn is_same_value(
+        &self,
+        addr: &Address,
+        a: Option<&dyn EDSValue>,
+        b: Option<&dyn EDSValue>,
+    ) -> bool {
+        let data_type = self.file.get_object(addr).and_then(|o| o.get_data_type());
+        match (a, b, data_type) {
+            (None, None, _) => true,
+            (Some(a), Some(b), Some(data_type)) => {
+                let a = eds_value_to_bytes(a, data_type);
+                a.is_some() && a == eds_value_to_bytes(b, data_type)
+            }
+            _ => false,
+        }
+    }
+
+    fn notify(&mut self, addr: &Address, old_value: Option<Box<dyn EDSValue>>) {
+        let new_value = self.get(addr).map(|v| v.clone_box());
+        if self.is_same_value(addr, old_value.as_deref(), new_value.as_deref()) {
+            return;
+        }
+        let change = ValueChange {
+            address: *addr,
+            old_value,
+            new_value,
+        };
+        for s in &mut self.subscribers {
+            if s.address.is_none_or(|a| a == *addr) {
+                (s.callback)(&change);
+            }
+        }
+    }
+
+    //Restores all values to the EDS defaults
+    pub fn reset_defaults(&mut self) {
+        self.reset_range(0..=0xFFFF, None);
+    }
+
+    //Restores all values to the ParameterValues of the DCF
+    pub fn reset_from_dcf(&mut self, dcf: &DCFFile) {
+        self.reset_range(0..=0xFFFF, Some(dcf));
+    }
+
+    //Restores the objects with an index in range to the ParameterValues of the
+    //DCF or the EDS defaults if none is given
+    pub fn reset_range(&mut self, range: RangeInclusive<u16>, dcf: Option<&DCFFile>) {
+        let initial = match dcf {
+            Some(dcf) => collect_values(&dcf.get_configured_eds()),
+            None => collect_values(
+                &DCFFile::from_eds(self.file.clone(), self.node_id).get_configured_eds(),
+            ),
+        };
+        let mut addrs: Vec<Address> = self
+            .values
+            .keys()
+            .chain(initial.keys())
+            .filter(|a| range.contains(&a.index) && self.file.get_object(a).is_some())
+            .copied()
+            .collect();
+        addrs.sort_by_key(|a| (a.index, a.subindex));
+        addrs.dedup();
+
+        let old_values: Vec<Option<Box<dyn EDSValue>>> = addrs
+            .iter()
+            .map(|a| self.get(a).map(|v| v.clone_box()))
+            .collect();
+        self.values.retain(|a, _| !range.contains(&a.index));
+        for (addr, value) in initial {
+            if range.contains(&addr.index) {
+                self.values.insert(addr, value);
+            }
+        }
+        for (addr, old_value) in addrs.iter().zip(old_values) {
+            self.notify(addr, old_value);
+        }
+    }
+
+    //Calls back on every change of any value
+    pub fn subscribe(&mut self, callback: Box<dyn FnMut(&ValueChange) + Send>) -> SubscriptionId {
+        self.add_subscriber(None, callback)
+    }
+
+    //Calls back on every change of the value at the given address
+    pub fn subscribe_to(
+        &mut self,
+        addr: &Address,
+        callback: Box<dyn FnMut(&ValueChange) + Send>,
+    ) -> SubscriptionId {
+        self.add_subscriber(Some(*addr), callback)
+    }
+
+    fn add_subscriber(
+        &mut self,
+        address: Option<Address>,
+        callback: Box<dyn FnMut(&ValueChange) + Send>,
+    ) -> SubscriptionId {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.subscribers.push(Subscriber {
+            id,
+            address,
+            callback,
+        });
+        id
+    }
+
+    pub fn unsubscribe(&mut self, id: SubscriptionId) -> bool {
+        let count = self.subscribers.len();
+        self.subscribers.retain(|s| s.id != id);
+        self.subscribers.len() != count
+    }
+}
+
+impl SdoObjectStore for ObjectDictionary {
+    fn read(&mut self, addr: &Address) -> Result<Vec<u8>, SdoAbortCode> {
+        let value = self.read_value(addr).map_err(|e| e.get_abort_code())?;
+        let data_type = self
+            .find_object(addr)
+            .map_err(|e| e.get_abort_code())?
+            .get_data_type()
+            .ok_or(SdoAbortCode::UnsupportedAccess)?;
+        eds_value_to_bytes(value, data_type).ok_or(SdoAbortCode::NoDataAvailable)
+    }
+
+    fn write(&mut self, addr: &Address, data: &[u8]) -> Result<(), SdoAbortCode> {
+        let data_type = self
+            .find_object(addr)
+            .map_err(|e| e.get_abort_code())?
+            .get_data_type()
+            .ok_or(SdoAbortCode::UnsupportedAccess)?;
+        let value = eds_value_from_bytes(data, data_type).ok_or(SdoAbortCode::InvalidValue)?;
+        self.write_value(addr, value)
+            .map_err(|e| e.get_abort_code())
+    }
+}
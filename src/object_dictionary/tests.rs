@@ -0,0 +1,187 @@
+use std::sync::{Arc, Mutex};
+
+use crate::ObjectDictionaryError;
+use crate::eds_file::{Address, DataType, EDSFile, NodeIdOffset};
+use crate::object_dictionary::{ObjectDictionary, ValueChange};
+use crate::sdo::{SdoAbortCode, SdoObjectStore};
+use crate::tests::utils::*;
+
+fn make_dictionary_file() -> EDSFile {
+    let lines = vec![
+        "[MandatoryObjects]",
+        "SupportedObjects=0",
+        "[OptionalObjects]",
+        "SupportedObjects=0",
+        "[ManufacturerObjects]",
+        "SupportedObjects=3",
+        "1=0x2000",
+        "2=0x2001",
+        "3=0x2002",
+        "[2000]",
+        "ParameterName=Speed",
+        "ObjectType=0x7",
+        "DataType=0x0003",
+        "AccessType=rw",
+        "DefaultValue=0",
+        "LowLimit=-100",
+        "HighLimit=100",
+        "[2001]",
+        "ParameterName=Serial",
+        "ObjectType=0x7",
+        "DataType=0x0007",
+        "AccessType=ro",
+        "DefaultValue=0x1234",
+        "[2002]",
+        "ParameterName=Command",
+        "ObjectType=0x7",
+        "DataType=0x0005",
+        "AccessType=wo",
+    ];
+    make_eds_file(&lines).expect("Failed to parse eds file!")
+}
+
+#[test]
+fn test_typed_writes() {
+    let mut dict = ObjectDictionary::new(&make_dictionary_file(), 3);
+    let speed = Address::new(0x2000, 0);
+    let serial = Address::new(0x2001, 0);
+    let command = Address::new(0x2002, 0);
+
+    assert_eq!(dict.get_as::<i16>(&speed), Some(0));
+    assert_eq!(dict.write_value(&speed, Box::new(42i16)), Ok(()));
+    assert_eq!(dict.get_as::<i16>(&speed), Some(42));
+    assert_eq!(
+        dict.write_value(&speed, Box::new(42u16)),
+        Err(ObjectDictionaryError::TypeMismatch {
+            addr: speed,
+            data_type: DataType::Int16
+        })
+    );
+    assert_eq!(
+        dict.write_value(&speed, Box::new(101i16)),
+        Err(ObjectDictionaryError::ValueTooHigh { addr: speed })
+    );
+    assert_eq!(
+        dict.write_value(&speed, Box::new(-101i16)),
+        Err(ObjectDictionaryError::ValueTooLow { addr: speed })
+    );
+    assert_eq!(dict.get_as::<i16>(&speed), Some(42));
+
+    //Read only objects can only be changed from the application side
+    assert_eq!(
+        dict.write_value(&serial, Box::new(1u32)),
+        Err(ObjectDictionaryError::NotWritable { addr: serial })
+    );
+    assert_eq!(dict.set(&serial, Box::new(1u32)), Ok(()));
+    assert_eq!(dict.get_as::<u32>(&serial), Some(1));
+
+    assert_eq!(
+        dict.read_value(&command).err(),
+        Some(ObjectDictionaryError::NotReadable { addr: command })
+    );
+    assert_eq!(
+        dict.write_value(&Address::new(0x3000, 0), Box::new(0u8)),
+        Err(ObjectDictionaryError::ObjectDoesNotExist {
+            addr: Address::new(0x3000, 0)
+        })
+    );
+
+    //SDO access reports abort codes
+    assert_eq!(
+        SdoObjectStore::read(&mut dict, &serial),
+        Ok(vec![1, 0, 0, 0])
+    );
+    assert_eq!(
+        SdoObjectStore::write(&mut dict, &speed, &[0xFF, 0x00]),
+        Err(SdoAbortCode::ValueTooHigh)
+    );
+    assert_eq!(
+        SdoObjectStore::write(&mut dict, &serial, &[0, 0, 0, 0]),
+        Err(SdoAbortCode::WriteReadOnly)
+    );
+    assert_eq!(SdoObjectStore::write(&mut dict, &command, &[7]), Ok(()));
+    assert_eq!(dict.get_as::<u8>(&command), Some(7));
+}
+
+#[test]
+fn test_subscriptions() {
+    let mut dict = ObjectDictionary::new(&make_dictionary_file(), 3);
+    let speed = Address::new(0x2000, 0);
+    let serial = Address::new(0x2001, 0);
+    let all: Arc<Mutex<Vec<ValueChange>>> = Arc::new(Mutex::new(Vec::new()));
+    let speed_changes = Arc::new(Mutex::new(0));
+
+    let recorder = all.clone();
+    let id = dict.subscribe(Box::new(move |change| {
+        recorder
+            .lock()
+            .expect("Failed to lock!")
+            .push(change.clone());
+    }));
+    let counter = speed_changes.clone();
+    dict.subscribe_to(
+        &speed,
+        Box::new(move |_| {
+            *counter.lock().expect("Failed to lock!") += 1;
+        }),
+    );
+
+    dict.set(&speed, Box::new(5i16))
+        .expect("Failed to set value!");
+    //Writing the same value again is not a change
+    dict.set(&speed, Box::new(5i16))
+        .expect("Failed to set value!");
+    dict.set(&serial, Box::new(7u32))
+        .expect("Failed to set value!");
+    assert!(dict.set(&speed, Box::new(500i16)).is_err());
+
+    {
+        let all = all.lock().expect("Failed to lock!");
+        assert_eq!(all.len(), 2);
+        assert_eq!(all[0].address, speed);
+        let old = all[0].old_value.as_ref().expect("Missing old value!");
+        assert_eq!(old.as_any().downcast_ref::<i16>(), Some(&0));
+        let new = all[0].new_value.as_ref().expect("Missing new value!");
+        assert_eq!(new.as_any().downcast_ref::<i16>(), Some(&5));
+        assert_eq!(all[1].address, serial);
+    }
+    assert_eq!(*speed_changes.lock().expect("Failed to lock!"), 1);
+
+    assert!(dict.unsubscribe(id));
+    assert!(!dict.unsubscribe(id));
+    dict.reset_defaults();
+    assert_eq!(all.lock().expect("Failed to lock!").len(), 2);
+    assert_eq!(*speed_changes.lock().expect("Failed to lock!"), 2);
+    assert_eq!(dict.get_as::<i16>(&speed), Some(0));
+    assert_eq!(dict.get_as::<u32>(&serial), Some(0x1234));
+}
+
+#[test]
+fn test_dcf_values() {
+    let dcf = make_dcf_file(&PDO_DCF.to_vec()).expect("Failed to parse dcf file!");
+    let mut dict = ObjectDictionary::from_dcf(&dcf);
+    let sync = Address::new(0x1005, 0);
+    let tpdo_cob_id = Address::new(0x1800, 1);
+
+    assert_eq!(dict.get_node_id(), 2);
+    assert_eq!(dict.get_as::<u32>(&sync), Some(0x40000080));
+    assert_eq!(dict.get_as::<u32>(&tpdo_cob_id), Some(0x182));
+
+    //Node id relative values are resolved on write
+    dict.set(&tpdo_cob_id, Box::new(NodeIdOffset { offset: 0x280 }))
+        .expect("Failed to set value!");
+    assert_eq!(dict.get_as::<u32>(&tpdo_cob_id), Some(0x282));
+    dict.set(&sync, Box::new(0x80u32))
+        .expect("Failed to set value!");
+
+    dict.reset_range(0x1800..=0x1800, None);
+    assert_eq!(dict.get_as::<u32>(&tpdo_cob_id), Some(0x182));
+    assert_eq!(dict.get_as::<u32>(&sync), Some(0x80));
+    dict.reset_defaults();
+    assert_eq!(dict.get_as::<u32>(&sync), Some(0x80));
+    dict.reset_from_dcf(&dcf);
+    assert_eq!(dict.get_as::<u32>(&sync), Some(0x40000080));
+
+    let eds = dict.to_eds_file();
+    assert_eq!(eds.get_default_u64(&sync, 2), Some(0x40000080));
+}
@@ -0,0 +1,27 @@
+use crate::eds_file::{Address, EDSValue};
+
+//Reported to subscribers whenever the value of an object actually changes
+#[derive(Debug)]
+pub struct ValueChange {
+    pub address: Address,
+    pub old_value: Option<Box<dyn EDSValue>>,
+    pub new_value: Option<Box<dyn EDSValue>>,
+}
+
+impl Clone for ValueChange {
+    fn clone(&self) -> Self {
+        ValueChange {
+            address: self.address,
+            old_value: self.old_value.as_ref().map(|v| v.clone_box()),
+            new_value: self.new_value.as_ref().map(|v| v.clone_box()),
+        }
+    }
+}
+
+pub type SubscriptionId = usize;
+
+pub(crate) struct Subscriber {
+    pub id: SubscriptionId,
+    pub address: Option<Address>,
+    pub callback: Box<dyn FnMut(&ValueChange) + Send>,
+}
@@ -0,0 +1,14 @@
+mod pdo_comm_params;
+mod pdo_direction;
+mod pdo_frame;
+mod pdo_mapping;
+mod pdo_mapping_builder;
+
+#[cfg(test)]
+mod tests;
+
+pub use pdo_comm_params::PdoCommParams;
+pub use pdo_direction::PdoDirection;
+pub use pdo_frame::{MAX_PDO_BYTES, PdoValues};
+pub use pdo_mapping::*;
+pub use pdo_mapping_builder::PdoMappingBuilder;
@@ -0,0 +1,160 @@
+use super::{MAX_PDO_NUMBER, PdoDirection};
+use crate::CommParamError;
+use crate::comm::{
+    COB_ID_BIT_30, COB_ID_INVALID_BIT, check_index, decode_cob_id, encode_cob_id, read_default,
+};
+use crate::dcf::ObjectWrite;
+use crate::eds_file::{Address, DataType, EDSFile, EDSValue};
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct PdoCommParams {
+    pub direction: PdoDirection,
+    pub number: u16,
+    pub cob_id: u32,
+    pub valid: bool,
+    pub rtr_allowed: bool,
+    pub extended: bool,
+    pub transmission_type: u8,
+    pub inhibit_100us: u16,
+    pub event_timer_ms: u16,
+    pub sync_start: u8,
+}
+
+impl PdoCommParams {
+    //PDOs are numbered from 1 to 512
+    pub fn communication_index(direction: PdoDirection, number: u16) -> Option<u16> {
+        let offset = number.checked_sub(1).filter(|n| *n < MAX_PDO_NUMBER)?;
+        direction.communication_index().checked_add(offset)
+    }
+
+    pub fn get_communication_index(&self) -> Option<u16> {
+        Self::communication_index(self.direction, self.number)
+    }
+
+    fn checked_communication_index(
+        direction: PdoDirection,
+        number: u16,
+    ) -> Result<u16, CommParamError> {
+        Self::communication_index(direction, number)
+            .ok_or(CommParamError::InvalidPdoNumber { direction, number })
+    }
+
+    //COB-IDs of the predefined connection set, only the first four PDOs have one
+    pub fn predefined_cob_id(direction: PdoDirection, number: u16, node_id: u8) -> Option<u32> {
+        if !(1..=4).contains(&number) {
+            return None;
+        }
+        let base = match direction {
+            PdoDirection::Transmit => 0x180,
+            PdoDirection::Receive => 0x200,
+        };
+        Some(base + 0x100 * (number as u32 - 1) + node_id as u32)
+    }
+
+    pub fn decode_cob_id_word(&mut self, raw: u32) {
+        let (cob_id, extended) = decode_cob_id(raw);
+        self.cob_id = cob_id;
+        self.extended = extended;
+        self.valid = raw & COB_ID_INVALID_BIT == 0;
+        self.rtr_allowed = raw & COB_ID_BIT_30 == 0;
+    }
+
+    pub fn encode_cob_id_word(&self) -> u32 {
+        let mut raw = encode_cob_id(self.cob_id, self.extended);
+        if !self.valid {
+            raw |= COB_ID_INVALID_BIT;
+        }
+        if !self.rtr_allowed {
+            raw |= COB_ID_BIT_30;
+        }
+        raw
+    }
+
+    pub fn is_synchronous(&self) -> bool {
+        self.transmission_type <= 240
+    }
+
+    pub fn is_cyclic(&self) -> bool {
+        (1..=240).contains(&self.transmission_type)
+    }
+
+    pub fn is_rtr_only(&self) -> bool {
+        self.transmission_type == 252 || self.transmission_type == 253
+    }
+
+    pub fn is_event_driven(&self) -> bool {
+        self.transmission_type >= 254
+    }
+
+    pub fn read(
+        file: &EDSFile,
+        direction: PdoDirection,
+        number: u16,
+        node_id: u8,
+    ) -> Result<PdoCommParams, CommParamError> {
+        let index = Self::checked_communication_index(direction, number)?;
+        check_index(file, index)?;
+        let cob_addr = Address::new(index, 1);
+        let raw = read_default(file, &cob_addr, node_id)?
+            .map(|v| v as u32)
+            .or_else(|| Self::predefined_cob_id(direction, number, node_id))
+            .ok_or(CommParamError::InvalidValue { addr: cob_addr })?;
+        let read_sub = |sub: u8| read_default(file, &Address::new(index, sub), node_id);
+        let mut out = PdoCommParams {
+            direction,
+            number,
+            cob_id: 0,
+            valid: false,
+            rtr_allowed: false,
+            extended: false,
+            transmission_type: read_sub(2)?.unwrap_or(255) as u8,
+            inhibit_100us: read_sub(3)?.unwrap_or(0) as u16,
+            event_timer_ms: read_sub(5)?.unwrap_or(0) as u16,
+            sync_start: read_sub(6)?.unwrap_or(0) as u8,
+        };
+        out.decode_cob_id_word(raw);
+        Ok(out)
+    }
+
+    pub fn read_all(file: &EDSFile, node_id: u8) -> Result<Vec<PdoCommParams>, CommParamError> {
+        let mut out = Vec::new();
+        for direction in [PdoDirection::Receive, PdoDirection::Transmit] {
+            for number in 1..=MAX_PDO_NUMBER {
+                let index = Self::checked_communication_index(direction, number)?;
+                if file.get_index_object(index).is_some() {
+                    out.push(Self::read(file, direction, number, node_id)?);
+                }
+            }
+        }
+        Ok(out)
+    }
+
+    //Only writes sub-entries the device actually implements
+    pub fn to_writes(&self, file: &EDSFile) -> Result<Vec<ObjectWrite>, CommParamError> {
+        let index = Self::checked_communication_index(self.direction, self.number)?;
+        let mut out = vec![
+            ObjectWrite::new(
+                Address::new(index, 1),
+                DataType::UInt32,
+                Box::new(self.encode_cob_id_word()),
+            ),
+            ObjectWrite::new(
+                Address::new(index, 2),
+                DataType::UInt8,
+                Box::new(self.transmission_type),
+            ),
+        ];
+        let optional: [(u8, DataType, Box<dyn EDSValue>); 3] = [
+            (3, DataType::UInt16, Box::new(self.inhibit_100us)),
+            (5, DataType::UInt16, Box::new(self.event_timer_ms)),
+            (6, DataType::UInt8, Box::new(self.sync_start)),
+        ];
+        for (sub, data_type, value) in optional {
+            let addr = Address::new(index, sub);
+            if file.get_object(&addr).is_some() {
+                out.push(ObjectWrite::new(addr, data_type, value));
+            }
+        }
+        Ok(out)
+    }
+}
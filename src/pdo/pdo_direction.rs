@@ -0,0 +1,41 @@
+use std::fmt::Display;
+
+use crate::eds_file::AccessMode;
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum PdoDirection {
+    Receive,
+    Transmit,
+}
+
+impl Display for PdoDirection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self {
+            PdoDirection::Receive => write!(f, "RPDO"),
+            PdoDirection::Transmit => write!(f, "TPDO"),
+        }
+    }
+}
+
+impl PdoDirection {
+    pub fn communication_index(&self) -> u16 {
+        match &self {
+            PdoDirection::Receive => 0x1400,
+            PdoDirection::Transmit => 0x1800,
+        }
+    }
+
+    pub fn mapping_index(&self) -> u16 {
+        match &self {
+            PdoDirection::Receive => 0x1600,
+            PdoDirection::Transmit => 0x1A00,
+        }
+    }
+
+    pub fn allows(&self, access_mode: &AccessMode) -> bool {
+        match &self {
+            PdoDirection::Receive => access_mode.is_rpdo_mappable(),
+            PdoDirection::Transmit => access_mode.is_tpdo_mappable(),
+        }
+    }
+}
@@ -0,0 +1,123 @@
+use std::collections::HashMap;
+
+use super::{PdoMapping, PdoMappingEntry};
+use crate::PdoFrameError;
+use crate::eds_file::{
+    Address, EDSDeviceInfo, EDSValue, eds_value_from_bytes, eds_value_matches_type,
+    eds_value_to_bytes,
+};
+
+pub const MAX_PDO_BYTES: usize = 8;
+
+//Values of a frame in mapping order, objects can be mapped more than once
+pub type PdoValues = Vec<(Address, Box<dyn EDSValue>)>;
+
+fn write_bits(payload: &mut [u8], offset: u32, length: u32, data: &[u8]) {
+    for i in 0..length {
+        let bit = (data[(i / 8) as usize] >> (i % 8)) & 1;
+        let pos = offset + i;
+        payload[(pos / 8) as usize] |= bit << (pos % 8);
+    }
+}
+
+fn read_bits(payload: &[u8], offset: u32, length: u32) -> Vec<u8> {
+    let mut out = vec![0u8; length.div_ceil(8) as usize];
+    for i in 0..length {
+        let pos = offset + i;
+        let bit = (payload[(pos / 8) as usize] >> (pos % 8)) & 1;
+        out[(i / 8) as usize] |= bit << (i % 8);
+    }
+    out
+}
+
+impl PdoMapping {
+    pub fn get_payload_length(&self) -> usize {
+        self.get_bit_length().div_ceil(8) as usize
+    }
+
+    //A granularity of 0 means the device does not support mapping
+    fn check_granularity(&self, device_info: &EDSDeviceInfo) -> Result<(), PdoFrameError> {
+        let granularity = device_info.granularity;
+        if granularity == 0 {
+            return Err(PdoFrameError::MappingNotSupported);
+        }
+        for e in self.entries.iter().chain(self.dummies.iter()) {
+            if e.bit_offset % granularity != 0 || e.bit_length % granularity != 0 {
+                return Err(PdoFrameError::GranularityViolation {
+                    addr: e.address,
+                    granularity,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    //Values must have the type of their entry, integers are not narrowed
+    fn entry_bytes(
+        entry: &PdoMappingEntry,
+        value: &dyn EDSValue,
+    ) -> Result<Vec<u8>, PdoFrameError> {
+        if !eds_value_matches_type(value, &entry.data_type) {
+            return Err(PdoFrameError::InvalidValue {
+                addr: entry.address,
+            });
+        }
+        let mut bytes =
+            eds_value_to_bytes(value, &entry.data_type).ok_or(PdoFrameError::InvalidValue {
+                addr: entry.address,
+            })?;
+        let size = (entry.bit_length as u32).div_ceil(8) as usize;
+        if bytes.len() > size {
+            return Err(PdoFrameError::InvalidValue {
+                addr: entry.address,
+            });
+        }
+        bytes.resize(size, 0);
+        Ok(bytes)
+    }
+
+    pub fn pack(
+        &self,
+        device_info: &EDSDeviceInfo,
+        values: &HashMap<Address, Box<dyn EDSValue>>,
+    ) -> Result<Vec<u8>, PdoFrameError> {
+        self.check_granularity(device_info)?;
+        let mut payload = vec![0u8; self.get_payload_length()];
+        for e in &self.entries {
+            let value = values
+                .get(&e.address)
+                .ok_or(PdoFrameError::MissingValue { addr: e.address })?;
+            let bytes = Self::entry_bytes(e, value.as_ref())?;
+            write_bits(
+                &mut payload,
+                e.bit_offset as u32,
+                e.bit_length as u32,
+                &bytes,
+            );
+        }
+        Ok(payload)
+    }
+
+    pub fn unpack(
+        &self,
+        device_info: &EDSDeviceInfo,
+        payload: &[u8],
+    ) -> Result<PdoValues, PdoFrameError> {
+        self.check_granularity(device_info)?;
+        let expected = self.get_payload_length();
+        if payload.len() < expected || payload.len() > MAX_PDO_BYTES {
+            return Err(PdoFrameError::InvalidPayloadLength {
+                expected,
+                actual: payload.len(),
+            });
+        }
+        let mut out = Vec::new();
+        for e in &self.entries {
+            let bytes = read_bits(payload, e.bit_offset as u32, e.bit_length as u32);
+            let value = eds_value_from_bytes(&bytes, &e.data_type)
+                .ok_or(PdoFrameError::InvalidValue { addr: e.address })?;
+            out.push((e.address, value));
+        }
+        Ok(out)
+    }
+}
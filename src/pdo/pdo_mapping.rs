@@ -0,0 +1,184 @@
+use super::PdoDirection;
+use crate::PdoMappingError;
+use crate::eds_file::{Address, DataType, EDSFile, eds_value_to_u64};
+
+pub const MAX_PDO_BITS: u32 = 64;
+pub const MAX_PDO_NUMBER: u16 = 512;
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct PdoMappingEntry {
+    pub address: Address,
+    pub data_type: DataType,
+    pub bit_offset: u8,
+    pub bit_length: u8,
+}
+
+impl PdoMappingEntry {
+    pub fn decode_raw(raw: u32) -> (Address, u8) {
+        let addr = Address::new((raw >> 16) as u16, (raw >> 8) as u8);
+        (addr, raw as u8)
+    }
+
+    pub fn encode_raw(addr: &Address, bit_length: u8) -> u32 {
+        ((addr.index as u32) << 16) | ((addr.subindex as u32) << 8) | bit_length as u32
+    }
+
+    //Dummy entries map a data type index instead of an object
+    pub fn is_dummy_address(addr: &Address) -> bool {
+        addr.index != 0 && addr.index < 0x1000
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct PdoMapping {
+    pub direction: PdoDirection,
+    pub number: u16,
+    pub entries: Vec<PdoMappingEntry>,
+    pub dummies: Vec<PdoMappingEntry>,
+}
+
+impl PdoMapping {
+    //PDOs are numbered from 1 to 512
+    pub fn mapping_index(direction: PdoDirection, number: u16) -> Option<u16> {
+        let offset = number.checked_sub(1).filter(|n| *n < MAX_PDO_NUMBER)?;
+        direction.mapping_index().checked_add(offset)
+    }
+
+    pub fn get_mapping_index(&self) -> Option<u16> {
+        Self::mapping_index(self.direction, self.number)
+    }
+
+    pub(crate) fn checked_mapping_index(
+        direction: PdoDirection,
+        number: u16,
+    ) -> Result<u16, PdoMappingError> {
+        Self::mapping_index(direction, number)
+            .ok_or(PdoMappingError::InvalidPdoNumber { direction, number })
+    }
+
+    pub fn get_bit_length(&self) -> u32 {
+        self.entries
+            .iter()
+            .chain(self.dummies.iter())
+            .map(|e| e.bit_length as u32)
+            .sum()
+    }
+
+    fn read_mapping_value(file: &EDSFile, addr: &Address) -> Result<u64, PdoMappingError> {
+        let obj = file
+            .get_object(addr)
+            .ok_or(PdoMappingError::MissingMappingObject { addr: *addr })?;
+        obj.get_default()
+            .and_then(|v| eds_value_to_u64(v, None))
+            .ok_or(PdoMappingError::InvalidMappingValue { addr: *addr })
+    }
+
+    fn decode_entry(
+        file: &EDSFile,
+        direction: PdoDirection,
+        mapping: &Address,
+        mapped: Address,
+        bit_offset: u8,
+        bit_length: u8,
+    ) -> Result<PdoMappingEntry, PdoMappingError> {
+        let data_type = if PdoMappingEntry::is_dummy_address(&mapped) {
+            DataType::parse(mapped.index).ok_or(PdoMappingError::MappedObjectNotFound {
+                mapping: *mapping,
+                mapped,
+            })?
+        } else {
+            let obj = file
+                .get_object(&mapped)
+                .ok_or(PdoMappingError::MappedObjectNotFound {
+                    mapping: *mapping,
+                    mapped,
+                })?;
+            if !obj.is_pdo_mappable() {
+                return Err(PdoMappingError::ObjectNotMappable { addr: mapped });
+            }
+            if let Some(access_mode) = obj.get_access_mode()
+                && !direction.allows(access_mode)
+            {
+                return Err(PdoMappingError::AccessModeNotMappable {
+                    addr: mapped,
+                    access_mode: access_mode.clone(),
+                    direction,
+                });
+            }
+            obj.get_data_type()
+                .ok_or(PdoMappingError::ObjectNotMappable { addr: mapped })?
+                .clone()
+        };
+        let length_valid = match data_type.bit_size() {
+            Some(size) => size == bit_length as u32,
+            None => bit_length % 8 == 0,
+        };
+        if bit_length == 0 || !length_valid {
+            return Err(PdoMappingError::InvalidBitLength {
+                addr: mapped,
+                bit_length,
+            });
+        }
+        Ok(PdoMappingEntry {
+            address: mapped,
+            data_type,
+            bit_offset,
+            bit_length,
+        })
+    }
+
+    pub fn decode(
+        file: &EDSFile,
+        direction: PdoDirection,
+        number: u16,
+    ) -> Result<PdoMapping, PdoMappingError> {
+        let mapping = Address::new(Self::checked_mapping_index(direction, number)?, 0);
+        let count = Self::read_mapping_value(file, &mapping)?;
+        if count > MAX_PDO_BITS as u64 {
+            return Err(PdoMappingError::InvalidMappingValue { addr: mapping });
+        }
+
+        let mut raw_entries = Vec::new();
+        for sub in 1..=count {
+            let sub_addr = Address::new(mapping.index, sub as u8);
+            let raw = Self::read_mapping_value(file, &sub_addr)?;
+            raw_entries.push(PdoMappingEntry::decode_raw(raw as u32));
+        }
+        let bits: u32 = raw_entries.iter().map(|(_, len)| *len as u32).sum();
+        if bits > MAX_PDO_BITS {
+            return Err(PdoMappingError::TooManyBits { mapping, bits });
+        }
+
+        let mut out = PdoMapping {
+            direction,
+            number,
+            entries: Vec::new(),
+            dummies: Vec::new(),
+        };
+        let mut bit_offset = 0;
+        for (mapped, bit_length) in raw_entries {
+            let entry =
+                Self::decode_entry(file, direction, &mapping, mapped, bit_offset, bit_length)?;
+            bit_offset += bit_length;
+            if PdoMappingEntry::is_dummy_address(&mapped) {
+                out.dummies.push(entry);
+            } else {
+                out.entries.push(entry);
+            }
+        }
+        Ok(out)
+    }
+
+    pub fn decode_all(file: &EDSFile) -> Result<Vec<PdoMapping>, PdoMappingError> {
+        let mut out = Vec::new();
+        for direction in [PdoDirection::Receive, PdoDirection::Transmit] {
+            for number in 1..=MAX_PDO_NUMBER {
+                let index = Self::checked_mapping_index(direction, number)?;
+                if file.get_object(&Address::new(index, 0)).is_some() {
+                    out.push(Self::decode(file, direction, number)?);
+                }
+            }
+        }
+        Ok(out)
+    }
+}
@@ -0,0 +1,157 @@
+use super::{MAX_PDO_BITS, PdoDirection, PdoMapping, PdoMappingEntry};
+use crate::PdoMappingError;
+use crate::dcf::ObjectWrite;
+use crate::eds_file::{Address, DataType, EDSFile};
+
+pub struct PdoMappingBuilder<'a> {
+    file: &'a EDSFile,
+    mapping: PdoMapping,
+    mapping_addr: Address,
+    bit_offset: u32,
+}
+
+impl<'a> PdoMappingBuilder<'a> {
+    pub fn new(
+        file: &'a EDSFile,
+        direction: PdoDirection,
+        number: u16,
+    ) -> Result<Self, PdoMappingError> {
+        let index = PdoMapping::checked_mapping_index(direction, number)?;
+        Ok(PdoMappingBuilder {
+            file,
+            mapping: PdoMapping {
+                direction,
+                number,
+                entries: Vec::new(),
+                dummies: Vec::new(),
+            },
+            mapping_addr: Address::new(index, 0),
+            bit_offset: 0,
+        })
+    }
+
+    fn push(&mut self, address: Address, data_type: DataType) -> Result<(), PdoMappingError> {
+        let mapping_addr = self.mapping_addr;
+        //A granularity of 0 means the mapping can not be changed
+        let granularity = self.file.device_info.granularity;
+        if granularity == 0 {
+            return Err(PdoMappingError::MappingNotSupported);
+        }
+        let bit_length = data_type
+            .bit_size()
+            .ok_or(PdoMappingError::InvalidBitLength {
+                addr: address,
+                bit_length: 0,
+            })?;
+        if bit_length % granularity as u32 != 0 {
+            return Err(PdoMappingError::GranularityViolation {
+                addr: address,
+                granularity,
+            });
+        }
+        if self.bit_offset + bit_length > MAX_PDO_BITS {
+            return Err(PdoMappingError::TooManyBits {
+                mapping: mapping_addr,
+                bits: self.bit_offset + bit_length,
+            });
+        }
+        let entry = PdoMappingEntry {
+            address,
+            data_type,
+            bit_offset: self.bit_offset as u8,
+            bit_length: bit_length as u8,
+        };
+        self.bit_offset += bit_length;
+        if PdoMappingEntry::is_dummy_address(&address) {
+            self.mapping.dummies.push(entry);
+        } else {
+            self.mapping.entries.push(entry);
+        }
+        Ok(())
+    }
+
+    pub fn add_object(&mut self, addr: &Address) -> Result<(), PdoMappingError> {
+        let direction = self.mapping.direction;
+        let obj = self
+            .file
+            .get_object(addr)
+            .ok_or(PdoMappingError::MappedObjectNotFound {
+                mapping: self.mapping_addr,
+                mapped: *addr,
+            })?;
+        if !obj.is_pdo_mappable() {
+            return Err(PdoMappingError::ObjectNotMappable { addr: *addr });
+        }
+        if let Some(access_mode) = obj.get_access_mode()
+            && !direction.allows(access_mode)
+        {
+            return Err(PdoMappingError::AccessModeNotMappable {
+                addr: *addr,
+                access_mode: access_mode.clone(),
+                direction,
+            });
+        }
+        let data_type = obj
+            .get_data_type()
+            .ok_or(PdoMappingError::ObjectNotMappable { addr: *addr })?;
+        self.push(*addr, data_type.clone())
+    }
+
+    //Fills the gap with the largest dummy objects allowed by [DummyUsage]
+    pub fn add_gap(&mut self, bits: u32) -> Result<(), PdoMappingError> {
+        let mut dummies: Vec<(u16, DataType)> = (1..0x1000)
+            .filter(|i| self.file.dummy_usage.is_supported(*i))
+            .filter_map(|i| DataType::parse(i).map(|t| (i, t)))
+            .filter(|(_, t)| t.bit_size().is_some())
+            .collect();
+        dummies.sort_by_key(|(_, t)| std::cmp::Reverse(t.bit_size()));
+        let mut remaining = bits;
+        while remaining > 0 {
+            let (index, data_type) = dummies
+                .iter()
+                .find(|(_, t)| t.bit_size().unwrap() <= remaining)
+                .ok_or(PdoMappingError::UnfillableGap { bits: remaining })?;
+            remaining -= data_type.bit_size().unwrap();
+            self.push(Address::new(*index, 0), data_type.clone())?;
+        }
+        Ok(())
+    }
+
+    pub fn build(self) -> PdoMapping {
+        self.mapping
+    }
+}
+
+impl PdoMapping {
+    fn get_ordered_entries(&self) -> Vec<&PdoMappingEntry> {
+        let mut entries: Vec<&PdoMappingEntry> =
+            self.entries.iter().chain(self.dummies.iter()).collect();
+        entries.sort_by_key(|e| e.bit_offset);
+        entries
+    }
+
+    //Mapping writes as required by CiA 301: disable the mapping by writing 0 to
+    //sub0, write the entries and enable it again by writing the entry count
+    pub fn to_writes(&self) -> Result<Vec<ObjectWrite>, PdoMappingError> {
+        let index = Self::checked_mapping_index(self.direction, self.number)?;
+        let entries = self.get_ordered_entries();
+        let mut out = vec![ObjectWrite::new(
+            Address::new(index, 0),
+            DataType::UInt8,
+            Box::new(0u8),
+        )];
+        for (i, e) in entries.iter().enumerate() {
+            out.push(ObjectWrite::new(
+                Address::new(index, i as u8 + 1),
+                DataType::UInt32,
+                Box::new(PdoMappingEntry::encode_raw(&e.address, e.bit_length)),
+            ));
+        }
+        out.push(ObjectWrite::new(
+            Address::new(index, 0),
+            DataType::UInt8,
+            Box::new(entries.len() as u8),
+        ));
+        Ok(out)
+    }
+}
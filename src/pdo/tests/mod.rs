@@ -0,0 +1,3 @@
+mod pdo_frame;
+mod pdo_mapping;
+mod pdo_mapping_builder;
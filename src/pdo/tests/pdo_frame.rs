@@ -0,0 +1,193 @@
+use std::collections::HashMap;
+
+use crate::PdoFrameError;
+use crate::eds_file::{Address, DataType, EDSDeviceInfo, EDSValue};
+use crate::pdo::{PdoDirection, PdoMapping, PdoMappingEntry};
+use crate::tests::utils::*;
+
+fn make_device_info(granularity: u8) -> EDSDeviceInfo {
+    let efile = make_eds_file(&vec![
+        "[MandatoryObjects]",
+        "SupportedObjects=0",
+        "[OptionalObjects]",
+        "SupportedObjects=0",
+        "[ManufacturerObjects]",
+        "SupportedObjects=0",
+    ])
+    .expect("Failed to parse eds file!");
+    let mut device_info = efile.device_info.clone();
+    device_info.granularity = granularity;
+    device_info
+}
+
+fn make_entry(index: u16, data_type: DataType, bit_offset: u8, bit_length: u8) -> PdoMappingEntry {
+    PdoMappingEntry {
+        address: Address::new(index, 0),
+        data_type,
+        bit_offset,
+        bit_length,
+    }
+}
+
+fn debug_values(values: &[(Address, Box<dyn EDSValue>)]) -> Vec<String> {
+    values.iter().map(|(_, v)| format!("{:?}", v)).collect()
+}
+
+#[test]
+fn test_pack_unpack_bytes() {
+    let mapping = PdoMapping {
+        direction: PdoDirection::Transmit,
+        number: 1,
+        entries: vec![
+            make_entry(0x2000, DataType::UInt16, 0, 16),
+            make_entry(0x2001, DataType::Int32, 24, 32),
+        ],
+        dummies: vec![make_entry(0x5, DataType::UInt8, 16, 8)],
+    };
+    let device_info = make_device_info(8);
+    let mut values: HashMap<Address, Box<dyn EDSValue>> = HashMap::new();
+    values.insert(Address::new(0x2000, 0), Box::new(0x1234u16));
+    values.insert(Address::new(0x2001, 0), Box::new(-2i32));
+    let payload = mapping
+        .pack(&device_info, &values)
+        .expect("Failed to pack pdo!");
+    assert_eq!(payload, vec![0x34, 0x12, 0x00, 0xFE, 0xFF, 0xFF, 0xFF]);
+
+    let unpacked = mapping
+        .unpack(&device_info, &payload)
+        .expect("Failed to unpack pdo!");
+    assert_eq!(unpacked[0].0, Address::new(0x2000, 0));
+    assert_eq!(unpacked[1].0, Address::new(0x2001, 0));
+    assert_eq!(debug_values(&unpacked), vec!["4660", "-2"]);
+}
+
+#[test]
+fn test_pack_unpack_bits() {
+    let mapping = PdoMapping {
+        direction: PdoDirection::Receive,
+        number: 2,
+        entries: vec![
+            make_entry(0x2000, DataType::Boolean, 0, 1),
+            make_entry(0x2001, DataType::Boolean, 1, 1),
+            make_entry(0x2002, DataType::UInt8, 2, 8),
+        ],
+        dummies: vec![],
+    };
+    let mut values: HashMap<Address, Box<dyn EDSValue>> = HashMap::new();
+    values.insert(Address::new(0x2000, 0), Box::new(true));
+    values.insert(Address::new(0x2001, 0), Box::new(false));
+    values.insert(Address::new(0x2002, 0), Box::new(0xFFu8));
+    assert_eq!(
+        mapping.pack(&make_device_info(8), &values),
+        Err(PdoFrameError::GranularityViolation {
+            addr: Address::new(0x2000, 0),
+            granularity: 8
+        })
+    );
+
+    let device_info = make_device_info(1);
+    let payload = mapping
+        .pack(&device_info, &values)
+        .expect("Failed to pack pdo!");
+    assert_eq!(payload, vec![0xFD, 0x03]);
+    let unpacked = mapping
+        .unpack(&device_info, &payload)
+        .expect("Failed to unpack pdo!");
+    assert_eq!(debug_values(&unpacked), vec!["true", "false", "255"]);
+}
+
+#[test]
+fn test_pack_unpack_errors() {
+    let mapping = PdoMapping {
+        direction: PdoDirection::Receive,
+        number: 1,
+        entries: vec![make_entry(0x2000, DataType::UInt32, 0, 32)],
+        dummies: vec![],
+    };
+    let device_info = make_device_info(8);
+    let mut values: HashMap<Address, Box<dyn EDSValue>> = HashMap::new();
+    assert_eq!(
+        mapping.pack(&device_info, &values),
+        Err(PdoFrameError::MissingValue {
+            addr: Address::new(0x2000, 0)
+        })
+    );
+    values.insert(Address::new(0x2000, 0), Box::new(1.0f32));
+    assert_eq!(
+        mapping.pack(&device_info, &values),
+        Err(PdoFrameError::InvalidValue {
+            addr: Address::new(0x2000, 0)
+        })
+    );
+
+    assert_eq!(
+        mapping.unpack(&device_info, &[0, 0]).err(),
+        Some(PdoFrameError::InvalidPayloadLength {
+            expected: 4,
+            actual: 2
+        })
+    );
+
+    //Integers are not truncated or reinterpreted to fit their entry
+    let narrow = PdoMapping {
+        direction: PdoDirection::Receive,
+        number: 1,
+        entries: vec![
+            make_entry(0x2000, DataType::UInt8, 0, 8),
+            make_entry(0x2001, DataType::Int32, 8, 32),
+        ],
+        dummies: vec![],
+    };
+    let mut values: HashMap<Address, Box<dyn EDSValue>> = HashMap::new();
+    values.insert(Address::new(0x2000, 0), Box::new(0x1234u32));
+    values.insert(Address::new(0x2001, 0), Box::new(-1i32));
+    assert_eq!(
+        narrow.pack(&device_info, &values),
+        Err(PdoFrameError::InvalidValue {
+            addr: Address::new(0x2000, 0)
+        })
+    );
+    values.insert(Address::new(0x2000, 0), Box::new(0x12u8));
+    values.insert(Address::new(0x2001, 0), Box::new(-1i8));
+    assert_eq!(
+        narrow.pack(&device_info, &values),
+        Err(PdoFrameError::InvalidValue {
+            addr: Address::new(0x2001, 0)
+        })
+    );
+    values.insert(Address::new(0x2001, 0), Box::new(-1i32));
+    assert_eq!(
+        narrow.pack(&device_info, &values),
+        Ok(vec![0x12, 0xFF, 0xFF, 0xFF, 0xFF])
+    );
+}
+
+#[test]
+fn test_unpack_mapping_order() {
+    //The same object may be mapped twice, every mapped value is kept
+    let mapping = PdoMapping {
+        direction: PdoDirection::Transmit,
+        number: 1,
+        entries: vec![
+            make_entry(0x2001, DataType::UInt8, 0, 8),
+            make_entry(0x2000, DataType::UInt8, 8, 8),
+            make_entry(0x2001, DataType::UInt8, 16, 8),
+        ],
+        dummies: vec![],
+    };
+    let unpacked = mapping
+        .unpack(&make_device_info(8), &[1, 2, 3])
+        .expect("Failed to unpack pdo!");
+    let addresses: Vec<u16> = unpacked.iter().map(|(a, _)| a.index).collect();
+    assert_eq!(addresses, vec![0x2001, 0x2000, 0x2001]);
+    assert_eq!(debug_values(&unpacked), vec!["1", "2", "3"]);
+
+    assert_eq!(
+        mapping.unpack(&make_device_info(0), &[1, 2, 3]).err(),
+        Some(PdoFrameError::MappingNotSupported)
+    );
+    assert_eq!(
+        mapping.pack(&make_device_info(0), &HashMap::new()),
+        Err(PdoFrameError::MappingNotSupported)
+    );
+}
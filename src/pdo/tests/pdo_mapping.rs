@@ -0,0 +1,214 @@
+use crate::PdoMappingError;
+use crate::eds_file::{AccessMode, Address, DataType};
+use crate::load_file;
+use crate::pdo::{PdoDirection, PdoMapping, PdoMappingBuilder, PdoMappingEntry};
+use crate::tests::utils::*;
+
+fn mapping_lines<'a>(index: &'a str, entries: &[&'a str]) -> Vec<String> {
+    let mut out = vec![
+        format!("[{}]", index),
+        "ParameterName=Mapping Parameter".to_string(),
+        "ObjectType=0x9".to_string(),
+        format!("SubNumber={}", entries.len() + 1),
+        format!("[{}sub0]", index),
+        "ParameterName=Number of entries".to_string(),
+        "ObjectType=0x7".to_string(),
+        "DataType=0x0005".to_string(),
+        "AccessType=rw".to_string(),
+        format!("DefaultValue={}", entries.len()),
+    ];
+    for (i, e) in entries.iter().enumerate() {
+        out.push(format!("[{}sub{}]", index, i + 1));
+        out.push(format!("ParameterName=Mapped object #{}", i + 1));
+        out.push("ObjectType=0x7".to_string());
+        out.push("DataType=0x0007".to_string());
+        out.push("AccessType=rw".to_string());
+        out.push(format!("DefaultValue={}", e));
+    }
+    out
+}
+
+fn make_mapping_file(rpdo: &[&str], tpdo: &[&str]) -> Vec<String> {
+    let mut lines: Vec<String> = vec![
+        "[MandatoryObjects]",
+        "SupportedObjects=0",
+        "[OptionalObjects]",
+        "SupportedObjects=2",
+        "1=0x1600",
+        "2=0x1A00",
+        "[ManufacturerObjects]",
+        "SupportedObjects=3",
+        "1=0x2000",
+        "2=0x2001",
+        "3=0x2002",
+        "[2000]",
+        "ParameterName=Controlword",
+        "ObjectType=0x7",
+        "DataType=0x0006",
+        "AccessType=rww",
+        "PDOMapping=1",
+        "[2001]",
+        "ParameterName=Statusword",
+        "ObjectType=0x7",
+        "DataType=0x0006",
+        "AccessType=ro",
+        "PDOMapping=1",
+        "[2002]",
+        "ParameterName=Not mappable",
+        "ObjectType=0x7",
+        "DataType=0x0007",
+        "AccessType=rw",
+        "PDOMapping=0",
+    ]
+    .iter()
+    .map(|s| s.to_string())
+    .collect();
+    lines.extend(mapping_lines("1600", rpdo));
+    lines.extend(mapping_lines("1A00", tpdo));
+    lines
+}
+
+fn decode_mapping(
+    rpdo: &[&str],
+    tpdo: &[&str],
+    direction: PdoDirection,
+) -> Result<PdoMapping, PdoMappingError> {
+    let lines = make_mapping_file(rpdo, tpdo);
+    let efile = make_eds_file(&lines.iter().map(|s| s.as_str()).collect())
+        .expect("Failed to parse eds file!");
+    PdoMapping::decode(&efile, direction, 1)
+}
+
+#[test]
+fn test_raw_entry() {
+    let (addr, len) = PdoMappingEntry::decode_raw(0x60400010);
+    assert_eq!(addr, Address::new(0x6040, 0));
+    assert_eq!(len, 16);
+    assert_eq!(PdoMappingEntry::encode_raw(&addr, len), 0x60400010);
+    assert!(PdoMappingEntry::is_dummy_address(&Address::new(0x5, 0)));
+    assert!(!PdoMappingEntry::is_dummy_address(&Address::new(0x2000, 0)));
+}
+
+#[test]
+fn test_decode_mapping() {
+    let mapping = decode_mapping(
+        &["0x20000010", "0x00050008", "0x20000010"],
+        &[],
+        PdoDirection::Receive,
+    )
+    .expect("Failed to decode mapping!");
+    assert_eq!(mapping.get_mapping_index(), Some(0x1600));
+    assert_eq!(mapping.get_bit_length(), 40);
+    assert_eq!(mapping.entries.len(), 2);
+    assert_eq!(mapping.entries[0].address, Address::new(0x2000, 0));
+    assert_eq!(mapping.entries[0].bit_offset, 0);
+    assert_eq!(mapping.entries[1].bit_offset, 24);
+    assert_eq!(mapping.entries[1].data_type, DataType::UInt16);
+    assert_eq!(mapping.dummies.len(), 1);
+    assert_eq!(mapping.dummies[0].bit_offset, 16);
+    assert_eq!(mapping.dummies[0].data_type, DataType::UInt8);
+}
+
+#[test]
+fn test_decode_mapping_errors() {
+    assert_eq!(
+        decode_mapping(&["0x30000010"], &[], PdoDirection::Receive),
+        Err(PdoMappingError::MappedObjectNotFound {
+            mapping: Address::new(0x1600, 0),
+            mapped: Address::new(0x3000, 0)
+        })
+    );
+    assert_eq!(
+        decode_mapping(&["0x20020020"], &[], PdoDirection::Receive),
+        Err(PdoMappingError::ObjectNotMappable {
+            addr: Address::new(0x2002, 0)
+        })
+    );
+    assert_eq!(
+        decode_mapping(&[], &["0x20000010"], PdoDirection::Transmit),
+        Err(PdoMappingError::AccessModeNotMappable {
+            addr: Address::new(0x2000, 0),
+            access_mode: AccessMode::ReadWritePDOWrite,
+            direction: PdoDirection::Transmit
+        })
+    );
+    assert_eq!(
+        decode_mapping(&[], &["0x20010008"], PdoDirection::Transmit),
+        Err(PdoMappingError::InvalidBitLength {
+            addr: Address::new(0x2001, 0),
+            bit_length: 8
+        })
+    );
+    assert_eq!(
+        decode_mapping(
+            &[],
+            &[
+                "0x20010010",
+                "0x20010010",
+                "0x20010010",
+                "0x20010010",
+                "0x00010001"
+            ],
+            PdoDirection::Transmit
+        ),
+        Err(PdoMappingError::TooManyBits {
+            mapping: Address::new(0x1A00, 0),
+            bits: 65
+        })
+    );
+}
+
+#[test]
+fn test_pdo_numbers() {
+    assert_eq!(
+        PdoMapping::mapping_index(PdoDirection::Transmit, 512),
+        Some(0x1BFF)
+    );
+    assert_eq!(PdoMapping::mapping_index(PdoDirection::Receive, 0), None);
+    assert_eq!(PdoMapping::mapping_index(PdoDirection::Receive, 513), None);
+    assert_eq!(
+        PdoMapping::mapping_index(PdoDirection::Transmit, 0xFFFF),
+        None
+    );
+
+    let efile = load_file("res/micro-motor.eds").expect("Failed to load sample file!");
+    for number in [0, 0xFFFF] {
+        assert_eq!(
+            PdoMapping::decode(&efile, PdoDirection::Receive, number),
+            Err(PdoMappingError::InvalidPdoNumber {
+                direction: PdoDirection::Receive,
+                number
+            })
+        );
+    }
+    assert!(PdoMappingBuilder::new(&efile, PdoDirection::Transmit, 0).is_err());
+    let mapping = PdoMapping {
+        direction: PdoDirection::Transmit,
+        number: 600,
+        entries: Vec::new(),
+        dummies: Vec::new(),
+    };
+    assert_eq!(mapping.get_mapping_index(), None);
+    assert_eq!(
+        mapping.to_writes().err(),
+        Some(PdoMappingError::InvalidPdoNumber {
+            direction: PdoDirection::Transmit,
+            number: 600
+        })
+    );
+}
+
+#[test]
+fn test_decode_all_sample() {
+    let efile = load_file("res/micro-motor.eds").expect("Failed to load sample file!");
+    let mappings = PdoMapping::decode_all(&efile).expect("Failed to decode mappings!");
+    assert_eq!(mappings.len(), 8);
+    assert_eq!(
+        mappings
+            .iter()
+            .filter(|m| m.direction == PdoDirection::Receive)
+            .count(),
+        4
+    );
+    assert!(mappings.iter().all(|m| m.entries.is_empty()));
+}
@@ -0,0 +1,160 @@
+use crate::PdoMappingError;
+use crate::dcf::ObjectWrite;
+use crate::eds_file::{AccessMode, Address, EDSFile};
+use crate::pdo::{PdoDirection, PdoMappingBuilder};
+use crate::tests::utils::*;
+
+fn make_builder_file(dummy_usage: &[&str]) -> EDSFile {
+    let mut lines = vec!["[DummyUsage]"];
+    lines.extend(dummy_usage.iter());
+    lines.extend(vec![
+        "[MandatoryObjects]",
+        "SupportedObjects=0",
+        "[OptionalObjects]",
+        "SupportedObjects=0",
+        "[ManufacturerObjects]",
+        "SupportedObjects=3",
+        "1=0x2000",
+        "2=0x2001",
+        "3=0x2002",
+        "[2000]",
+        "ParameterName=Controlword",
+        "ObjectType=0x7",
+        "DataType=0x0006",
+        "AccessType=rww",
+        "PDOMapping=1",
+        "[2001]",
+        "ParameterName=Position",
+        "ObjectType=0x7",
+        "DataType=0x0004",
+        "AccessType=rwr",
+        "PDOMapping=1",
+        "[2002]",
+        "ParameterName=Enabled",
+        "ObjectType=0x7",
+        "DataType=0x0001",
+        "AccessType=ro",
+        "PDOMapping=1",
+    ]);
+    make_eds_file(&lines).expect("Failed to parse eds file!")
+}
+
+#[test]
+fn test_build_mapping() {
+    let efile = make_builder_file(&["Dummy0005=1", "Dummy0006=1"]);
+    let mut builder = PdoMappingBuilder::new(&efile, PdoDirection::Receive, 2)
+        .expect("Failed to create builder!");
+    builder
+        .add_object(&Address::new(0x2000, 0))
+        .expect("Failed to map object!");
+    builder.add_gap(24).expect("Failed to fill gap!");
+    builder
+        .add_object(&Address::new(0x2000, 0))
+        .expect("Failed to map object!");
+    let mapping = builder.build();
+    assert_eq!(mapping.get_mapping_index(), Some(0x1601));
+    assert_eq!(mapping.get_bit_length(), 56);
+    assert_eq!(mapping.entries.len(), 2);
+    assert_eq!(mapping.entries[1].bit_offset, 40);
+    assert_eq!(mapping.dummies.len(), 2);
+    assert_eq!(mapping.dummies[0].address, Address::new(0x6, 0));
+    assert_eq!(mapping.dummies[1].address, Address::new(0x5, 0));
+
+    let writes = mapping.to_writes().expect("Failed to write mapping!");
+    assert_eq!(writes.len(), 6);
+    assert_eq!(writes[0].address, Address::new(0x1601, 0));
+    assert_eq!(writes[0].get_bytes(), Some(vec![0]));
+    assert_eq!(writes[2].address, Address::new(0x1601, 2));
+    assert_eq!(writes[2].get_bytes(), Some(vec![0x10, 0x00, 0x06, 0x00]));
+    assert_eq!(writes[5].get_bytes(), Some(vec![4]));
+    assert_eq!(
+        ObjectWrite::to_dcf_string(&writes[..2], &efile),
+        Some(
+            "[1601sub0]\nParameterValue=0x00\n\n[1601sub1]\nParameterValue=0x20000010\n\n"
+                .to_string()
+        )
+    );
+}
+
+#[test]
+fn test_build_mapping_dcf() {
+    let efile = make_builder_file(&[]);
+    let mut builder = PdoMappingBuilder::new(&efile, PdoDirection::Transmit, 1)
+        .expect("Failed to create builder!");
+    builder
+        .add_object(&Address::new(0x2001, 0))
+        .expect("Failed to map object!");
+    let mapping = builder.build();
+    let dcf = ObjectWrite::to_dcf_string(
+        &mapping.to_writes().expect("Failed to write mapping!"),
+        &efile,
+    )
+    .expect("Failed to write dcf!");
+    assert_eq!(
+        dcf,
+        "[1A00sub1]\nParameterValue=0x20010020\n\n[1A00sub0]\nParameterValue=0x01\n\n"
+    );
+    assert_eq!(mapping.entries[0].bit_length, 32);
+}
+
+#[test]
+fn test_build_mapping_errors() {
+    let efile = make_builder_file(&["Dummy0001=1", "Dummy0005=0"]);
+    let mut builder = PdoMappingBuilder::new(&efile, PdoDirection::Transmit, 1)
+        .expect("Failed to create builder!");
+    assert_eq!(
+        builder.add_object(&Address::new(0x2000, 0)),
+        Err(PdoMappingError::AccessModeNotMappable {
+            addr: Address::new(0x2000, 0),
+            access_mode: AccessMode::ReadWritePDOWrite,
+            direction: PdoDirection::Transmit
+        })
+    );
+    assert_eq!(
+        builder.add_object(&Address::new(0x2002, 0)),
+        Err(PdoMappingError::GranularityViolation {
+            addr: Address::new(0x2002, 0),
+            granularity: 8
+        })
+    );
+    assert_eq!(
+        builder.add_object(&Address::new(0x2003, 0)),
+        Err(PdoMappingError::MappedObjectNotFound {
+            mapping: Address::new(0x1A00, 0),
+            mapped: Address::new(0x2003, 0)
+        })
+    );
+    assert_eq!(
+        builder.add_gap(8),
+        Err(PdoMappingError::GranularityViolation {
+            addr: Address::new(0x1, 0),
+            granularity: 8
+        })
+    );
+    builder
+        .add_object(&Address::new(0x2001, 0))
+        .expect("Failed to map object!");
+    builder
+        .add_object(&Address::new(0x2001, 0))
+        .expect("Failed to map object!");
+    assert_eq!(
+        builder.add_object(&Address::new(0x2001, 0)),
+        Err(PdoMappingError::TooManyBits {
+            mapping: Address::new(0x1A00, 0),
+            bits: 96
+        })
+    );
+
+    let mut fixed = efile.clone();
+    fixed.device_info.granularity = 0;
+    let mut builder = PdoMappingBuilder::new(&fixed, PdoDirection::Transmit, 1)
+        .expect("Failed to create builder!");
+    assert_eq!(
+        builder.add_object(&Address::new(0x2001, 0)),
+        Err(PdoMappingError::MappingNotSupported)
+    );
+    assert_eq!(
+        builder.add_gap(8),
+        Err(PdoMappingError::MappingNotSupported)
+    );
+}
@@ -30,6 +30,12 @@ impl RawFile {
         Ok(RawFile { lines: lines })
     }
 
+    pub fn new_from_string(data: &str) -> RawFile {
+        RawFile {
+            lines: data.lines().map(|l| l.to_string()).collect(),
+        }
+    }
+
     pub fn get_lines(&self) -> &Vec<String> {
         &self.lines
     }
@@ -0,0 +1,103 @@
+mod sdo_abort_code;
+mod sdo_block;
+mod sdo_bus_client;
+mod sdo_client;
+mod sdo_object_store;
+mod sdo_server;
+
+#[cfg(test)]
+mod tests;
+
+pub use sdo_abort_code::SdoAbortCode;
+pub(crate) use sdo_block::{BlockReceiver, BlockSender};
+pub use sdo_bus_client::SdoBusClient;
+pub use sdo_client::{SdoClient, SdoTransfer};
+pub use sdo_object_store::SdoObjectStore;
+pub use sdo_server::SdoServer;
+
+use crate::eds_file::Address;
+
+pub const SDO_SEGMENT_BYTES: usize = 7;
+pub const SDO_EXPEDITED_BYTES: usize = 4;
+pub const SDO_MAX_BLOCK_SIZE: u8 = 127;
+
+pub(crate) const SDO_ABORT: u8 = 0x80;
+
+//CRC-16-CCITT with polynomial 0x1021 and initial value 0 used by block
+//transfers
+pub fn sdo_crc16(data: &[u8]) -> u16 {
+    let mut crc: u16 = 0;
+    for byte in data {
+        crc ^= (*byte as u16) << 8;
+        for _ in 0..8 {
+            crc = if crc & 0x8000 != 0 {
+                (crc << 1) ^ 0x1021
+            } else {
+                crc << 1
+            };
+        }
+    }
+    crc
+}
+
+pub(crate) fn get_multiplexer(frame: &[u8; 8]) -> Address {
+    Address::new(u16::from_le_bytes([frame[1], frame[2]]), frame[3])
+}
+
+pub(crate) fn make_frame(command: u8, addr: &Address, data: &[u8]) -> [u8; 8] {
+    let mut frame = [0u8; 8];
+    frame[0] = command;
+    frame[1..3].copy_from_slice(&addr.index.to_le_bytes());
+    frame[3] = addr.subindex;
+    frame[4..4 + data.len()].copy_from_slice(data);
+    frame
+}
+
+pub fn make_abort_frame(addr: &Address, code: SdoAbortCode) -> [u8; 8] {
+    make_frame(SDO_ABORT, addr, &code.get_code().to_le_bytes())
+}
+
+pub fn parse_abort_frame(frame: &[u8; 8]) -> Option<(Address, SdoAbortCode)> {
+    if frame[0] != SDO_ABORT {
+        return None;
+    }
+    let code = u32::from_le_bytes([frame[4], frame[5], frame[6], frame[7]]);
+    Some((get_multiplexer(frame), SdoAbortCode::parse(code)))
+}
+
+//Segment of a segmented transfer, returns the frame and whether it is the
+//last one
+pub(crate) fn make_segment(
+    command: u8,
+    toggle: bool,
+    data: &[u8],
+    offset: usize,
+) -> ([u8; 8], bool) {
+    let end = (offset + SDO_SEGMENT_BYTES).min(data.len());
+    let last = end == data.len();
+    let unused = (SDO_SEGMENT_BYTES - (end - offset)) as u8;
+    let mut frame = [0u8; 8];
+    frame[0] = command | (toggle as u8) << 4 | unused << 1 | last as u8;
+    frame[1..1 + end - offset].copy_from_slice(&data[offset..end]);
+    (frame, last)
+}
+
+//Data of a received segment and whether it is the last one
+pub(crate) fn read_segment(frame: &[u8; 8]) -> (&[u8], bool) {
+    let unused = ((frame[0] >> 1) & 0x7) as usize;
+    (&frame[1..8 - unused], frame[0] & 1 != 0)
+}
+
+//Data of an expedited initiate frame
+pub(crate) fn read_expedited(frame: &[u8; 8]) -> &[u8] {
+    let unused = if frame[0] & 1 != 0 {
+        ((frame[0] >> 2) & 0x3) as usize
+    } else {
+        0
+    };
+    &frame[4..8 - unused]
+}
+
+pub(crate) fn read_size(frame: &[u8; 8]) -> u32 {
+    u32::from_le_bytes([frame[4], frame[5], frame[6], frame[7]])
+}
@@ -0,0 +1,218 @@
+use std::fmt::Display;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum SdoAbortCode {
+    ToggleBitNotAlternated,
+    ProtocolTimedOut,
+    InvalidCommandSpecifier,
+    InvalidBlockSize,
+    InvalidSequenceNumber,
+    CrcError,
+    OutOfMemory,
+    UnsupportedAccess,
+    ReadWriteOnly,
+    WriteReadOnly,
+    ObjectDoesNotExist,
+    ObjectNotMappable,
+    PdoLengthExceeded,
+    ParameterIncompatibility,
+    InternalIncompatibility,
+    HardwareError,
+    LengthMismatch,
+    LengthTooHigh,
+    LengthTooLow,
+    SubindexDoesNotExist,
+    InvalidValue,
+    ValueTooHigh,
+    ValueTooLow,
+    MaxLessThanMin,
+    ResourceNotAvailable,
+    GeneralError,
+    CannotTransfer,
+    CannotTransferLocalControl,
+    CannotTransferDeviceState,
+    NoObjectDictionary,
+    NoDataAvailable,
+    Other { code: u32 },
+}
+
+const ABORT_CODES: [(SdoAbortCode, u32, &str); 31] = [
+    (
+        SdoAbortCode::ToggleBitNotAlternated,
+        0x05030000,
+        "Toggle bit not alternated",
+    ),
+    (
+        SdoAbortCode::ProtocolTimedOut,
+        0x05040000,
+        "SDO protocol timed out",
+    ),
+    (
+        SdoAbortCode::InvalidCommandSpecifier,
+        0x05040001,
+        "Client/server command specifier not valid or unknown",
+    ),
+    (
+        SdoAbortCode::InvalidBlockSize,
+        0x05040002,
+        "Invalid block size",
+    ),
+    (
+        SdoAbortCode::InvalidSequenceNumber,
+        0x05040003,
+        "Invalid sequence number",
+    ),
+    (SdoAbortCode::CrcError, 0x05040004, "CRC error"),
+    (SdoAbortCode::OutOfMemory, 0x05040005, "Out of memory"),
+    (
+        SdoAbortCode::UnsupportedAccess,
+        0x06010000,
+        "Unsupported access to an object",
+    ),
+    (
+        SdoAbortCode::ReadWriteOnly,
+        0x06010001,
+        "Attempt to read a write only object",
+    ),
+    (
+        SdoAbortCode::WriteReadOnly,
+        0x06010002,
+        "Attempt to write a read only object",
+    ),
+    (
+        SdoAbortCode::ObjectDoesNotExist,
+        0x06020000,
+        "Object does not exist in the object dictionary",
+    ),
+    (
+        SdoAbortCode::ObjectNotMappable,
+        0x06040041,
+        "Object cannot be mapped to the PDO",
+    ),
+    (
+        SdoAbortCode::PdoLengthExceeded,
+        0x06040042,
+        "The number and length of the objects to be mapped would exceed PDO length",
+    ),
+    (
+        SdoAbortCode::ParameterIncompatibility,
+        0x06040043,
+        "General parameter incompatibility reason",
+    ),
+    (
+        SdoAbortCode::InternalIncompatibility,
+        0x06040047,
+        "General internal incompatibility in the device",
+    ),
+    (
+        SdoAbortCode::HardwareError,
+        0x06060000,
+        "Access failed due to a hardware error",
+    ),
+    (
+        SdoAbortCode::LengthMismatch,
+        0x06070010,
+        "Data type does not match, length of service parameter does not match",
+    ),
+    (
+        SdoAbortCode::LengthTooHigh,
+        0x06070012,
+        "Data type does not match, length of service parameter too high",
+    ),
+    (
+        SdoAbortCode::LengthTooLow,
+        0x06070013,
+        "Data type does not match, length of service parameter too low",
+    ),
+    (
+        SdoAbortCode::SubindexDoesNotExist,
+        0x06090011,
+        "Sub-index does not exist",
+    ),
+    (
+        SdoAbortCode::InvalidValue,
+        0x06090030,
+        "Invalid value for parameter",
+    ),
+    (
+        SdoAbortCode::ValueTooHigh,
+        0x06090031,
+        "Value of parameter written too high",
+    ),
+    (
+        SdoAbortCode::ValueTooLow,
+        0x06090032,
+        "Value of parameter written too low",
+    ),
+    (
+        SdoAbortCode::MaxLessThanMin,
+        0x06090036,
+        "Maximum value is less than minimum value",
+    ),
+    (
+        SdoAbortCode::ResourceNotAvailable,
+        0x060A0023,
+        "Resource not available: SDO connection",
+    ),
+    (SdoAbortCode::GeneralError, 0x08000000, "General error"),
+    (
+        SdoAbortCode::CannotTransfer,
+        0x08000020,
+        "Data cannot be transferred or stored to the application",
+    ),
+    (
+        SdoAbortCode::CannotTransferLocalControl,
+        0x08000021,
+        "Data cannot be transferred or stored to the application because of local control",
+    ),
+    (
+        SdoAbortCode::CannotTransferDeviceState,
+        0x08000022,
+        "Data cannot be transferred or stored to the application because of the present device state",
+    ),
+    (
+        SdoAbortCode::NoObjectDictionary,
+        0x08000023,
+        "Object dictionary dynamic generation fails or no object dictionary is present",
+    ),
+    (
+        SdoAbortCode::NoDataAvailable,
+        0x08000024,
+        "No data available",
+    ),
+];
+
+impl SdoAbortCode {
+    pub fn parse(code: u32) -> SdoAbortCode {
+        ABORT_CODES
+            .iter()
+            .find(|(_, c, _)| *c == code)
+            .map(|(abort, _, _)| *abort)
+            .unwrap_or(SdoAbortCode::Other { code })
+    }
+
+    pub fn get_code(&self) -> u32 {
+        match &self {
+            SdoAbortCode::Other { code } => *code,
+            abort => ABORT_CODES
+                .iter()
+                .find(|(a, _, _)| a == *abort)
+                .map(|(_, c, _)| *c)
+                .unwrap_or(0),
+        }
+    }
+
+    pub fn get_description(&self) -> &'static str {
+        ABORT_CODES
+            .iter()
+            .find(|(a, _, _)| a == self)
+            .map(|(_, _, d)| *d)
+            .unwrap_or("Unknown abort code")
+    }
+}
+
+impl Display for SdoAbortCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "0x{:08X}: {}", self.get_code(), self.get_description())
+    }
+}
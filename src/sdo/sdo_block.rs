@@ -0,0 +1,116 @@
+use super::{SDO_SEGMENT_BYTES, SdoAbortCode};
+
+//Sending side of a block transfer, data is split into sub-blocks of up to
+//blksize segments which the receiver acknowledges
+#[derive(Debug, Clone)]
+pub(crate) struct BlockSender {
+    pub data: Vec<u8>,
+    pub offset: usize,
+    pub blksize: u8,
+    sent: u8,
+    last_in_block: bool,
+}
+
+impl BlockSender {
+    pub fn new(data: Vec<u8>, blksize: u8) -> BlockSender {
+        BlockSender {
+            data,
+            offset: 0,
+            blksize,
+            sent: 0,
+            last_in_block: false,
+        }
+    }
+
+    pub fn next_block(&mut self) -> Vec<[u8; 8]> {
+        let mut out = Vec::new();
+        let mut pos = self.offset;
+        self.last_in_block = false;
+        for seq in 1..=self.blksize {
+            let end = (pos + SDO_SEGMENT_BYTES).min(self.data.len());
+            let last = end == self.data.len();
+            let mut frame = [0u8; 8];
+            frame[0] = seq | if last { 0x80 } else { 0 };
+            frame[1..1 + end - pos].copy_from_slice(&self.data[pos..end]);
+            out.push(frame);
+            pos = end;
+            if last {
+                self.last_in_block = true;
+                break;
+            }
+        }
+        self.sent = out.len() as u8;
+        out
+    }
+
+    //Returns true once the last segment has been acknowledged
+    pub fn acknowledge(&mut self, ackseq: u8, blksize: u8) -> Result<bool, SdoAbortCode> {
+        if ackseq > self.sent {
+            return Err(SdoAbortCode::InvalidSequenceNumber);
+        }
+        if blksize == 0 || blksize > super::SDO_MAX_BLOCK_SIZE {
+            return Err(SdoAbortCode::InvalidBlockSize);
+        }
+        if self.last_in_block && ackseq == self.sent {
+            return Ok(true);
+        }
+        self.offset += ackseq as usize * SDO_SEGMENT_BYTES;
+        self.blksize = blksize;
+        Ok(false)
+    }
+
+    //Number of bytes in the last segment that do not contain data
+    pub fn get_unused_bytes(&self) -> u8 {
+        match self.data.len() % SDO_SEGMENT_BYTES {
+            0 if !self.data.is_empty() => 0,
+            rem => (SDO_SEGMENT_BYTES - rem) as u8,
+        }
+    }
+}
+
+//Receiving side of a block transfer, segments out of sequence are dropped
+//and the sub-block ends with sequence number blksize or the last segment
+#[derive(Debug, Clone)]
+pub(crate) struct BlockReceiver {
+    pub data: Vec<u8>,
+    pub blksize: u8,
+    pub complete: bool,
+    expected: u8,
+}
+
+impl BlockReceiver {
+    pub fn new(blksize: u8) -> BlockReceiver {
+        BlockReceiver {
+            data: Vec::new(),
+            blksize,
+            complete: false,
+            expected: 1,
+        }
+    }
+
+    //Returns the acknowledge frame once the sub-block has ended
+    pub fn push(&mut self, frame: &[u8; 8]) -> Option<[u8; 8]> {
+        let seq = frame[0] & 0x7F;
+        let last = frame[0] & 0x80 != 0;
+        if seq == self.expected {
+            self.data.extend_from_slice(&frame[1..]);
+            self.expected += 1;
+            self.complete = last;
+        }
+        if !last && seq < self.blksize {
+            return None;
+        }
+        let ack = [0xA2, self.expected - 1, self.blksize, 0, 0, 0, 0, 0];
+        self.expected = 1;
+        Some(ack)
+    }
+
+    //Strips the padding of the last segment
+    pub fn finish(&mut self, unused_bytes: u8) -> Result<(), SdoAbortCode> {
+        if unused_bytes as usize > self.data.len() {
+            return Err(SdoAbortCode::InvalidValue);
+        }
+        self.data.truncate(self.data.len() - unused_bytes as usize);
+        Ok(())
+    }
+}
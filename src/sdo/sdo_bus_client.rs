@@ -0,0 +1,86 @@
+use std::time::{Duration, Instant};
+
+use super::{SdoAbortCode, SdoClient, SdoObjectStore, SdoTransfer};
+use crate::can_bus::{CanBus, CanFrame};
+use crate::eds_file::Address;
+
+//Runs SDO transfers against the default SDO server of a node over a CanBus,
+//blocking until each transfer finishes or times out
+pub struct SdoBusClient<'a> {
+    bus: &'a mut dyn CanBus,
+    pub rx_cob_id: u32,
+    pub tx_cob_id: u32,
+    pub timeout: Duration,
+    client: SdoClient,
+}
+
+impl<'a> SdoBusClient<'a> {
+    pub fn new(bus: &'a mut dyn CanBus, node_id: u8) -> SdoBusClient<'a> {
+        SdoBusClient {
+            bus,
+            rx_cob_id: 0x600 + node_id as u32,
+            tx_cob_id: 0x580 + node_id as u32,
+            timeout: Duration::from_millis(500),
+            client: SdoClient::new(),
+        }
+    }
+
+    fn send(&mut self, frames: &[[u8; 8]]) -> Result<(), SdoAbortCode> {
+        for frame in frames {
+            self.bus
+                .send(&CanFrame::new(self.rx_cob_id, frame))
+                .map_err(|_| SdoAbortCode::GeneralError)?;
+        }
+        Ok(())
+    }
+
+    fn run(&mut self, first: [u8; 8]) -> Result<Vec<u8>, SdoAbortCode> {
+        self.send(&[first])?;
+        let mut deadline = Instant::now() + self.timeout;
+        loop {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            let frame = match self.bus.recv(remaining) {
+                Ok(Some(frame)) => frame,
+                Ok(None) if remaining.is_zero() => {
+                    let abort = self.client.abort(SdoAbortCode::ProtocolTimedOut);
+                    let _ = self.send(&[abort]);
+                    return Err(SdoAbortCode::ProtocolTimedOut);
+                }
+                Ok(None) => continue,
+                Err(_) => return Err(SdoAbortCode::GeneralError),
+            };
+            if frame.cob_id != self.tx_cob_id || frame.extended || frame.rtr {
+                continue;
+            }
+            let Ok(response) = <[u8; 8]>::try_from(frame.data.as_slice()) else {
+                continue;
+            };
+            match self.client.handle_frame(&response) {
+                SdoTransfer::Pending(frames) => {
+                    self.send(&frames)?;
+                    deadline = Instant::now() + self.timeout;
+                }
+                SdoTransfer::Finished { frames, data } => {
+                    self.send(&frames)?;
+                    return Ok(data);
+                }
+                SdoTransfer::Aborted { frames, code } => {
+                    self.send(&frames)?;
+                    return Err(code);
+                }
+            }
+        }
+    }
+}
+
+impl SdoObjectStore for SdoBusClient<'_> {
+    fn read(&mut self, addr: &Address) -> Result<Vec<u8>, SdoAbortCode> {
+        let first = self.client.upload(addr);
+        self.run(first)
+    }
+
+    fn write(&mut self, addr: &Address, data: &[u8]) -> Result<(), SdoAbortCode> {
+        let first = self.client.download(addr, data);
+        self.run(first).map(|_| ())
+    }
+}
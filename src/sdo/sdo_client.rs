@@ -0,0 +1,347 @@
+use super::{
+    BlockReceiver, BlockSender, SDO_EXPEDITED_BYTES, SDO_MAX_BLOCK_SIZE, SdoAbortCode,
+    get_multiplexer, make_abort_frame, make_frame, make_segment, parse_abort_frame, read_expedited,
+    read_segment, read_size, sdo_crc16,
+};
+use crate::eds_file::Address;
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum SdoTransfer {
+    //Frames to send, the transfer is still running
+    Pending(Vec<[u8; 8]>),
+    //Uploaded data, empty for downloads
+    Finished {
+        frames: Vec<[u8; 8]>,
+        data: Vec<u8>,
+    },
+    //Frames contain the abort frame if the client aborted the transfer
+    Aborted {
+        frames: Vec<[u8; 8]>,
+        code: SdoAbortCode,
+    },
+}
+
+#[derive(Debug, Clone)]
+enum ClientState {
+    Idle,
+    UploadInitiate,
+    UploadSegment {
+        toggle: bool,
+        data: Vec<u8>,
+        size: Option<u32>,
+    },
+    DownloadInitiate {
+        data: Vec<u8>,
+        expedited: bool,
+    },
+    DownloadSegment {
+        data: Vec<u8>,
+        offset: usize,
+        toggle: bool,
+        last: bool,
+    },
+    BlockDownloadInitiate {
+        data: Vec<u8>,
+        crc: bool,
+    },
+    BlockDownload {
+        sender: BlockSender,
+        crc: bool,
+    },
+    BlockDownloadEnd,
+    BlockUploadInitiate {
+        blksize: u8,
+        crc: bool,
+    },
+    BlockUpload {
+        receiver: BlockReceiver,
+        crc: bool,
+        size: Option<u32>,
+    },
+}
+
+#[derive(Debug, Clone)]
+pub struct SdoClient {
+    address: Address,
+    state: ClientState,
+}
+
+impl Default for SdoClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SdoClient {
+    pub fn new() -> SdoClient {
+        SdoClient {
+            address: Address::new(0, 0),
+            state: ClientState::Idle,
+        }
+    }
+
+    pub fn is_idle(&self) -> bool {
+        matches!(self.state, ClientState::Idle)
+    }
+
+    pub fn get_address(&self) -> &Address {
+        &self.address
+    }
+
+    pub fn upload(&mut self, addr: &Address) -> [u8; 8] {
+        self.address = *addr;
+        self.state = ClientState::UploadInitiate;
+        make_frame(0x40, addr, &[])
+    }
+
+    //Up to 4 bytes are sent expedited, everything else segmented
+    pub fn download(&mut self, addr: &Address, data: &[u8]) -> [u8; 8] {
+        self.address = *addr;
+        let expedited = !data.is_empty() && data.len() <= SDO_EXPEDITED_BYTES;
+        let frame = if expedited {
+            let unused = (SDO_EXPEDITED_BYTES - data.len()) as u8;
+            make_frame(0x23 | unused << 2, addr, data)
+        } else {
+            make_frame(0x21, addr, &(data.len() as u32).to_le_bytes())
+        };
+        self.state = ClientState::DownloadInitiate {
+            data: data.to_vec(),
+            expedited,
+        };
+        frame
+    }
+
+    pub fn block_download(&mut self, addr: &Address, data: &[u8], crc: bool) -> [u8; 8] {
+        self.address = *addr;
+        self.state = ClientState::BlockDownloadInitiate {
+            data: data.to_vec(),
+            crc,
+        };
+        make_frame(
+            0xC2 | (crc as u8) << 2,
+            addr,
+            &(data.len() as u32).to_le_bytes(),
+        )
+    }
+
+    pub fn block_upload(&mut self, addr: &Address, blksize: u8, crc: bool) -> [u8; 8] {
+        self.address = *addr;
+        let blksize = blksize.clamp(1, SDO_MAX_BLOCK_SIZE);
+        self.state = ClientState::BlockUploadInitiate { blksize, crc };
+        make_frame(0xA0 | (crc as u8) << 2, addr, &[blksize, 0])
+    }
+
+    pub fn abort(&mut self, code: SdoAbortCode) -> [u8; 8] {
+        self.state = ClientState::Idle;
+        make_abort_frame(&self.address, code)
+    }
+
+    fn fail(&mut self, code: SdoAbortCode) -> SdoTransfer {
+        SdoTransfer::Aborted {
+            frames: vec![self.abort(code)],
+            code,
+        }
+    }
+
+    fn finish(&mut self, frames: Vec<[u8; 8]>, data: Vec<u8>) -> SdoTransfer {
+        self.state = ClientState::Idle;
+        SdoTransfer::Finished { frames, data }
+    }
+
+    fn check_initiate(
+        &mut self,
+        frame: &[u8; 8],
+        command: u8,
+        mask: u8,
+    ) -> Result<(), SdoAbortCode> {
+        if frame[0] & mask != command {
+            return Err(SdoAbortCode::InvalidCommandSpecifier);
+        }
+        if get_multiplexer(frame) != self.address {
+            return Err(SdoAbortCode::GeneralError);
+        }
+        Ok(())
+    }
+
+    pub fn handle_frame(&mut self, frame: &[u8; 8]) -> SdoTransfer {
+        //Segments never use sequence number 0 so aborts can not be mistaken
+        //for block segments
+        if let Some((_, code)) = parse_abort_frame(frame)
+            && !self.is_idle()
+        {
+            self.state = ClientState::Idle;
+            return SdoTransfer::Aborted {
+                frames: Vec::new(),
+                code,
+            };
+        }
+        let state = std::mem::replace(&mut self.state, ClientState::Idle);
+        match self.step(state, frame) {
+            Ok(transfer) => transfer,
+            Err(code) => self.fail(code),
+        }
+    }
+
+    fn step(&mut self, state: ClientState, frame: &[u8; 8]) -> Result<SdoTransfer, SdoAbortCode> {
+        match state {
+            ClientState::Idle => Ok(SdoTransfer::Pending(Vec::new())),
+            ClientState::UploadInitiate => {
+                self.check_initiate(frame, 0x40, 0xE0)?;
+                if frame[0] & 0x2 != 0 {
+                    return Ok(self.finish(Vec::new(), read_expedited(frame).to_vec()));
+                }
+                let size = (frame[0] & 0x1 != 0).then(|| read_size(frame));
+                self.state = ClientState::UploadSegment {
+                    toggle: false,
+                    data: Vec::new(),
+                    size,
+                };
+                Ok(SdoTransfer::Pending(vec![[0x60, 0, 0, 0, 0, 0, 0, 0]]))
+            }
+            ClientState::UploadSegment {
+                toggle,
+                mut data,
+                size,
+            } => {
+                if frame[0] & 0xE0 != 0x00 {
+                    return Err(SdoAbortCode::InvalidCommandSpecifier);
+                }
+                if (frame[0] & 0x10 != 0) != toggle {
+                    return Err(SdoAbortCode::ToggleBitNotAlternated);
+                }
+                let (segment, last) = read_segment(frame);
+                data.extend_from_slice(segment);
+                if last {
+                    if size.is_some_and(|s| s as usize != data.len()) {
+                        return Err(SdoAbortCode::LengthMismatch);
+                    }
+                    return Ok(self.finish(Vec::new(), data));
+                }
+                let toggle = !toggle;
+                self.state = ClientState::UploadSegment { toggle, data, size };
+                Ok(SdoTransfer::Pending(vec![[
+                    0x60 | (toggle as u8) << 4,
+                    0,
+                    0,
+                    0,
+                    0,
+                    0,
+                    0,
+                    0,
+                ]]))
+            }
+            ClientState::DownloadInitiate { data, expedited } => {
+                self.check_initiate(frame, 0x60, 0xE0)?;
+                if expedited {
+                    return Ok(self.finish(Vec::new(), Vec::new()));
+                }
+                let (segment, last) = make_segment(0x00, false, &data, 0);
+                self.state = ClientState::DownloadSegment {
+                    data,
+                    offset: 0,
+                    toggle: false,
+                    last,
+                };
+                Ok(SdoTransfer::Pending(vec![segment]))
+            }
+            ClientState::DownloadSegment {
+                data,
+                offset,
+                toggle,
+                last,
+            } => {
+                if frame[0] & 0xE0 != 0x20 {
+                    return Err(SdoAbortCode::InvalidCommandSpecifier);
+                }
+                if (frame[0] & 0x10 != 0) != toggle {
+                    return Err(SdoAbortCode::ToggleBitNotAlternated);
+                }
+                if last {
+                    return Ok(self.finish(Vec::new(), Vec::new()));
+                }
+                let offset = offset + super::SDO_SEGMENT_BYTES;
+                let toggle = !toggle;
+                let (segment, last) = make_segment(0x00, toggle, &data, offset);
+                self.state = ClientState::DownloadSegment {
+                    data,
+                    offset,
+                    toggle,
+                    last,
+                };
+                Ok(SdoTransfer::Pending(vec![segment]))
+            }
+            ClientState::BlockDownloadInitiate { data, crc } => {
+                self.check_initiate(frame, 0xA0, 0xE3)?;
+                let blksize = frame[4];
+                if blksize == 0 || blksize > SDO_MAX_BLOCK_SIZE {
+                    return Err(SdoAbortCode::InvalidBlockSize);
+                }
+                let mut sender = BlockSender::new(data, blksize);
+                let frames = sender.next_block();
+                self.state = ClientState::BlockDownload {
+                    sender,
+                    crc: crc && frame[0] & 0x4 != 0,
+                };
+                Ok(SdoTransfer::Pending(frames))
+            }
+            ClientState::BlockDownload { mut sender, crc } => {
+                if frame[0] != 0xA2 {
+                    return Err(SdoAbortCode::InvalidCommandSpecifier);
+                }
+                if !sender.acknowledge(frame[1], frame[2])? {
+                    let frames = sender.next_block();
+                    self.state = ClientState::BlockDownload { sender, crc };
+                    return Ok(SdoTransfer::Pending(frames));
+                }
+                let checksum = if crc { sdo_crc16(&sender.data) } else { 0 };
+                let mut end = [0u8; 8];
+                end[0] = 0xC1 | sender.get_unused_bytes() << 2;
+                end[1..3].copy_from_slice(&checksum.to_le_bytes());
+                self.state = ClientState::BlockDownloadEnd;
+                Ok(SdoTransfer::Pending(vec![end]))
+            }
+            ClientState::BlockDownloadEnd => {
+                if frame[0] != 0xA1 {
+                    return Err(SdoAbortCode::InvalidCommandSpecifier);
+                }
+                Ok(self.finish(Vec::new(), Vec::new()))
+            }
+            ClientState::BlockUploadInitiate { blksize, crc } => {
+                self.check_initiate(frame, 0xC0, 0xE1)?;
+                let size = (frame[0] & 0x2 != 0).then(|| read_size(frame));
+                self.state = ClientState::BlockUpload {
+                    receiver: BlockReceiver::new(blksize),
+                    crc: crc && frame[0] & 0x4 != 0,
+                    size,
+                };
+                Ok(SdoTransfer::Pending(vec![[0xA3, 0, 0, 0, 0, 0, 0, 0]]))
+            }
+            ClientState::BlockUpload {
+                mut receiver,
+                crc,
+                size,
+            } => {
+                if !receiver.complete {
+                    let ack = receiver.push(frame);
+                    self.state = ClientState::BlockUpload {
+                        receiver,
+                        crc,
+                        size,
+                    };
+                    return Ok(SdoTransfer::Pending(ack.into_iter().collect()));
+                }
+                if frame[0] & 0xE3 != 0xC1 {
+                    return Err(SdoAbortCode::InvalidCommandSpecifier);
+                }
+                receiver.finish((frame[0] >> 2) & 0x7)?;
+                if crc && sdo_crc16(&receiver.data) != u16::from_le_bytes([frame[1], frame[2]]) {
+                    return Err(SdoAbortCode::CrcError);
+                }
+                if size.is_some_and(|s| s as usize != receiver.data.len()) {
+                    return Err(SdoAbortCode::LengthMismatch);
+                }
+                Ok(self.finish(vec![[0xA1, 0, 0, 0, 0, 0, 0, 0]], receiver.data))
+            }
+        }
+    }
+}
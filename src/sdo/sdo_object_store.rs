@@ -0,0 +1,22 @@
+use std::collections::HashMap;
+
+use super::SdoAbortCode;
+use crate::eds_file::Address;
+
+//Storage behind an SDO server, values are passed as their little endian
+//encoding
+pub trait SdoObjectStore {
+    fn read(&mut self, addr: &Address) -> Result<Vec<u8>, SdoAbortCode>;
+    fn write(&mut self, addr: &Address, data: &[u8]) -> Result<(), SdoAbortCode>;
+}
+
+impl SdoObjectStore for HashMap<Address, Vec<u8>> {
+    fn read(&mut self, addr: &Address) -> Result<Vec<u8>, SdoAbortCode> {
+        self.get(addr).cloned().ok_or(SdoAbortCode::NoDataAvailable)
+    }
+
+    fn write(&mut self, addr: &Address, data: &[u8]) -> Result<(), SdoAbortCode> {
+        self.insert(*addr, data.to_vec());
+        Ok(())
+    }
+}
@@ -0,0 +1,322 @@
+use std::cmp::Ordering;
+
+use super::{
+    BlockReceiver, BlockSender, SDO_EXPEDITED_BYTES, SDO_MAX_BLOCK_SIZE, SDO_SEGMENT_BYTES,
+    SdoAbortCode, SdoObjectStore, get_multiplexer, make_abort_frame, make_frame, make_segment,
+    parse_abort_frame, read_expedited, read_segment, read_size, sdo_crc16,
+};
+use crate::eds_file::{Address, EDSFile, EDSObject, eds_value_compare, eds_value_from_bytes};
+
+#[derive(Debug, Clone)]
+enum ServerState {
+    Idle,
+    DownloadSegment {
+        toggle: bool,
+        data: Vec<u8>,
+        size: Option<u32>,
+    },
+    UploadSegment {
+        data: Vec<u8>,
+        offset: usize,
+        toggle: bool,
+    },
+    BlockDownload {
+        receiver: BlockReceiver,
+        crc: bool,
+        size: Option<u32>,
+    },
+    BlockUploadInitiate {
+        sender: BlockSender,
+        crc: bool,
+    },
+    BlockUpload {
+        sender: BlockSender,
+        crc: bool,
+    },
+    BlockUploadEnd,
+}
+
+#[derive(Debug, Clone)]
+pub struct SdoServer {
+    file: EDSFile,
+    pub block_size: u8,
+    address: Address,
+    state: ServerState,
+}
+
+impl SdoServer {
+    pub fn new(file: &EDSFile) -> SdoServer {
+        SdoServer {
+            file: file.clone(),
+            block_size: SDO_MAX_BLOCK_SIZE,
+            address: Address::new(0, 0),
+            state: ServerState::Idle,
+        }
+    }
+
+    pub fn is_idle(&self) -> bool {
+        matches!(self.state, ServerState::Idle)
+    }
+
+    fn find_object(&self, addr: &Address) -> Result<&EDSObject, SdoAbortCode> {
+        self.file
+            .get_index_object(addr.index)
+            .ok_or(SdoAbortCode::ObjectDoesNotExist)?
+            .get_sub_object(addr.subindex)
+            .ok_or(SdoAbortCode::SubindexDoesNotExist)
+    }
+
+    pub fn validate_read(&self, addr: &Address) -> Result<(), SdoAbortCode> {
+        let obj = self.find_object(addr)?;
+        match obj.get_access_mode() {
+            None => Err(SdoAbortCode::UnsupportedAccess),
+            Some(mode) if !mode.is_readable() => Err(SdoAbortCode::ReadWriteOnly),
+            Some(_) => Ok(()),
+        }
+    }
+
+    fn check_writable(&self, addr: &Address) -> Result<&EDSObject, SdoAbortCode> {
+        let obj = self.find_object(addr)?;
+        match obj.get_access_mode() {
+            None => Err(SdoAbortCode::UnsupportedAccess),
+            Some(mode) if !mode.is_writable() => Err(SdoAbortCode::WriteReadOnly),
+            Some(_) => Ok(obj),
+        }
+    }
+
+    //Checks access mode, size and limits of a value written to the object
+    pub fn validate_write(&self, addr: &Address, data: &[u8]) -> Result<(), SdoAbortCode> {
+        let obj = self.check_writable(addr)?;
+        let data_type = obj.get_data_type().ok_or(SdoAbortCode::UnsupportedAccess)?;
+        if let Some(bits) = data_type.bit_size() {
+            match data.len().cmp(&(bits.div_ceil(8) as usize)) {
+                Ordering::Greater => return Err(SdoAbortCode::LengthTooHigh),
+                Ordering::Less => return Err(SdoAbortCode::LengthTooLow),
+                Ordering::Equal => {}
+            }
+        }
+        let value = eds_value_from_bytes(data, data_type).ok_or(SdoAbortCode::InvalidValue)?;
+        let (low, high) = obj.get_limits();
+        if let Some(low) = low
+            && eds_value_compare(value.as_ref(), low, data_type) == Some(Ordering::Less)
+        {
+            return Err(SdoAbortCode::ValueTooLow);
+        }
+        if let Some(high) = high
+            && eds_value_compare(value.as_ref(), high, data_type) == Some(Ordering::Greater)
+        {
+            return Err(SdoAbortCode::ValueTooHigh);
+        }
+        Ok(())
+    }
+
+    fn write(
+        &self,
+        data: &[u8],
+        store: &mut dyn SdoObjectStore,
+    ) -> Result<Vec<[u8; 8]>, SdoAbortCode> {
+        self.validate_write(&self.address, data)?;
+        store.write(&self.address, data)?;
+        Ok(Vec::new())
+    }
+
+    fn read(&self, store: &mut dyn SdoObjectStore) -> Result<Vec<u8>, SdoAbortCode> {
+        self.validate_read(&self.address)?;
+        store.read(&self.address)
+    }
+
+    pub fn handle_frame(
+        &mut self,
+        frame: &[u8; 8],
+        store: &mut dyn SdoObjectStore,
+    ) -> Vec<[u8; 8]> {
+        if parse_abort_frame(frame).is_some() {
+            self.state = ServerState::Idle;
+            return Vec::new();
+        }
+        let state = std::mem::replace(&mut self.state, ServerState::Idle);
+        match self.step(state, frame, store) {
+            Ok(frames) => frames,
+            Err(code) => {
+                self.state = ServerState::Idle;
+                vec![make_abort_frame(&self.address, code)]
+            }
+        }
+    }
+
+    fn step(
+        &mut self,
+        state: ServerState,
+        frame: &[u8; 8],
+        store: &mut dyn SdoObjectStore,
+    ) -> Result<Vec<[u8; 8]>, SdoAbortCode> {
+        match state {
+            ServerState::DownloadSegment { toggle, data, size } if frame[0] & 0xE0 == 0x00 => {
+                self.download_segment(toggle, data, size, frame, store)
+            }
+            ServerState::UploadSegment {
+                data,
+                offset,
+                toggle,
+            } if frame[0] & 0xE0 == 0x60 => {
+                if (frame[0] & 0x10 != 0) != toggle {
+                    return Err(SdoAbortCode::ToggleBitNotAlternated);
+                }
+                let (segment, last) = make_segment(0x00, toggle, &data, offset);
+                if !last {
+                    self.state = ServerState::UploadSegment {
+                        data,
+                        offset: offset + SDO_SEGMENT_BYTES,
+                        toggle: !toggle,
+                    };
+                }
+                Ok(vec![segment])
+            }
+            ServerState::BlockDownload {
+                mut receiver,
+                crc,
+                size,
+            } => {
+                if !receiver.complete {
+                    let ack = receiver.push(frame);
+                    self.state = ServerState::BlockDownload {
+                        receiver,
+                        crc,
+                        size,
+                    };
+                    return Ok(ack.into_iter().collect());
+                }
+                if frame[0] & 0xE3 != 0xC1 {
+                    return Err(SdoAbortCode::InvalidCommandSpecifier);
+                }
+                receiver.finish((frame[0] >> 2) & 0x7)?;
+                if crc && sdo_crc16(&receiver.data) != u16::from_le_bytes([frame[1], frame[2]]) {
+                    return Err(SdoAbortCode::CrcError);
+                }
+                if size.is_some_and(|s| s as usize != receiver.data.len()) {
+                    return Err(SdoAbortCode::LengthMismatch);
+                }
+                self.write(&receiver.data, store)?;
+                Ok(vec![[0xA1, 0, 0, 0, 0, 0, 0, 0]])
+            }
+            ServerState::BlockUploadInitiate { mut sender, crc } if frame[0] == 0xA3 => {
+                let frames = sender.next_block();
+                self.state = ServerState::BlockUpload { sender, crc };
+                Ok(frames)
+            }
+            ServerState::BlockUpload { mut sender, crc } if frame[0] == 0xA2 => {
+                if !sender.acknowledge(frame[1], frame[2])? {
+                    let frames = sender.next_block();
+                    self.state = ServerState::BlockUpload { sender, crc };
+                    return Ok(frames);
+                }
+                let checksum = if crc { sdo_crc16(&sender.data) } else { 0 };
+                let mut end = [0u8; 8];
+                end[0] = 0xC1 | sender.get_unused_bytes() << 2;
+                end[1..3].copy_from_slice(&checksum.to_le_bytes());
+                self.state = ServerState::BlockUploadEnd;
+                Ok(vec![end])
+            }
+            ServerState::BlockUploadEnd if frame[0] == 0xA1 => Ok(Vec::new()),
+            _ => self.initiate(frame, store),
+        }
+    }
+
+    fn download_segment(
+        &mut self,
+        toggle: bool,
+        mut data: Vec<u8>,
+        size: Option<u32>,
+        frame: &[u8; 8],
+        store: &mut dyn SdoObjectStore,
+    ) -> Result<Vec<[u8; 8]>, SdoAbortCode> {
+        if (frame[0] & 0x10 != 0) != toggle {
+            return Err(SdoAbortCode::ToggleBitNotAlternated);
+        }
+        let (segment, last) = read_segment(frame);
+        data.extend_from_slice(segment);
+        if last {
+            if size.is_some_and(|s| s as usize != data.len()) {
+                return Err(SdoAbortCode::LengthMismatch);
+            }
+            self.write(&data, store)?;
+        } else {
+            self.state = ServerState::DownloadSegment {
+                toggle: !toggle,
+                data,
+                size,
+            };
+        }
+        Ok(vec![[0x20 | (toggle as u8) << 4, 0, 0, 0, 0, 0, 0, 0]])
+    }
+
+    fn initiate(
+        &mut self,
+        frame: &[u8; 8],
+        store: &mut dyn SdoObjectStore,
+    ) -> Result<Vec<[u8; 8]>, SdoAbortCode> {
+        self.address = get_multiplexer(frame);
+        let addr = self.address;
+        let size = (frame[0] & 0x1 != 0).then(|| read_size(frame));
+        match frame[0] >> 5 {
+            1 => {
+                let obj = self.check_writable(&addr)?;
+                if frame[0] & 0x2 != 0 {
+                    let mut data = read_expedited(frame).to_vec();
+                    //Without a size indication the size follows from the object
+                    if frame[0] & 0x1 == 0
+                        && let Some(bits) = obj.get_data_type().and_then(|d| d.bit_size())
+                    {
+                        data.truncate(bits.div_ceil(8) as usize);
+                    }
+                    self.write(&data, store)?;
+                } else {
+                    self.state = ServerState::DownloadSegment {
+                        toggle: false,
+                        data: Vec::new(),
+                        size,
+                    };
+                }
+                Ok(vec![make_frame(0x60, &addr, &[])])
+            }
+            2 => {
+                let data = self.read(store)?;
+                if !data.is_empty() && data.len() <= SDO_EXPEDITED_BYTES {
+                    let unused = (SDO_EXPEDITED_BYTES - data.len()) as u8;
+                    return Ok(vec![make_frame(0x43 | unused << 2, &addr, &data)]);
+                }
+                let frame = make_frame(0x41, &addr, &(data.len() as u32).to_le_bytes());
+                self.state = ServerState::UploadSegment {
+                    data,
+                    offset: 0,
+                    toggle: false,
+                };
+                Ok(vec![frame])
+            }
+            5 if frame[0] & 0x3 == 0 => {
+                let data = self.read(store)?;
+                let blksize = frame[4];
+                if blksize == 0 || blksize > SDO_MAX_BLOCK_SIZE {
+                    return Err(SdoAbortCode::InvalidBlockSize);
+                }
+                let len = (data.len() as u32).to_le_bytes();
+                self.state = ServerState::BlockUploadInitiate {
+                    sender: BlockSender::new(data, blksize),
+                    crc: frame[0] & 0x4 != 0,
+                };
+                Ok(vec![make_frame(0xC6, &addr, &len)])
+            }
+            6 if frame[0] & 0x1 == 0 => {
+                self.check_writable(&addr)?;
+                let size = (frame[0] & 0x2 != 0).then(|| read_size(frame));
+                self.state = ServerState::BlockDownload {
+                    receiver: BlockReceiver::new(self.block_size),
+                    crc: frame[0] & 0x4 != 0,
+                    size,
+                };
+                Ok(vec![make_frame(0xA4, &addr, &[self.block_size])])
+            }
+            _ => Err(SdoAbortCode::InvalidCommandSpecifier),
+        }
+    }
+}
@@ -0,0 +1,262 @@
+use std::collections::HashMap;
+
+use crate::eds_file::{Address, EDSFile};
+use crate::sdo::{
+    SdoAbortCode, SdoClient, SdoServer, SdoTransfer, make_abort_frame, parse_abort_frame, sdo_crc16,
+};
+use crate::tests::utils::*;
+
+fn make_sdo_file() -> EDSFile {
+    let lines = vec![
+        "[MandatoryObjects]",
+        "SupportedObjects=0",
+        "[OptionalObjects]",
+        "SupportedObjects=0",
+        "[ManufacturerObjects]",
+        "SupportedObjects=3",
+        "1=0x2000",
+        "2=0x2001",
+        "3=0x2002",
+        "[2000]",
+        "ParameterName=Speed",
+        "ObjectType=0x7",
+        "DataType=0x0003",
+        "AccessType=rw",
+        "DefaultValue=0",
+        "LowLimit=-100",
+        "HighLimit=100",
+        "[2001]",
+        "ParameterName=Label",
+        "ObjectType=0x7",
+        "DataType=0x0009",
+        "AccessType=rw",
+        "[2002]",
+        "ParameterName=Serial",
+        "ObjectType=0x7",
+        "DataType=0x0007",
+        "AccessType=ro",
+    ];
+    make_eds_file(&lines).expect("Failed to parse eds file!")
+}
+
+//Passes frames between client and server until the transfer ends, frames
+//for which drop returns true are lost on the way to the server
+fn run_transfer(
+    client: &mut SdoClient,
+    server: &mut SdoServer,
+    store: &mut HashMap<Address, Vec<u8>>,
+    first: [u8; 8],
+    drop: &dyn Fn(&[u8; 8]) -> bool,
+) -> SdoTransfer {
+    let mut requests = vec![first];
+    for _ in 0..1000 {
+        let mut responses = Vec::new();
+        for f in requests.iter().filter(|f| !drop(f)) {
+            responses.extend(server.handle_frame(f, store));
+        }
+        requests.clear();
+        for r in responses {
+            match client.handle_frame(&r) {
+                SdoTransfer::Pending(frames) => requests.extend(frames),
+                done => {
+                    let frames = match &done {
+                        SdoTransfer::Finished { frames, data: _ } => frames,
+                        SdoTransfer::Aborted { frames, code: _ } => frames,
+                        SdoTransfer::Pending(frames) => frames,
+                    };
+                    for f in frames {
+                        server.handle_frame(f, store);
+                    }
+                    return done;
+                }
+            }
+        }
+    }
+    panic!("Transfer did not finish!");
+}
+
+fn no_drop(_: &[u8; 8]) -> bool {
+    false
+}
+
+#[test]
+fn test_crc() {
+    assert_eq!(sdo_crc16(b"123456789"), 0x31C3);
+    assert_eq!(sdo_crc16(&[]), 0);
+}
+
+#[test]
+fn test_abort_codes() {
+    assert_eq!(SdoAbortCode::parse(0x06010002), SdoAbortCode::WriteReadOnly);
+    assert_eq!(SdoAbortCode::WriteReadOnly.get_code(), 0x06010002);
+    assert_eq!(
+        SdoAbortCode::parse(0x12345678),
+        SdoAbortCode::Other { code: 0x12345678 }
+    );
+    assert_eq!(
+        SdoAbortCode::ToggleBitNotAlternated.to_string(),
+        "0x05030000: Toggle bit not alternated"
+    );
+    let frame = make_abort_frame(&Address::new(0x1018, 1), SdoAbortCode::ObjectDoesNotExist);
+    assert_eq!(frame, [0x80, 0x18, 0x10, 0x01, 0x00, 0x00, 0x02, 0x06]);
+    assert_eq!(
+        parse_abort_frame(&frame),
+        Some((Address::new(0x1018, 1), SdoAbortCode::ObjectDoesNotExist))
+    );
+}
+
+#[test]
+fn test_expedited() {
+    let file = make_sdo_file();
+    let mut server = SdoServer::new(&file);
+    let mut client = SdoClient::new();
+    let mut store = HashMap::new();
+    let addr = Address::new(0x2000, 0);
+
+    let frame = client.download(&addr, &(-50i16).to_le_bytes());
+    assert_eq!(frame, [0x2B, 0x00, 0x20, 0x00, 0xCE, 0xFF, 0x00, 0x00]);
+    let result = run_transfer(&mut client, &mut server, &mut store, frame, &no_drop);
+    assert!(matches!(result, SdoTransfer::Finished { .. }));
+    assert!(client.is_idle());
+
+    let frame = client.upload(&addr);
+    let result = run_transfer(&mut client, &mut server, &mut store, frame, &no_drop);
+    assert_eq!(
+        result,
+        SdoTransfer::Finished {
+            frames: Vec::new(),
+            data: vec![0xCE, 0xFF]
+        }
+    );
+
+    let frame = client.download(&addr, &500i16.to_le_bytes());
+    let result = run_transfer(&mut client, &mut server, &mut store, frame, &no_drop);
+    assert_eq!(
+        result,
+        SdoTransfer::Aborted {
+            frames: Vec::new(),
+            code: SdoAbortCode::ValueTooHigh
+        }
+    );
+    let frame = client.download(&addr, &[1, 2, 3]);
+    let result = run_transfer(&mut client, &mut server, &mut store, frame, &no_drop);
+    assert!(matches!(
+        result,
+        SdoTransfer::Aborted {
+            frames: _,
+            code: SdoAbortCode::LengthTooHigh
+        }
+    ));
+    assert_eq!(store.get(&addr), Some(&vec![0xCE, 0xFF]));
+}
+
+#[test]
+fn test_access_errors() {
+    let file = make_sdo_file();
+    let mut server = SdoServer::new(&file);
+    let mut client = SdoClient::new();
+    let mut store = HashMap::new();
+
+    let cases = [
+        (Address::new(0x2002, 0), SdoAbortCode::WriteReadOnly),
+        (Address::new(0x3000, 0), SdoAbortCode::ObjectDoesNotExist),
+        (Address::new(0x2000, 1), SdoAbortCode::SubindexDoesNotExist),
+    ];
+    for (addr, code) in cases {
+        let frame = client.download(&addr, &[0, 0, 0, 0]);
+        let result = run_transfer(&mut client, &mut server, &mut store, frame, &no_drop);
+        assert_eq!(
+            result,
+            SdoTransfer::Aborted {
+                frames: Vec::new(),
+                code
+            }
+        );
+    }
+    assert_eq!(
+        server.validate_write(&Address::new(0x2000, 0), &(-101i16).to_le_bytes()),
+        Err(SdoAbortCode::ValueTooLow)
+    );
+    assert_eq!(server.validate_read(&Address::new(0x2002, 0)), Ok(()));
+}
+
+#[test]
+fn test_segmented() {
+    let file = make_sdo_file();
+    let mut server = SdoServer::new(&file);
+    let mut client = SdoClient::new();
+    let mut store = HashMap::new();
+    let addr = Address::new(0x2001, 0);
+    let label = b"Front left wheel drive";
+
+    let frame = client.download(&addr, label);
+    let result = run_transfer(&mut client, &mut server, &mut store, frame, &no_drop);
+    assert!(matches!(result, SdoTransfer::Finished { .. }));
+    assert_eq!(store.get(&addr), Some(&label.to_vec()));
+
+    let frame = client.upload(&addr);
+    let result = run_transfer(&mut client, &mut server, &mut store, frame, &no_drop);
+    assert_eq!(
+        result,
+        SdoTransfer::Finished {
+            frames: Vec::new(),
+            data: label.to_vec()
+        }
+    );
+
+    //A repeated toggle bit aborts the transfer
+    server.handle_frame(&client.download(&addr, label), &mut store);
+    let segment = [0x00, b'a', b'b', b'c', b'd', b'e', b'f', b'g'];
+    assert_eq!(
+        server.handle_frame(&segment, &mut store),
+        vec![[0x20, 0, 0, 0, 0, 0, 0, 0]]
+    );
+    let response = server.handle_frame(&segment, &mut store);
+    assert_eq!(
+        parse_abort_frame(&response[0]),
+        Some((addr, SdoAbortCode::ToggleBitNotAlternated))
+    );
+    assert!(server.is_idle());
+}
+
+#[test]
+fn test_block() {
+    let file = make_sdo_file();
+    let mut server = SdoServer::new(&file);
+    server.block_size = 4;
+    let mut client = SdoClient::new();
+    let mut store = HashMap::new();
+    let addr = Address::new(0x2001, 0);
+    let label: Vec<u8> = (0..200).map(|i| b'A' + (i % 26) as u8).collect();
+
+    let frame = client.block_download(&addr, &label, true);
+    let result = run_transfer(&mut client, &mut server, &mut store, frame, &no_drop);
+    assert!(matches!(result, SdoTransfer::Finished { .. }));
+    assert_eq!(store.get(&addr), Some(&label));
+
+    let frame = client.block_upload(&addr, 5, true);
+    let result = run_transfer(&mut client, &mut server, &mut store, frame, &no_drop);
+    assert_eq!(
+        result,
+        SdoTransfer::Finished {
+            frames: vec![[0xA1, 0, 0, 0, 0, 0, 0, 0]],
+            data: label.clone()
+        }
+    );
+    assert!(server.is_idle());
+
+    //Lost segments are repeated after the acknowledge
+    let frame = client.block_download(&addr, &label[..100], false);
+    let dropped = std::cell::Cell::new(false);
+    let drop = |f: &[u8; 8]| {
+        if f[0] == 0x02 && !dropped.get() {
+            dropped.set(true);
+            return true;
+        }
+        false
+    };
+    let result = run_transfer(&mut client, &mut server, &mut store, frame, &drop);
+    assert!(dropped.get());
+    assert!(matches!(result, SdoTransfer::Finished { .. }));
+    assert_eq!(store.get(&addr), Some(&label[..100].to_vec()));
+}
@@ -68,7 +68,6 @@ impl StructuredFile {
         Some((key, val))
     }
 
-    #[cfg(test)]
     pub fn get_objects(&self) -> Vec<&StructuredFileObject> {
         self.objects.values().collect()
     }
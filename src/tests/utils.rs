@@ -4,6 +4,12 @@ use std::{
 };
 use tempfile::tempfile;
 
+use crate::ParseError;
+use crate::dcf::DCFFile;
+use crate::eds_file::EDSFile;
+use crate::raw_file::RawFile;
+use crate::structured_file::StructuredFile;
+
 pub fn make_tmp_file(data: String) -> File {
     let mut file = tempfile().expect("Failed to create temporary file!");
     file.write(data.as_bytes())
@@ -24,3 +30,143 @@ pub fn make_string(lines: &Vec<&str>) -> String {
     }
     out
 }
+
+pub const EDS_HEADER: [&str; 36] = [
+    "[FileInfo]",
+    "FileName=test.eds",
+    "FileVersion=1",
+    "FileRevision=4",
+    "EDSVersion=4.0",
+    "Description=aaaaaaaaa",
+    "CreationTime=04:09PM",
+    "CreationDate=08-06-2012",
+    "CreatedBy=Peabody",
+    "ModificationTime=02:46PM",
+    "ModificationDate=04-25-2014",
+    "ModifiedBy=Peabody",
+    "[DeviceInfo]",
+    "VendorName=Aperture Science",
+    "VendorNumber=0x286",
+    "ProductName=ED201",
+    "ProductNumber=0x488",
+    "RevisionNumber=0x00030012",
+    "OrderCode=ED201",
+    "BaudRate_10=0",
+    "BaudRate_20=1",
+    "BaudRate_50=1",
+    "BaudRate_125=1",
+    "BaudRate_250=1",
+    "BaudRate_500=1",
+    "BaudRate_800=1",
+    "BaudRate_1000=1",
+    "DynamicChannelsSupported=0",
+    "GroupMessaging=0",
+    "LSS_Supported=0",
+    "Granularity=8",
+    "SimpleBootUpSlave=1",
+    "SimpleBootUpMaster=0",
+    "NrOfRXPDO=5",
+    "NrOfTXPDO=5",
+    "",
+];
+
+pub fn make_eds_file(lines: &Vec<&str>) -> Result<EDSFile, ParseError> {
+    let mut data = EDS_HEADER.to_vec();
+    data.extend(lines.iter());
+    let mut tmp = make_tmp_file(make_string(&data));
+    let rfile = RawFile::new_from_file(&mut tmp)?;
+    let sfile = StructuredFile::parse(rfile)?;
+    EDSFile::parse(&sfile)
+}
+
+pub const PDO_DCF: [&str; 80] = [
+    "[DeviceComissioning]",
+    "NodeID=2",
+    "Baudrate=500",
+    "[MandatoryObjects]",
+    "SupportedObjects=0",
+    "[OptionalObjects]",
+    "SupportedObjects=5",
+    "1=0x1005",
+    "2=0x1006",
+    "3=0x1017",
+    "4=0x1800",
+    "5=0x1A00",
+    "[ManufacturerObjects]",
+    "SupportedObjects=1",
+    "1=0x2000",
+    "[1005]",
+    "ParameterName=COB-ID SYNC message",
+    "ObjectType=0x7",
+    "DataType=0x0007",
+    "AccessType=rw",
+    "DefaultValue=0x80",
+    "ParameterValue=0x40000080",
+    "[1006]",
+    "ParameterName=Communication cycle period",
+    "ObjectType=0x7",
+    "DataType=0x0007",
+    "AccessType=rw",
+    "DefaultValue=0",
+    "ParameterValue=10000",
+    "[1017]",
+    "ParameterName=Producer heartbeat time",
+    "ObjectType=0x7",
+    "DataType=0x0006",
+    "AccessType=rw",
+    "DefaultValue=100",
+    "[1800]",
+    "ParameterName=TPDO1 communication parameter",
+    "ObjectType=0x9",
+    "SubNumber=3",
+    "[1800sub0]",
+    "ParameterName=Highest sub-index supported",
+    "ObjectType=0x7",
+    "DataType=0x0005",
+    "AccessType=const",
+    "DefaultValue=2",
+    "[1800sub1]",
+    "ParameterName=COB-ID used by TPDO",
+    "ObjectType=0x7",
+    "DataType=0x0007",
+    "AccessType=rw",
+    "DefaultValue=$NODEID+0x180",
+    "[1800sub2]",
+    "ParameterName=Transmission type",
+    "ObjectType=0x7",
+    "DataType=0x0005",
+    "AccessType=rw",
+    "DefaultValue=2",
+    "[1A00]",
+    "ParameterName=TPDO1 mapping parameter",
+    "ObjectType=0x9",
+    "SubNumber=2",
+    "[1A00sub0]",
+    "ParameterName=Number of mapped objects",
+    "ObjectType=0x7",
+    "DataType=0x0005",
+    "AccessType=rw",
+    "DefaultValue=1",
+    "[1A00sub1]",
+    "ParameterName=Mapped object 1",
+    "ObjectType=0x7",
+    "DataType=0x0007",
+    "AccessType=rw",
+    "DefaultValue=0x20000010",
+    "[2000]",
+    "ParameterName=Speed",
+    "ObjectType=0x7",
+    "DataType=0x0006",
+    "AccessType=ro",
+    "PDOMapping=1",
+    "",
+];
+
+pub fn make_dcf_file(lines: &Vec<&str>) -> Result<DCFFile, ParseError> {
+    let mut data = EDS_HEADER.to_vec();
+    data.extend(lines.iter());
+    let mut tmp = make_tmp_file(make_string(&data));
+    let rfile = RawFile::new_from_file(&mut tmp)?;
+    let sfile = StructuredFile::parse(rfile)?;
+    DCFFile::parse(&sfile)
+}
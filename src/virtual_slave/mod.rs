@@ -0,0 +1,401 @@
+mod nmt_state;
+
+#[cfg(test)]
+mod tests;
+
+pub use nmt_state::*;
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::can_bus::{CanBus, CanFrame};
+use crate::comm::{SDO_SERVER_INDEX, SdoServerParams, SyncParams, read_default};
+use crate::dcf::DCFFile;
+use crate::eds_file::{Address, EDSFile, EDSValue};
+use crate::network::{HEARTBEAT_COB_ID_BASE, HEARTBEAT_TIME_INDEX};
+use crate::object_dictionary::ObjectDictionary;
+use crate::pdo::{PdoCommParams, PdoDirection, PdoMapping};
+use crate::sdo::SdoServer;
+use crate::{CanBusError, CommParamError, ObjectDictionaryError, VirtualSlaveError};
+
+#[derive(Debug, Clone)]
+struct TpdoState {
+    params: PdoCommParams,
+    mapping: Option<PdoMapping>,
+    sync_count: u8,
+    last_sent: Option<Instant>,
+    event_pending: bool,
+}
+
+//Simulated CANopen device answering on a CanBus like the real one would
+pub struct VirtualSlave<B: CanBus> {
+    bus: B,
+    node_id: u8,
+    dcf: DCFFile,
+    dictionary: ObjectDictionary,
+    written: Arc<Mutex<Vec<Address>>>,
+    sdo: SdoServer,
+    state: NmtState,
+    sdo_rx: u32,
+    sdo_tx: u32,
+    sync: Option<SyncParams>,
+    heartbeat_ms: u64,
+    last_heartbeat: Option<Instant>,
+    tpdos: Vec<TpdoState>,
+    rpdos: Vec<(PdoCommParams, PdoMapping)>,
+}
+
+impl<B: CanBus> VirtualSlave<B> {
+    pub fn new(dcf: &DCFFile, bus: B) -> Result<VirtualSlave<B>, CommParamError> {
+        let mut dictionary = ObjectDictionary::from_dcf(dcf);
+        let written = Arc::new(Mutex::new(Vec::new()));
+        let recorder = written.clone();
+        dictionary.subscribe(Box::new(move |change| {
+            if let Ok(mut written) = recorder.lock() {
+                written.push(change.address);
+            }
+        }));
+        let mut out = VirtualSlave {
+            bus,
+            node_id: dcf.get_node_id(),
+            dcf: dcf.clone(),
+            sdo: SdoServer::new(&dcf.eds),
+            dictionary,
+            written,
+            state: NmtState::Initialising,
+            sdo_rx: 0,
+            sdo_tx: 0,
+            sync: None,
+            heartbeat_ms: 0,
+            last_heartbeat: None,
+            tpdos: Vec::new(),
+            rpdos: Vec::new(),
+        };
+        out.load_comm_params()?;
+        Ok(out)
+    }
+
+    pub fn from_eds(eds: &EDSFile, node_id: u8, bus: B) -> Result<VirtualSlave<B>, CommParamError> {
+        Self::new(&DCFFile::from_eds(eds.clone(), node_id), bus)
+    }
+
+    pub fn get_node_id(&self) -> u8 {
+        self.node_id
+    }
+
+    pub fn get_state(&self) -> NmtState {
+        self.state
+    }
+
+    pub fn get_bus(&mut self) -> &mut B {
+        &mut self.bus
+    }
+
+    pub fn get_dictionary(&self) -> &ObjectDictionary {
+        &self.dictionary
+    }
+
+    //Changes made here are picked up on the next poll
+    pub fn get_dictionary_mut(&mut self) -> &mut ObjectDictionary {
+        &mut self.dictionary
+    }
+
+    pub fn get_value(&self, addr: &Address) -> Option<&dyn EDSValue> {
+        self.dictionary.get(addr)
+    }
+
+    //Sets a value from the application side, TPDOs mapping it see an event
+    pub fn set_value(
+        &mut self,
+        addr: &Address,
+        value: Box<dyn EDSValue>,
+    ) -> Result<(), ObjectDictionaryError> {
+        let result = self.dictionary.set(addr, value);
+        self.process_writes();
+        result
+    }
+
+    fn load_comm_params(&mut self) -> Result<(), CommParamError> {
+        let file = &self.dictionary.to_eds_file();
+        let node_id = self.node_id;
+        let sdo = match file.get_index_object(SDO_SERVER_INDEX) {
+            Some(_) => SdoServerParams::read(file, 1, node_id)?,
+            None => {
+                SdoServerParams::decode(1, 0x600 + node_id as u32, 0x580 + node_id as u32, None)
+            }
+        };
+        let sync = match file.get_index_object(0x1005) {
+            Some(_) => Some(SyncParams::read(file, node_id)?),
+            None => None,
+        };
+        let heartbeat = Address::new(HEARTBEAT_TIME_INDEX, 0);
+        let heartbeat_ms = read_default(file, &heartbeat, node_id)?.unwrap_or(0);
+
+        let mut tpdos = Vec::new();
+        let mut rpdos = Vec::new();
+        for params in PdoCommParams::read_all(file, node_id)? {
+            if !params.valid {
+                continue;
+            }
+            let mapping = PdoMapping::decode(file, params.direction, params.number).ok();
+            match params.direction {
+                PdoDirection::Transmit => tpdos.push(TpdoState {
+                    params,
+                    mapping,
+                    sync_count: 0,
+                    last_sent: None,
+                    event_pending: false,
+                }),
+                PdoDirection::Receive => {
+                    if let Some(mapping) = mapping {
+                        rpdos.push((params, mapping));
+                    }
+                }
+            }
+        }
+        self.sdo_rx = sdo.rx_cob_id;
+        self.sdo_tx = sdo.tx_cob_id;
+        self.sync = sync;
+        self.heartbeat_ms = heartbeat_ms;
+        self.tpdos = tpdos;
+        self.rpdos = rpdos;
+        Ok(())
+    }
+
+    fn send(&mut self, cob_id: u32, extended: bool, data: &[u8]) -> Result<(), CanBusError> {
+        let mut frame = CanFrame::new(cob_id, data);
+        frame.extended = extended;
+        self.bus.send(&frame)
+    }
+
+    fn boot_up(&mut self, now: Instant) -> Result<(), CanBusError> {
+        self.state = NmtState::PreOperational;
+        self.last_heartbeat = Some(now);
+        self.send(HEARTBEAT_COB_ID_BASE + self.node_id as u32, false, &[0])
+    }
+
+    //Resets reload the communication parameters, a node that fails to do so
+    //stays in initialisation
+    fn reset(&mut self, now: Instant) -> Result<(), VirtualSlaveError> {
+        self.clear_writes();
+        self.sdo = SdoServer::new(&self.dcf.eds);
+        self.state = NmtState::Initialising;
+        self.load_comm_params()?;
+        Ok(self.boot_up(now)?)
+    }
+
+    //Sends the boot-up message and enters pre-operational
+    pub fn start(&mut self, now: Instant) -> Result<(), VirtualSlaveError> {
+        Ok(self.boot_up(now)?)
+    }
+
+    //Handles all received frames and sends whatever is due at the given time
+    pub fn poll(&mut self, now: Instant) -> Result<(), VirtualSlaveError> {
+        self.process_writes();
+        while let Some(frame) = self.bus.recv(Duration::ZERO)? {
+            self.handle_frame(&frame, now)?;
+        }
+        Ok(self.process_timers(now)?)
+    }
+
+    //Reacts to values written through SDO, RPDO or the application, invalid
+    //communication parameters keep the previous configuration
+    fn process_writes(&mut self) {
+        let written = match self.written.lock() {
+            Ok(mut written) => std::mem::take(&mut *written),
+            Err(_) => return,
+        };
+        if written.iter().any(|a| (0x1000..0x2000).contains(&a.index)) {
+            let previous = self.tpdos.clone();
+            if self.load_comm_params().is_ok() {
+                for tpdo in &mut self.tpdos {
+                    let old = previous
+                        .iter()
+                        .find(|p| p.params.number == tpdo.params.number);
+                    if let Some(old) = old {
+                        tpdo.last_sent = old.last_sent;
+                    }
+                }
+            }
+        }
+        for tpdo in &mut self.tpdos {
+            let Some(mapping) = &tpdo.mapping else {
+                continue;
+            };
+            if mapping.entries.iter().any(|e| written.contains(&e.address)) {
+                tpdo.event_pending = true;
+            }
+        }
+    }
+
+    fn clear_writes(&mut self) {
+        if let Ok(mut written) = self.written.lock() {
+            written.clear();
+        }
+    }
+
+    fn handle_nmt(&mut self, frame: &CanFrame, now: Instant) -> Result<(), VirtualSlaveError> {
+        if frame.data.len() < 2 || (frame.data[1] != 0 && frame.data[1] != self.node_id) {
+            return Ok(());
+        }
+        match frame.data[0] {
+            NMT_START => {
+                if self.state != NmtState::Operational {
+                    for tpdo in &mut self.tpdos {
+                        tpdo.event_pending = tpdo.params.is_event_driven();
+                        tpdo.sync_count = 0;
+                    }
+                }
+                self.state = NmtState::Operational;
+            }
+            NMT_STOP => self.state = NmtState::Stopped,
+            NMT_ENTER_PRE_OPERATIONAL => self.state = NmtState::PreOperational,
+            NMT_RESET_NODE => {
+                self.dictionary.reset_from_dcf(&self.dcf);
+                return self.reset(now);
+            }
+            NMT_RESET_COMMUNICATION => {
+                self.dictionary
+                    .reset_range(0x1000..=0x1FFF, Some(&self.dcf));
+                return self.reset(now);
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn handle_frame(&mut self, frame: &CanFrame, now: Instant) -> Result<(), VirtualSlaveError> {
+        if frame.cob_id == 0 && !frame.extended {
+            return self.handle_nmt(frame, now);
+        }
+        if matches!(self.state, NmtState::Initialising | NmtState::Stopped) {
+            return Ok(());
+        }
+        if frame.cob_id == self.sdo_rx && !frame.extended && !frame.rtr {
+            let mut request = [0u8; 8];
+            let len = frame.data.len().min(8);
+            request[..len].copy_from_slice(&frame.data[..len]);
+            let responses = self.sdo.handle_frame(&request, &mut self.dictionary);
+            for response in responses {
+                self.send(self.sdo_tx, false, &response)?;
+            }
+            self.process_writes();
+            return Ok(());
+        }
+        if self.state != NmtState::Operational {
+            return Ok(());
+        }
+        if let Some(sync) = &self.sync
+            && frame.cob_id == sync.cob_id
+            && frame.extended == sync.extended
+        {
+            return Ok(self.handle_sync(now)?);
+        }
+        if frame.rtr {
+            let requested = self.tpdos.iter().position(|t| {
+                t.params.cob_id == frame.cob_id
+                    && t.params.extended == frame.extended
+                    && t.params.rtr_allowed
+            });
+            if let Some(i) = requested {
+                return Ok(self.send_tpdo(i, now)?);
+            }
+            return Ok(());
+        }
+        let rpdo = self
+            .rpdos
+            .iter()
+            .find(|(p, _)| p.cob_id == frame.cob_id && p.extended == frame.extended);
+        if let Some((_, mapping)) = rpdo
+            && let Ok(values) = mapping.unpack(&self.dictionary.get_file().device_info, &frame.data)
+        {
+            for (addr, value) in values {
+                let _ = self.dictionary.set(&addr, value);
+            }
+            self.process_writes();
+        }
+        Ok(())
+    }
+
+    fn handle_sync(&mut self, now: Instant) -> Result<(), CanBusError> {
+        for i in 0..self.tpdos.len() {
+            let tpdo = &mut self.tpdos[i];
+            let due = match tpdo.params.transmission_type {
+                0 => tpdo.event_pending,
+                n @ 1..=240 => {
+                    tpdo.sync_count += 1;
+                    tpdo.sync_count >= n
+                }
+                _ => false,
+            };
+            if due {
+                tpdo.sync_count = 0;
+                self.send_tpdo(i, now)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn send_tpdo(&mut self, i: usize, now: Instant) -> Result<(), CanBusError> {
+        let tpdo = &mut self.tpdos[i];
+        tpdo.event_pending = false;
+        tpdo.last_sent = Some(now);
+        let Some(mapping) = &tpdo.mapping else {
+            return Ok(());
+        };
+        let mut values: HashMap<Address, Box<dyn EDSValue>> = HashMap::new();
+        for e in &mapping.entries {
+            if let Some(v) = self.dictionary.get(&e.address) {
+                values.insert(e.address, v.clone_box());
+            }
+        }
+        let Ok(payload) = mapping.pack(&self.dictionary.get_file().device_info, &values) else {
+            return Ok(());
+        };
+        let (cob_id, extended) = (tpdo.params.cob_id, tpdo.params.extended);
+        self.send(cob_id, extended, &payload)
+    }
+
+    fn process_timers(&mut self, now: Instant) -> Result<(), CanBusError> {
+        if self.state == NmtState::Initialising {
+            return Ok(());
+        }
+        if self.heartbeat_ms != 0 {
+            let period = Duration::from_millis(self.heartbeat_ms);
+            let due = self
+                .last_heartbeat
+                .is_none_or(|t| now.duration_since(t) >= period);
+            if due {
+                self.last_heartbeat = Some(now);
+                let code = self.state.get_heartbeat_code();
+                self.send(HEARTBEAT_COB_ID_BASE + self.node_id as u32, false, &[code])?;
+            }
+        }
+        if self.state != NmtState::Operational {
+            return Ok(());
+        }
+        for i in 0..self.tpdos.len() {
+            let tpdo = &mut self.tpdos[i];
+            if !tpdo.params.is_event_driven() {
+                continue;
+            }
+            let timer = Duration::from_millis(tpdo.params.event_timer_ms as u64);
+            if !timer.is_zero()
+                && tpdo
+                    .last_sent
+                    .is_none_or(|t| now.duration_since(t) >= timer)
+            {
+                tpdo.event_pending = true;
+            }
+            let inhibit = Duration::from_micros(tpdo.params.inhibit_100us as u64 * 100);
+            let inhibited = tpdo
+                .last_sent
+                .is_some_and(|t| now.duration_since(t) < inhibit);
+            if tpdo.event_pending && !inhibited {
+                self.send_tpdo(i, now)?;
+            }
+        }
+        Ok(())
+    }
+}
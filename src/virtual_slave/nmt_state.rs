@@ -0,0 +1,38 @@
+use std::fmt::Display;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum NmtState {
+    Initialising,
+    PreOperational,
+    Operational,
+    Stopped,
+}
+
+impl Display for NmtState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self {
+            NmtState::Initialising => write!(f, "initialising"),
+            NmtState::PreOperational => write!(f, "pre-operational"),
+            NmtState::Operational => write!(f, "operational"),
+            NmtState::Stopped => write!(f, "stopped"),
+        }
+    }
+}
+
+impl NmtState {
+    //State as sent in heartbeat messages, boot-up uses the initialising code
+    pub fn get_heartbeat_code(&self) -> u8 {
+        match &self {
+            NmtState::Initialising => 0,
+            NmtState::Stopped => 4,
+            NmtState::Operational => 5,
+            NmtState::PreOperational => 127,
+        }
+    }
+}
+
+pub const NMT_START: u8 = 0x01;
+pub const NMT_STOP: u8 = 0x02;
+pub const NMT_ENTER_PRE_OPERATIONAL: u8 = 0x80;
+pub const NMT_RESET_NODE: u8 = 0x81;
+pub const NMT_RESET_COMMUNICATION: u8 = 0x82;
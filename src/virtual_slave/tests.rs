@@ -0,0 +1,233 @@
+use std::time::{Duration, Instant};
+
+use crate::ObjectDictionaryError;
+use crate::can_bus::{CanBus, CanFrame, LoopbackBus, LoopbackNode};
+use crate::eds_file::{Address, DataType, eds_value_to_u64};
+use crate::sdo::{SdoAbortCode, SdoClient, SdoTransfer};
+use crate::tests::utils::*;
+use crate::virtual_slave::{NmtState, VirtualSlave};
+
+const TIMEOUT: Duration = Duration::from_millis(100);
+
+fn make_slave() -> (LoopbackNode, VirtualSlave<LoopbackNode>) {
+    let bus = LoopbackBus::new();
+    let master = bus.connect();
+    let dcf = make_dcf_file(&PDO_DCF.to_vec()).expect("Failed to parse dcf file!");
+    let slave = VirtualSlave::new(&dcf, bus.connect()).expect("Failed to create slave!");
+    (master, slave)
+}
+
+fn expect_frame(master: &mut LoopbackNode) -> CanFrame {
+    master
+        .recv(TIMEOUT)
+        .expect("Failed to receive frame!")
+        .expect("Missing frame!")
+}
+
+fn expect_silence(master: &mut LoopbackNode) {
+    assert_eq!(master.recv(Duration::ZERO), Ok(None));
+}
+
+fn sdo_transfer(
+    master: &mut LoopbackNode,
+    slave: &mut VirtualSlave<LoopbackNode>,
+    client: &mut SdoClient,
+    first: [u8; 8],
+    now: Instant,
+) -> SdoTransfer {
+    let mut requests = vec![first];
+    loop {
+        for r in &requests {
+            master
+                .send(&CanFrame::new(0x602, r))
+                .expect("Failed to send frame!");
+        }
+        slave.poll(now).expect("Failed to poll slave!");
+        requests.clear();
+        while let Some(frame) = master.recv(Duration::ZERO).expect("Failed to receive!") {
+            assert_eq!(frame.cob_id, 0x582);
+            let response: [u8; 8] = frame.data.try_into().expect("Invalid SDO frame!");
+            match client.handle_frame(&response) {
+                SdoTransfer::Pending(frames) => requests.extend(frames),
+                done => return done,
+            }
+        }
+    }
+}
+
+#[test]
+fn test_nmt_and_heartbeat() {
+    let (mut master, mut slave) = make_slave();
+    let t0 = Instant::now();
+    assert_eq!(slave.get_state(), NmtState::Initialising);
+    slave.start(t0).expect("Failed to start slave!");
+    assert_eq!(expect_frame(&mut master), CanFrame::new(0x702, &[0]));
+
+    slave
+        .poll(t0 + Duration::from_millis(50))
+        .expect("Failed to poll slave!");
+    expect_silence(&mut master);
+    slave
+        .poll(t0 + Duration::from_millis(100))
+        .expect("Failed to poll slave!");
+    assert_eq!(expect_frame(&mut master), CanFrame::new(0x702, &[127]));
+
+    //Commands for other nodes are ignored
+    master
+        .send(&CanFrame::new(0, &[0x02, 3]))
+        .expect("Failed to send!");
+    slave
+        .poll(t0 + Duration::from_millis(110))
+        .expect("Failed to poll slave!");
+    assert_eq!(slave.get_state(), NmtState::PreOperational);
+    master
+        .send(&CanFrame::new(0, &[0x02, 0]))
+        .expect("Failed to send!");
+    slave
+        .poll(t0 + Duration::from_millis(200))
+        .expect("Failed to poll slave!");
+    assert_eq!(slave.get_state(), NmtState::Stopped);
+    assert_eq!(expect_frame(&mut master), CanFrame::new(0x702, &[4]));
+
+    //No SDO in stopped
+    master
+        .send(&CanFrame::new(0x602, &[0x40, 0x17, 0x10, 0, 0, 0, 0, 0]))
+        .expect("Failed to send!");
+    slave
+        .poll(t0 + Duration::from_millis(210))
+        .expect("Failed to poll slave!");
+    expect_silence(&mut master);
+
+    master
+        .send(&CanFrame::new(0, &[0x81, 2]))
+        .expect("Failed to send!");
+    slave
+        .poll(t0 + Duration::from_millis(220))
+        .expect("Failed to poll slave!");
+    assert_eq!(slave.get_state(), NmtState::PreOperational);
+    assert_eq!(expect_frame(&mut master), CanFrame::new(0x702, &[0]));
+}
+
+#[test]
+fn test_sdo_access() {
+    let (mut master, mut slave) = make_slave();
+    let t0 = Instant::now();
+    slave.start(t0).expect("Failed to start slave!");
+    expect_frame(&mut master);
+    let mut client = SdoClient::new();
+    let heartbeat = Address::new(0x1017, 0);
+
+    let frame = client.upload(&heartbeat);
+    let result = sdo_transfer(&mut master, &mut slave, &mut client, frame, t0);
+    assert_eq!(
+        result,
+        SdoTransfer::Finished {
+            frames: Vec::new(),
+            data: vec![100, 0]
+        }
+    );
+
+    let frame = client.download(&Address::new(0x2000, 0), &[1, 0]);
+    let result = sdo_transfer(&mut master, &mut slave, &mut client, frame, t0);
+    assert!(matches!(
+        result,
+        SdoTransfer::Aborted {
+            frames: _,
+            code: SdoAbortCode::WriteReadOnly
+        }
+    ));
+
+    //A shorter heartbeat period is used right away
+    let frame = client.download(&heartbeat, &20u16.to_le_bytes());
+    let result = sdo_transfer(&mut master, &mut slave, &mut client, frame, t0);
+    assert!(matches!(result, SdoTransfer::Finished { .. }));
+    assert_eq!(
+        slave
+            .get_value(&heartbeat)
+            .and_then(|v| eds_value_to_u64(v, None)),
+        Some(20)
+    );
+    slave
+        .poll(t0 + Duration::from_millis(20))
+        .expect("Failed to poll slave!");
+    assert_eq!(expect_frame(&mut master), CanFrame::new(0x702, &[127]));
+
+    //Reset communication restores the communication parameters
+    master
+        .send(&CanFrame::new(0, &[0x82, 0]))
+        .expect("Failed to send!");
+    slave
+        .poll(t0 + Duration::from_millis(30))
+        .expect("Failed to poll slave!");
+    assert_eq!(expect_frame(&mut master), CanFrame::new(0x702, &[0]));
+    assert_eq!(
+        slave
+            .get_value(&heartbeat)
+            .and_then(|v| eds_value_to_u64(v, None)),
+        Some(100)
+    );
+}
+
+#[test]
+fn test_tpdo() {
+    let (mut master, mut slave) = make_slave();
+    let t0 = Instant::now();
+    slave.start(t0).expect("Failed to start slave!");
+    expect_frame(&mut master);
+    let speed = Address::new(0x2000, 0);
+    assert_eq!(
+        slave.set_value(&speed, Box::new(0x1234u32)),
+        Err(ObjectDictionaryError::TypeMismatch {
+            addr: speed,
+            data_type: DataType::UInt16
+        })
+    );
+    assert!(slave.get_value(&speed).is_none());
+    slave
+        .set_value(&speed, Box::new(0x1234u16))
+        .expect("Failed to set value!");
+
+    //No PDOs before operational
+    master
+        .send(&CanFrame::new(0x80, &[]))
+        .expect("Failed to send!");
+    slave.poll(t0).expect("Failed to poll slave!");
+    expect_silence(&mut master);
+
+    master
+        .send(&CanFrame::new(0, &[0x01, 2]))
+        .expect("Failed to send!");
+    slave.poll(t0).expect("Failed to poll slave!");
+    assert_eq!(slave.get_state(), NmtState::Operational);
+
+    //Transmission type 2 sends on every second SYNC
+    master
+        .send(&CanFrame::new(0x80, &[]))
+        .expect("Failed to send!");
+    slave.poll(t0).expect("Failed to poll slave!");
+    expect_silence(&mut master);
+    master
+        .send(&CanFrame::new(0x80, &[]))
+        .expect("Failed to send!");
+    slave.poll(t0).expect("Failed to poll slave!");
+    assert_eq!(
+        expect_frame(&mut master),
+        CanFrame::new(0x182, &[0x34, 0x12])
+    );
+
+    //Switching to event driven sends on every change
+    let mut client = SdoClient::new();
+    let frame = client.download(&Address::new(0x1800, 2), &[254]);
+    let result = sdo_transfer(&mut master, &mut slave, &mut client, frame, t0);
+    assert!(matches!(result, SdoTransfer::Finished { .. }));
+    slave.poll(t0).expect("Failed to poll slave!");
+    expect_silence(&mut master);
+    slave
+        .set_value(&speed, Box::new(0x0042u16))
+        .expect("Failed to set value!");
+    slave.poll(t0).expect("Failed to poll slave!");
+    assert_eq!(
+        expect_frame(&mut master),
+        CanFrame::new(0x182, &[0x42, 0x00])
+    );
+}